@@ -1,41 +1,93 @@
-use crate::dsp::{a_weighting, ema_precomputed, ema_tc};
+use crate::{
+    dsp::{a_weighting, ema_precomputed, ema_tc},
+    filterbank::Tri,
+    noise::subtract_noise,
+};
 
-use super::SpectrumAnalyzer;
+use super::{
+    BandParams, DynamicsParams, ScalingMode, ScalingParams,
+    SpectrumAnalyzer,
+};
+
+/// Hann coherent gain and the one-sided spectrum each halve a sine's
+/// bin amplitude, so a full-scale sine reads 0 dBFS after this.
+const FULL_SCALE_GAIN: f32 = 4.0;
 
 pub fn analyze_bands(
     sa: &mut SpectrumAnalyzer,
+    params: &BandParams,
     dt_s: f32,
     gate_open: bool,
 ) {
+    let BandParams { scaling, dynamics } = params;
     let filters_len = sa.filters.len();
-    accumulate_band_db(sa, dt_s, filters_len);
-    update_db_range(sa, filters_len, dt_s);
-    normalise_targets(sa, filters_len, gate_open);
+    let alpha_eq = (-dt_s / dynamics.eq_tau_s).exp();
+    accumulate_band_db(sa, scaling.mode, alpha_eq, filters_len);
+    update_db_range(sa, scaling, dynamics, dt_s);
+    if let Some(gates) = &mut sa.band_gates {
+        gates.update(dynamics, dt_s);
+        normalise_targets(sa, scaling.mode, dynamics, filters_len);
+        close_bands(sa);
+    } else if gate_open {
+        normalise_targets(sa, scaling.mode, dynamics, filters_len);
+    } else if let Some(targets) =
+        sa.bars_target.get_mut(..filters_len)
+    {
+        targets.fill(0.0);
+    }
+}
+
+fn close_bands(sa: &mut SpectrumAnalyzer) {
+    let Some(gates) = &sa.band_gates else {
+        return;
+    };
+    for (target, &open) in sa.bars_target.iter_mut().zip(gates.open())
+    {
+        if !open {
+            *target = 0.0;
+        }
+    }
 }
 
 fn accumulate_band_db(
     sa: &mut SpectrumAnalyzer,
-    dt_s: f32,
+    mode: ScalingMode,
+    alpha_eq: f32,
     filters_len: usize,
 ) {
-    let alpha_eq = (-dt_s / 6.0).exp();
-
     for (i, tri) in sa.filters.iter().enumerate().take(filters_len) {
         let mut acc = 0.0f32;
+        let mut noise = 0.0f32;
         for &(idx, wgt) in &tri.taps {
             if let Some(&val) = sa.spec_pow_smooth.get(idx) {
                 acc = val.mul_add(wgt, acc);
             }
+            if let Some(&val) = sa.noise_pow.get(idx) {
+                noise = val.mul_add(wgt, noise);
+            }
+        }
+        let acc = subtract_noise(acc, noise);
+        if let Some(gates) = &mut sa.band_gates {
+            gates.set_level(i, power_db(acc));
         }
         let amp_weighted = acc.sqrt() * a_weighting(tri.center_hz);
 
-        if let Some(eq) = sa.eq_ref.get_mut(i) {
-            *eq = ema_precomputed(*eq, amp_weighted, alpha_eq)
-                .max(1e-9);
-            let rel = amp_weighted / *eq;
-            if let Some(target) = sa.bars_target.get_mut(i) {
-                *target = 20.0 * rel.max(1e-12).log10();
+        let level = match mode {
+            ScalingMode::Auto => {
+                let Some(eq) = sa.eq_ref.get_mut(i) else {
+                    continue;
+                };
+                *eq = ema_precomputed(*eq, amp_weighted, alpha_eq)
+                    .max(1e-9);
+                amp_weighted / *eq
+            }
+            ScalingMode::Absolute | ScalingMode::Hybrid => {
+                amp_weighted * FULL_SCALE_GAIN
             }
+        };
+
+        if let Some(target) = sa.bars_target.get_mut(i) {
+            *target = 20.0 * level.max(1e-12).log10();
         }
     }
 
@@ -47,61 +99,165 @@ fn accumulate_band_db(
     }
 }
 
+fn update_db_range(
+    sa: &mut SpectrumAnalyzer,
+    scaling: &ScalingParams,
+    dynamics: &DynamicsParams,
+    dt_s: f32,
+) {
+    let (tau_low, tau_high) = match scaling.mode {
+        ScalingMode::Absolute => {
+            sa.db_low = scaling.floor_db;
+            sa.db_high = scaling.ceiling_db;
+            return;
+        }
+        ScalingMode::Auto => {
+            (dynamics.range_tau_low_s, dynamics.range_tau_high_s)
+        }
+        ScalingMode::Hybrid => {
+            (dynamics.hybrid_tau_s, dynamics.hybrid_tau_s)
+        }
+    };
+
+    if let Some((q10, q90)) =
+        band_percentiles(sa, dynamics, sa.filters.len())
+    {
+        sa.db_low = ema_tc(sa.db_low, q10, tau_low, dt_s);
+        sa.db_high = ema_tc(sa.db_high, q90, tau_high, dt_s);
+    }
+
+    if scaling.mode == ScalingMode::Hybrid {
+        sa.db_low =
+            sa.db_low.clamp(scaling.floor_db, scaling.ceiling_db);
+        sa.db_high =
+            sa.db_high.clamp(scaling.floor_db, scaling.ceiling_db);
+    }
+}
+
 #[allow(
     clippy::cast_precision_loss,
     clippy::cast_possible_truncation,
     clippy::cast_sign_loss
 )]
-fn update_db_range(sa: &mut SpectrumAnalyzer, len: usize, dt_s: f32) {
+fn band_percentiles(
+    sa: &mut SpectrumAnalyzer,
+    dynamics: &DynamicsParams,
+    len: usize,
+) -> Option<(f32, f32)> {
     if len == 0 {
-        return;
+        return None;
     }
 
     let len_f = len as f32;
-    let idx_low = ((len_f - 1.0) * 0.10).round().max(0.0) as usize;
-    let idx_high = ((len_f - 1.0) * 0.90).round().max(0.0) as usize;
+    let idx_low = ((len_f - 1.0) * dynamics.percentile_low)
+        .round()
+        .max(0.0) as usize;
+    let idx_high = ((len_f - 1.0) * dynamics.percentile_high)
+        .round()
+        .max(0.0) as usize;
 
-    let Some(slice) = sa.sort_scratch.get_mut(..len) else {
-        return;
-    };
+    let slice = sa.sort_scratch.get_mut(..len)?;
 
     slice.select_nth_unstable_by(idx_low, f32::total_cmp);
-    let q10 = slice.get(idx_low).copied();
+    let q_low = slice.get(idx_low).copied();
 
     #[allow(clippy::arithmetic_side_effects)]
     let split = idx_high.saturating_sub(idx_low);
     let (_, remaining) = slice.split_at_mut(idx_low);
     remaining.select_nth_unstable_by(split, f32::total_cmp);
-    let q90 = remaining.get(split).copied();
+    let q_high = remaining.get(split).copied();
 
-    if let (Some(q10), Some(q90)) = (q10, q90) {
-        sa.db_low = ema_tc(sa.db_low, q10, 0.30, dt_s);
-        sa.db_high = ema_tc(sa.db_high, q90, 0.50, dt_s);
-    }
+    q_low.zip(q_high)
 }
 
 fn normalise_targets(
     sa: &mut SpectrumAnalyzer,
+    mode: ScalingMode,
+    dynamics: &DynamicsParams,
     filters_len: usize,
-    gate_open: bool,
 ) {
-    let low = sa.db_low - 3.0;
-    let high = sa.db_high + 6.0;
-    let range_inv = 1.0 / (high - low).max(12.0);
-
-    if gate_open {
-        for i in 0..filters_len {
-            if let Some(target) = sa.bars_target.get_mut(i) {
-                let mut v = (*target - low) * range_inv;
-                v = v.clamp(0.0, 1.0).powf(0.85);
-                *target = 1.0 - (1.0 - v).powf(1.6);
-            }
-        }
-    } else if let Some(targets) =
-        sa.bars_target.get_mut(..filters_len)
-    {
-        for t in targets {
-            *t = 0.0;
-        }
+    let (low, range_inv) = display_range(sa, mode, dynamics);
+    let Some(targets) = sa.bars_target.get_mut(..filters_len) else {
+        return;
+    };
+    for target in targets {
+        *target = shape(*target, low, range_inv, dynamics);
     }
 }
+
+/// Bottom of the drawn range in dB and the inverse of its width.
+fn display_range(
+    sa: &SpectrumAnalyzer,
+    mode: ScalingMode,
+    dynamics: &DynamicsParams,
+) -> (f32, f32) {
+    let (low, high) = match mode {
+        ScalingMode::Absolute => (sa.db_low, sa.db_high),
+        ScalingMode::Auto | ScalingMode::Hybrid => (
+            sa.db_low + dynamics.margin_low_db,
+            sa.db_high + dynamics.margin_high_db,
+        ),
+    };
+    (low, 1.0 / (high - low).max(dynamics.min_range_db))
+}
+
+fn shape(
+    db: f32,
+    low: f32,
+    range_inv: f32,
+    dynamics: &DynamicsParams,
+) -> f32 {
+    let v = ((db - low) * range_inv)
+        .clamp(0.0, 1.0)
+        .powf(dynamics.shape_exp);
+    1.0 - (1.0 - v).powf(dynamics.ease_exp)
+}
+
+/// Unweighted level of one band in dBFS, a full-scale sine reading
+/// 0 dB.
+#[must_use]
+pub fn band_level_db(tri: &Tri, spec_pow: &[f32]) -> f32 {
+    let acc = tri.taps.iter().fold(0.0f32, |acc, &(idx, wgt)| {
+        spec_pow.get(idx).map_or(acc, |&val| val.mul_add(wgt, acc))
+    });
+    power_db(acc)
+}
+
+/// Band power in dBFS.
+fn power_db(power: f32) -> f32 {
+    10.0f32.mul_add(
+        power.max(1e-24).log10(),
+        20.0 * FULL_SCALE_GAIN.log10(),
+    )
+}
+
+/// Height a band would be drawn at if it read `level_db`, using the
+/// weighting, equalisation and range of the live bars.
+pub fn level_height(
+    sa: &SpectrumAnalyzer,
+    band: usize,
+    level_db: f32,
+    params: &BandParams,
+) -> f32 {
+    let BandParams { scaling, dynamics } = params;
+    let mode = scaling.mode;
+    let Some(tri) = sa.filters.get(band) else {
+        return 0.0;
+    };
+    let weighted = 20.0f32.mul_add(
+        a_weighting(tri.center_hz).max(1e-12).log10(),
+        level_db,
+    );
+    let db = match mode {
+        ScalingMode::Auto => {
+            let eq = sa.eq_ref.get(band).copied().unwrap_or(1.0);
+            20.0f32.mul_add(
+                -(eq * FULL_SCALE_GAIN).max(1e-12).log10(),
+                weighted,
+            )
+        }
+        ScalingMode::Absolute | ScalingMode::Hybrid => weighted,
+    };
+    let (low, range_inv) = display_range(sa, mode, dynamics);
+    shape(db, low, range_inv, dynamics)
+}
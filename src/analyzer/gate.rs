@@ -0,0 +1,216 @@
+use crate::dsp::ema_tc;
+
+use super::DynamicsParams;
+
+/// How far the close threshold sits below the open threshold.
+const HYSTERESIS_DB: f32 = 3.0;
+/// How long a band's quietest moment counts as its floor.
+pub const FLOOR_WINDOW_S: f32 = 8.0;
+/// The window is kept as the minima of this many equal parts.
+const SUBWINDOWS: usize = 8;
+/// Lowest level tracked, so silent bands stay finite.
+const LEVEL_MIN_DB: f32 = -200.0;
+
+/// A gate per band, each opening a margin above its own noise floor.
+///
+/// The floor is the minimum of the band's smoothed level over the
+/// last [`FLOOR_WINDOW_S`] seconds. Sound held for less than the
+/// window never lifts it, so sustained notes keep their band open.
+/// Once the quietest moment has left the window the floor drifts up
+/// slowly to the new minimum, so steady sound such as hum settles
+/// into it and closes the band. Floors start at the gate's minimum,
+/// and the window counts as that quiet until it has filled, so a
+/// band is never held shut by a sound it was already playing when
+/// the gates were created or reset.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BandGates {
+    min_db: f32,
+    level_db: Vec<f32>,
+    smooth_db: Vec<f32>,
+    floor_db: Vec<f32>,
+    /// Minimum of the smoothed level in the current part of the
+    /// window, per band.
+    part_min_db: Vec<f32>,
+    /// Minima of the finished parts, [`SUBWINDOWS`] per band.
+    window_db: Vec<f32>,
+    part: usize,
+    part_s: f32,
+    open: Vec<bool>,
+    below_s: Vec<f32>,
+}
+
+impl BandGates {
+    /// Gates that never open below `min_db`, whatever the floor.
+    #[must_use]
+    pub const fn new(min_db: f32) -> Self {
+        Self {
+            min_db,
+            level_db: Vec::new(),
+            smooth_db: Vec::new(),
+            floor_db: Vec::new(),
+            part_min_db: Vec::new(),
+            window_db: Vec::new(),
+            part: 0,
+            part_s: 0.0,
+            open: Vec::new(),
+            below_s: Vec::new(),
+        }
+    }
+
+    #[must_use]
+    pub const fn min_db(&self) -> f32 {
+        self.min_db
+    }
+
+    pub const fn set_min_db(&mut self, min_db: f32) {
+        self.min_db = min_db;
+    }
+
+    /// Matches the number of bands, forgetting the floors when it
+    /// changes.
+    #[allow(clippy::arithmetic_side_effects)]
+    pub fn resize(&mut self, bands: usize) {
+        if self.open.len() != bands {
+            self.level_db = vec![f32::NEG_INFINITY; bands];
+            self.smooth_db = vec![self.min_db; bands];
+            self.floor_db = vec![self.min_db; bands];
+            self.part_min_db = vec![f32::INFINITY; bands];
+            self.window_db = vec![self.min_db; bands * SUBWINDOWS];
+            self.part = 0;
+            self.part_s = 0.0;
+            self.open = vec![false; bands];
+            self.below_s = vec![0.0; bands];
+        }
+    }
+
+    /// Drops every floor and the whole window to `min_db`, so a band
+    /// already playing a steady sound opens and then settles.
+    pub fn reset(&mut self) {
+        self.smooth_db.fill(self.min_db);
+        self.floor_db.fill(self.min_db);
+        self.part_min_db.fill(f32::INFINITY);
+        self.window_db.fill(self.min_db);
+        self.part = 0;
+        self.part_s = 0.0;
+        self.open.fill(false);
+        self.below_s.fill(0.0);
+    }
+
+    /// Level of each band in dBFS, as of the last update.
+    #[must_use]
+    pub fn levels_db(&self) -> &[f32] {
+        &self.level_db
+    }
+
+    /// Noise floor of each band in dBFS.
+    #[must_use]
+    pub fn floors_db(&self) -> &[f32] {
+        &self.floor_db
+    }
+
+    /// Which bands are open.
+    #[must_use]
+    pub fn open(&self) -> &[bool] {
+        &self.open
+    }
+
+    #[must_use]
+    pub fn is_open(&self, band: usize) -> bool {
+        self.open.get(band).copied().unwrap_or(false)
+    }
+
+    pub(crate) fn set_level(&mut self, band: usize, db: f32) {
+        if let Some(level) = self.level_db.get_mut(band) {
+            *level = db;
+        }
+    }
+
+    /// Tracks the minimum of each band's smoothed level, moves the
+    /// floor to it and opens or closes the band against the floor.
+    pub fn update(&mut self, dynamics: &DynamicsParams, dt_s: f32) {
+        self.track_minima(dynamics, dt_s);
+
+        for (band, ((&level, floor), (open, below_s))) in self
+            .level_db
+            .iter()
+            .zip(self.floor_db.iter_mut())
+            .zip(self.open.iter_mut().zip(self.below_s.iter_mut()))
+            .enumerate()
+        {
+            let window_min = self
+                .window_db
+                .chunks_exact(SUBWINDOWS)
+                .nth(band)
+                .into_iter()
+                .flatten()
+                .chain(self.part_min_db.get(band))
+                .copied()
+                .fold(f32::INFINITY, f32::min);
+            *floor = if window_min < *floor {
+                window_min
+            } else {
+                ema_tc(
+                    *floor,
+                    window_min,
+                    dynamics.band_floor_rise_s,
+                    dt_s,
+                )
+            };
+
+            let open_db = (*floor + dynamics.band_gate_margin_db)
+                .max(self.min_db);
+            if !*open {
+                *below_s = 0.0;
+                *open = level > open_db;
+            } else if level < open_db - HYSTERESIS_DB {
+                *below_s += dt_s;
+                if *below_s >= dynamics.gate_confirm_s {
+                    *open = false;
+                    *below_s = 0.0;
+                }
+            } else {
+                *below_s = 0.0;
+            }
+        }
+    }
+
+    /// Smooths each level and folds it into the current part of the
+    /// window, moving on to the next part when this one is full.
+    #[allow(
+        clippy::cast_precision_loss,
+        clippy::arithmetic_side_effects
+    )]
+    fn track_minima(&mut self, dynamics: &DynamicsParams, dt_s: f32) {
+        for ((&level, smooth), part_min) in self
+            .level_db
+            .iter()
+            .zip(self.smooth_db.iter_mut())
+            .zip(self.part_min_db.iter_mut())
+        {
+            *smooth = ema_tc(
+                *smooth,
+                level.max(LEVEL_MIN_DB),
+                dynamics.band_floor_fall_s,
+                dt_s,
+            );
+            *part_min = part_min.min(*smooth);
+        }
+
+        self.part_s += dt_s;
+        if self.part_s < FLOOR_WINDOW_S / SUBWINDOWS as f32 {
+            return;
+        }
+        self.part_s = 0.0;
+        for (parts, part_min) in self
+            .window_db
+            .chunks_exact_mut(SUBWINDOWS)
+            .zip(self.part_min_db.iter_mut())
+        {
+            if let Some(slot) = parts.get_mut(self.part) {
+                *slot = *part_min;
+            }
+            *part_min = f32::INFINITY;
+        }
+        self.part = (self.part + 1) % SUBWINDOWS;
+    }
+}
@@ -1,9 +1,18 @@
 mod bands;
+mod gate;
 mod params;
+mod peaks;
+mod physics;
 mod spectrum;
 mod spring;
 
-pub use params::FlowSpringParams;
+pub use bands::band_level_db;
+pub use gate::BandGates;
+pub use params::{
+    BandParams, DynamicsParams, FlowSpringParams, PeakParams,
+    PhysicsModel, ScalingMode, ScalingParams, SmoothingParams,
+};
+pub use physics::{MAX_ACCUM_S, PHYSICS_STEP_S};
 
 use crate::filterbank::Tri;
 
@@ -12,7 +21,23 @@ pub struct SpectrumAnalyzer {
     pub filters: Vec<Tri>,
     pub bars_y: Vec<f32>,
     pub bars_v: Vec<f32>,
+    /// Physics state before the most recent fixed step.
+    pub bars_prev: Vec<f32>,
+    /// `bars_prev` blended toward `bars_y` by the leftover time, for
+    /// drawing.
+    pub bars_render: Vec<f32>,
+    pub physics_acc_s: f32,
+    pub peak_y: Vec<f32>,
+    pub peak_v: Vec<f32>,
+    pub peak_hold_s: Vec<f32>,
     pub eq_ref: Vec<f32>,
+    /// Per-bin noise power subtracted before band levels are taken.
+    /// Empty disables subtraction.
+    pub noise_pow: Vec<f32>,
+    /// Gates per band, used instead of the single gate passed to
+    /// [`Self::analyze_bands`] and [`Self::apply_flow_and_spring`].
+    /// `None` keeps the single gate.
+    pub band_gates: Option<BandGates>,
     pub db_low: f32,
     pub db_high: f32,
     pub(crate) sort_scratch: Vec<f32>,
@@ -30,7 +55,15 @@ impl SpectrumAnalyzer {
             filters: Vec::new(),
             bars_y: Vec::new(),
             bars_v: Vec::new(),
+            bars_prev: Vec::new(),
+            bars_render: Vec::new(),
+            physics_acc_s: 0.0,
+            peak_y: Vec::new(),
+            peak_v: Vec::new(),
+            peak_hold_s: Vec::new(),
             eq_ref: Vec::new(),
+            noise_pow: Vec::new(),
+            band_gates: None,
             db_low: -60.0,
             db_high: -20.0,
             sort_scratch: Vec::new(),
@@ -46,6 +79,11 @@ impl SpectrumAnalyzer {
         if self.bars_y.len() != num_bars {
             self.bars_y = vec![0.0; num_bars];
             self.bars_v = vec![0.0; num_bars];
+            self.bars_prev = vec![0.0; num_bars];
+            self.bars_render = vec![0.0; num_bars];
+            self.peak_y = vec![0.0; num_bars];
+            self.peak_v = vec![0.0; num_bars];
+            self.peak_hold_s = vec![0.0; num_bars];
             self.eq_ref = vec![1e-6; num_bars];
             self.bars_target = vec![0.0; num_bars];
             self.sort_scratch = vec![0.0; num_bars];
@@ -53,19 +91,55 @@ impl SpectrumAnalyzer {
             self.render_fulls = vec![0; num_bars];
             self.render_fracs = vec![0.0; num_bars];
         }
+        if let Some(gates) = &mut self.band_gates {
+            gates.resize(num_bars);
+        }
+    }
+
+    /// Gates each band on its own above `min_db`, or goes back to the
+    /// single gate with `None`. Band floors carry over while gating
+    /// stays on.
+    pub fn set_band_gating(&mut self, min_db: Option<f32>) {
+        match (&mut self.band_gates, min_db) {
+            (Some(gates), Some(db)) => gates.set_min_db(db),
+            (slot, db) => {
+                *slot = db.map(|db| {
+                    let mut gates = BandGates::new(db);
+                    gates.resize(self.bars_y.len());
+                    gates
+                });
+            }
+        }
     }
 
     pub fn update_spectrum(
         &mut self,
         spec_pow: &[f32],
-        tau_spec: f32,
+        params: &SmoothingParams,
         dt_s: f32,
     ) {
-        spectrum::update_spectrum(self, spec_pow, tau_spec, dt_s);
+        spectrum::update_spectrum(self, spec_pow, params, dt_s);
     }
 
-    pub fn analyze_bands(&mut self, dt_s: f32, gate_open: bool) {
-        bands::analyze_bands(self, dt_s, gate_open);
+    pub fn analyze_bands(
+        &mut self,
+        params: &BandParams,
+        dt_s: f32,
+        gate_open: bool,
+    ) {
+        bands::analyze_bands(self, params, dt_s, gate_open);
+    }
+
+    /// Height band `band` would be drawn at if it read `level_db`
+    /// dBFS, for lines drawn over the live bars.
+    #[must_use]
+    pub fn level_height(
+        &self,
+        band: usize,
+        level_db: f32,
+        params: &BandParams,
+    ) -> f32 {
+        bands::level_height(self, band, level_db, params)
     }
 
     pub fn apply_flow_and_spring(
@@ -74,6 +148,10 @@ impl SpectrumAnalyzer {
         dt_s: f32,
         gate_open: bool,
     ) {
-        spring::apply_flow_and_spring(self, params, dt_s, gate_open);
+        physics::apply(self, params, dt_s, gate_open);
+    }
+
+    pub fn update_peaks(&mut self, params: &PeakParams, dt_s: f32) {
+        peaks::update_peaks(self, params, dt_s);
     }
 }
@@ -1,5 +1,126 @@
+use serde::Deserialize;
+
+/// How bar heights chase their targets.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PhysicsModel {
+    /// Lateral diffusion plus a damped spring.
+    Spring,
+    /// Instant rise, accelerating fall.
+    Gravity,
+    /// First-order smoothing toward the target.
+    Ema,
+    /// Neighbour smoothing that spreads peaks sideways, then gravity.
+    Monstercat,
+    /// Damped spring whose stiffness grows with band frequency.
+    BandSpring,
+}
+
 pub struct FlowSpringParams {
+    pub model: PhysicsModel,
     pub flow_k: f32,
     pub spr_k: f32,
     pub spr_zeta: f32,
+    pub gravity: f32,
+    pub ema_tau_s: f32,
+    pub monstercat: f32,
+    pub silence_tau_s: f32,
+}
+
+/// Peak cap hold time in seconds and fall acceleration in screen
+/// heights per second squared.
+pub struct PeakParams {
+    pub hold_s: f32,
+    pub gravity: f32,
+}
+
+/// Spectrum attack/release time constants.
+///
+/// An `attack_tau` of zero snaps upward instantly. `tilt` scales both
+/// with frequency as `(1 kHz / f)^tilt`, so positive values smooth
+/// bass more and negative values smooth treble more.
+pub struct SmoothingParams {
+    pub attack_tau: f32,
+    pub release_tau: f32,
+    pub tilt: f32,
+    pub bin_hz: f32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ScalingMode {
+    Auto,
+    Absolute,
+    Hybrid,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ScalingParams {
+    pub mode: ScalingMode,
+    pub floor_db: f32,
+    pub ceiling_db: f32,
+}
+
+/// Time constants and shaping used by the adaptive band dynamics,
+/// the noise gates and the silence decay.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DynamicsParams {
+    pub eq_tau_s: f32,
+    pub percentile_low: f32,
+    pub percentile_high: f32,
+    pub range_tau_low_s: f32,
+    pub range_tau_high_s: f32,
+    pub hybrid_tau_s: f32,
+    pub margin_low_db: f32,
+    pub margin_high_db: f32,
+    pub min_range_db: f32,
+    pub shape_exp: f32,
+    pub ease_exp: f32,
+    pub gate_attack_s: f32,
+    pub gate_release_s: f32,
+    pub gate_confirm_s: f32,
+    pub silence_tau_s: f32,
+    /// How fast a band's noise floor drifts up once its quietest
+    /// moment has left the window.
+    pub band_floor_rise_s: f32,
+    /// Smoothing of the band level whose minimum is the floor.
+    pub band_floor_fall_s: f32,
+    /// How far above its floor a band's gate opens.
+    pub band_gate_margin_db: f32,
+}
+
+impl DynamicsParams {
+    pub const DEFAULT: Self = Self {
+        eq_tau_s: 6.0,
+        percentile_low: 0.10,
+        percentile_high: 0.90,
+        range_tau_low_s: 0.30,
+        range_tau_high_s: 0.50,
+        hybrid_tau_s: 8.0,
+        margin_low_db: -3.0,
+        margin_high_db: 6.0,
+        min_range_db: 12.0,
+        shape_exp: 0.85,
+        ease_exp: 1.6,
+        gate_attack_s: 0.012,
+        gate_release_s: 0.22,
+        gate_confirm_s: 0.12,
+        silence_tau_s: 0.22,
+        band_floor_rise_s: 4.0,
+        band_floor_fall_s: 0.1,
+        band_gate_margin_db: 6.0,
+    };
+}
+
+impl Default for DynamicsParams {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+/// How band levels turn into bar targets.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BandParams {
+    pub scaling: ScalingParams,
+    pub dynamics: DynamicsParams,
 }
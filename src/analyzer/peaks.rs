@@ -0,0 +1,30 @@
+use super::{PeakParams, SpectrumAnalyzer};
+
+/// Raises each cap to the bar it sits on, holds it there for
+/// `hold_s`, then lets it fall with `gravity`.
+pub fn update_peaks(
+    sa: &mut SpectrumAnalyzer,
+    params: &PeakParams,
+    dt_s: f32,
+) {
+    for (i, &y) in sa.bars_y.iter().enumerate() {
+        let (Some(peak), Some(v), Some(hold)) = (
+            sa.peak_y.get_mut(i),
+            sa.peak_v.get_mut(i),
+            sa.peak_hold_s.get_mut(i),
+        ) else {
+            continue;
+        };
+
+        if y >= *peak {
+            *peak = y;
+            *v = 0.0;
+            *hold = params.hold_s;
+        } else if *hold > 0.0 {
+            *hold -= dt_s;
+        } else {
+            *v = params.gravity.mul_add(dt_s, *v);
+            *peak = (-*v).mul_add(dt_s, *peak).max(y);
+        }
+    }
+}
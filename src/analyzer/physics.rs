@@ -0,0 +1,165 @@
+use crate::dsp::ema_tc;
+
+use super::{
+    FlowSpringParams, PhysicsModel, SpectrumAnalyzer, spring,
+};
+
+/// Internal physics timestep. Frames advance the simulation by whole
+/// steps and carry the remainder, so motion does not depend on the
+/// frame rate.
+pub const PHYSICS_STEP_S: f32 = 1.0 / 240.0;
+
+/// Longest span simulated in one frame. Time beyond this after a
+/// stall is dropped instead of integrated.
+pub const MAX_ACCUM_S: f32 = 0.1;
+
+#[allow(
+    clippy::cast_possible_truncation,
+    clippy::cast_sign_loss,
+    clippy::cast_precision_loss
+)]
+pub fn apply(
+    sa: &mut SpectrumAnalyzer,
+    params: &FlowSpringParams,
+    dt_s: f32,
+    gate_open: bool,
+) {
+    if sa.bars_y.is_empty() {
+        return;
+    }
+
+    sa.physics_acc_s =
+        (sa.physics_acc_s + dt_s.max(0.0)).min(MAX_ACCUM_S);
+    let steps = (sa.physics_acc_s / PHYSICS_STEP_S).floor() as usize;
+    sa.physics_acc_s = (steps as f32)
+        .mul_add(-PHYSICS_STEP_S, sa.physics_acc_s)
+        .max(0.0);
+
+    for _ in 0..steps {
+        sa.bars_prev.clone_from(&sa.bars_y);
+        step(sa, params, PHYSICS_STEP_S, gate_open);
+    }
+
+    interpolate(sa, sa.physics_acc_s / PHYSICS_STEP_S);
+}
+
+fn interpolate(sa: &mut SpectrumAnalyzer, alpha: f32) {
+    for (out, (&prev, &cur)) in sa
+        .bars_render
+        .iter_mut()
+        .zip(sa.bars_prev.iter().zip(sa.bars_y.iter()))
+    {
+        *out = (cur - prev).mul_add(alpha, prev);
+    }
+}
+
+fn step(
+    sa: &mut SpectrumAnalyzer,
+    params: &FlowSpringParams,
+    dt_s: f32,
+    gate_open: bool,
+) {
+    let n = sa.bars_y.len();
+    if !gate_open && sa.band_gates.is_none() {
+        spring::decay_to_silence(sa, params.silence_tau_s, n, dt_s);
+        return;
+    }
+
+    match params.model {
+        PhysicsModel::Spring => {
+            spring::apply_flow_and_spring(sa, params, dt_s, n);
+        }
+        PhysicsModel::BandSpring => {
+            spring::apply_band_spring(sa, params, dt_s, n);
+        }
+        PhysicsModel::Gravity => {
+            copy_targets(sa, n);
+            fall_with_gravity(sa, params.gravity, dt_s, n);
+        }
+        PhysicsModel::Ema => {
+            smooth_toward_target(sa, params, dt_s, n);
+        }
+        PhysicsModel::Monstercat => {
+            spread_to_neighbours(sa, params.monstercat, n);
+            fall_with_gravity(sa, params.gravity, dt_s, n);
+        }
+    }
+    spring::decay_closed_bands(sa, params.silence_tau_s, dt_s);
+}
+
+fn copy_targets(sa: &mut SpectrumAnalyzer, n: usize) {
+    if let (Some(dst), Some(src)) =
+        (sa.flowed_scratch.get_mut(..n), sa.bars_target.get(..n))
+    {
+        dst.copy_from_slice(src);
+    }
+}
+
+/// Bars jump up to their target and fall back with constant
+/// acceleration. `bars_v` holds the current fall speed.
+fn fall_with_gravity(
+    sa: &mut SpectrumAnalyzer,
+    gravity: f32,
+    dt_s: f32,
+    n: usize,
+) {
+    for (y, (v, &target)) in sa
+        .bars_y
+        .iter_mut()
+        .zip(sa.bars_v.iter_mut().zip(sa.flowed_scratch.iter()))
+        .take(n)
+    {
+        if target >= *y {
+            *y = target;
+            *v = 0.0;
+        } else {
+            *v = gravity.mul_add(dt_s, *v);
+            *y = (-*v).mul_add(dt_s, *y).max(target);
+        }
+    }
+}
+
+fn smooth_toward_target(
+    sa: &mut SpectrumAnalyzer,
+    params: &FlowSpringParams,
+    dt_s: f32,
+    n: usize,
+) {
+    for (y, (v, &target)) in sa
+        .bars_y
+        .iter_mut()
+        .zip(sa.bars_v.iter_mut().zip(sa.bars_target.iter()))
+        .take(n)
+    {
+        *y = ema_tc(*y, target, params.ema_tau_s, dt_s)
+            .clamp(0.0, 1.0);
+        *v = 0.0;
+    }
+}
+
+/// Raises every band to at least `target[j] / factor^|i - j|` of each
+/// neighbour `j`, using one pass in each direction.
+fn spread_to_neighbours(
+    sa: &mut SpectrumAnalyzer,
+    factor: f32,
+    n: usize,
+) {
+    let inv = 1.0 / factor.max(1.0);
+    let (Some(out), Some(targets)) =
+        (sa.flowed_scratch.get_mut(..n), sa.bars_target.get(..n))
+    else {
+        return;
+    };
+
+    let mut carry = 0.0f32;
+    for (o, &t) in out.iter_mut().zip(targets) {
+        carry = (carry * inv).max(t);
+        *o = carry;
+    }
+
+    carry = 0.0;
+    for o in out.iter_mut().rev() {
+        carry = (carry * inv).max(*o);
+        *o = carry;
+    }
+}
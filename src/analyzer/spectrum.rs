@@ -1,23 +1,50 @@
 use crate::dsp::ema_tc;
 
-use super::SpectrumAnalyzer;
+use super::{SmoothingParams, SpectrumAnalyzer};
+
+const TILT_PIVOT_HZ: f32 = 1_000.0;
+const TILT_MIN_HZ: f32 = 20.0;
 
 pub fn update_spectrum(
     sa: &mut SpectrumAnalyzer,
     spec_pow: &[f32],
-    tau_spec: f32,
+    params: &SmoothingParams,
     dt_s: f32,
 ) {
     let len = sa.spec_pow_smooth.len().min(spec_pow.len());
     for (i, &pow) in spec_pow.iter().enumerate().take(len) {
         let incoming = pow.max(1e-12);
+        let scale = tau_scale(params, i);
         if let Some(prev_val) = sa.spec_pow_smooth.get_mut(i) {
             let prev = *prev_val;
             *prev_val = if incoming >= prev {
-                incoming
+                if params.attack_tau > 0.0 {
+                    ema_tc(
+                        prev,
+                        incoming,
+                        params.attack_tau * scale,
+                        dt_s,
+                    )
+                } else {
+                    incoming
+                }
             } else {
-                ema_tc(prev, incoming, tau_spec, dt_s)
+                ema_tc(
+                    prev,
+                    incoming,
+                    params.release_tau * scale,
+                    dt_s,
+                )
             };
         }
     }
 }
+
+#[allow(clippy::cast_precision_loss)]
+fn tau_scale(params: &SmoothingParams, bin: usize) -> f32 {
+    if params.tilt == 0.0 {
+        return 1.0;
+    }
+    let hz = (bin as f32 * params.bin_hz).max(TILT_MIN_HZ);
+    (TILT_PIVOT_HZ / hz).powf(params.tilt)
+}
@@ -1,27 +1,53 @@
 use super::{FlowSpringParams, SpectrumAnalyzer};
 
+/// Reference frequency at which the band spring uses `spr_k` as is.
+const BAND_SPRING_PIVOT_HZ: f32 = 1_000.0;
+
 pub fn apply_flow_and_spring(
     sa: &mut SpectrumAnalyzer,
     params: &FlowSpringParams,
     dt_s: f32,
-    gate_open: bool,
+    n: usize,
 ) {
-    let n = sa.bars_y.len();
-    if n == 0 {
-        return;
-    }
-
-    if !gate_open {
-        decay_to_silence(sa, n, dt_s);
-        return;
-    }
-
     diffuse_lateral_flow(sa, params, n);
     integrate_spring(sa, params, dt_s, n);
 }
 
-fn decay_to_silence(sa: &mut SpectrumAnalyzer, n: usize, dt_s: f32) {
-    let tau_silence = 0.22f32;
+/// Like [`apply_flow_and_spring`], but each band's stiffness scales
+/// as `(center / 1 kHz)^0.5`, so treble reacts faster than bass.
+pub fn apply_band_spring(
+    sa: &mut SpectrumAnalyzer,
+    params: &FlowSpringParams,
+    dt_s: f32,
+    n: usize,
+) {
+    diffuse_lateral_flow(sa, params, n);
+
+    for i in 0..n {
+        let k = sa.filters.get(i).map_or(params.spr_k, |tri| {
+            let ratio = tri.center_hz.max(1.0) / BAND_SPRING_PIVOT_HZ;
+            (params.spr_k * ratio.sqrt()).clamp(10.0, 800.0)
+        });
+        let c = 2.0 * k.sqrt() * params.spr_zeta;
+
+        if let (Some(y), Some(v), Some(&target)) = (
+            sa.bars_y.get_mut(i),
+            sa.bars_v.get_mut(i),
+            sa.flowed_scratch.get(i),
+        ) {
+            let a = k.mul_add(target - *y, -(c * *v));
+            *v = a.mul_add(dt_s, *v);
+            *y = (*v).mul_add(dt_s, *y).clamp(0.0, 1.0);
+        }
+    }
+}
+
+pub fn decay_to_silence(
+    sa: &mut SpectrumAnalyzer,
+    tau_silence: f32,
+    n: usize,
+    dt_s: f32,
+) {
     let a = (-dt_s / tau_silence).exp();
 
     for (y, v) in
@@ -35,6 +61,31 @@ fn decay_to_silence(sa: &mut SpectrumAnalyzer, n: usize, dt_s: f32) {
     }
 }
 
+/// Lets the bands whose gates are closed fall from where they were
+/// before this step, as [`decay_to_silence`] does for all of them.
+pub fn decay_closed_bands(
+    sa: &mut SpectrumAnalyzer,
+    tau_silence: f32,
+    dt_s: f32,
+) {
+    let Some(gates) = &sa.band_gates else {
+        return;
+    };
+    let a = (-dt_s / tau_silence).exp();
+
+    for ((y, v), (&prev, &open)) in sa
+        .bars_y
+        .iter_mut()
+        .zip(sa.bars_v.iter_mut())
+        .zip(sa.bars_prev.iter().zip(gates.open()))
+    {
+        if !open {
+            *y = if prev * a < 0.001 { 0.0 } else { prev * a };
+            *v = 0.0;
+        }
+    }
+}
+
 fn diffuse_lateral_flow(
     sa: &mut SpectrumAnalyzer,
     params: &FlowSpringParams,
@@ -44,7 +44,7 @@ pub fn run() -> Result<()> {
 
         if let Some(cfg) = config_watch.latest()? {
             terminal.set_color(cfg.color)?;
-            runtime.set_fft_size(cfg.fft_size);
+            runtime.set_fft_size(&cfg);
             frame.apply_config(&cfg, &runtime);
             clock.set_frame_ms(cfg.frame_ms);
         }
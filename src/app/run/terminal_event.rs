@@ -43,7 +43,22 @@ fn handle_key_event(
     match ctx.runtime.handle_key(code)? {
         InputAction::Quit => return Ok(TerminalAction::Quit),
         InputAction::AudioChanged => {
-            ctx.frame.clear_filters();
+            ctx.frame.audio_changed(ctx.runtime);
+        }
+        InputAction::Calibrate => ctx.frame.start_calibration(),
+        InputAction::NextView => ctx.frame.next_view(),
+        InputAction::NextBothDisplay => ctx.frame.next_both_display(),
+        InputAction::TogglePeaks => {
+            ctx.frame.toggle_peaks();
+            return Ok(TerminalAction::Continue);
+        }
+        InputAction::Reference(action) => {
+            ctx.frame.reference(action);
+            return Ok(TerminalAction::Continue);
+        }
+        InputAction::Spl(action) => {
+            ctx.frame.spl(action);
+            return Ok(TerminalAction::Continue);
         }
         InputAction::Continue => {}
     }
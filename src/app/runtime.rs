@@ -3,7 +3,7 @@ use crossterm::event::KeyCode;
 use lookas::{
     audio::{AudioController, AudioError, AudioMode},
     buffer::SharedBuf,
-    config::Config,
+    config::{Config, FftSize},
 };
 use std::sync::{Arc, Mutex};
 
@@ -14,11 +14,18 @@ mod input;
 pub(super) use frame::Frame;
 
 use input::{InputContext, KeyAction, handle_key};
+pub(super) use input::{ReferenceAction, SplAction};
 
 pub enum InputAction {
     Continue,
     Quit,
     AudioChanged,
+    Calibrate,
+    NextView,
+    NextBothDisplay,
+    TogglePeaks,
+    Reference(ReferenceAction),
+    Spl(SplAction),
 }
 
 pub enum StartupCapture {
@@ -30,8 +37,32 @@ pub struct RuntimeDiagnostics {
     pub startup_capture: StartupCapture,
 }
 
+/// The configured FFT size, with what `Auto` depends on besides the
+/// sample rate.
+#[derive(Clone, Copy)]
+struct FftRequest {
+    size: FftSize,
+    fmin: f32,
+    frame_ms: u64,
+}
+
+impl FftRequest {
+    const fn new(cfg: &Config) -> Self {
+        Self {
+            size: cfg.fft_size,
+            fmin: cfg.fmin,
+            frame_ms: cfg.frame_ms,
+        }
+    }
+
+    fn resolve(self, sample_rate: f32) -> usize {
+        self.size.resolve(self.fmin, sample_rate, self.frame_ms)
+    }
+}
+
 pub struct Runtime {
     fft_size: usize,
+    fft_request: FftRequest,
     audio: AudioController,
     mic_shared: Arc<Mutex<SharedBuf>>,
     sys_shared: Arc<Mutex<SharedBuf>>,
@@ -43,10 +74,11 @@ pub struct Runtime {
 
 impl Runtime {
     pub fn new(cfg: &Config) -> Result<Self> {
-        let fft_size = cfg.fft_size;
-        let cap = ring_cap(fft_size);
-        let mic_shared = Arc::new(Mutex::new(SharedBuf::new(cap)));
-        let sys_shared = Arc::new(Mutex::new(SharedBuf::new(cap)));
+        // The ring size depends on the sample rate, so capture starts
+        // into empty buffers, which drop samples, and they are sized
+        // once the device has reported its rate.
+        let mic_shared = Arc::new(Mutex::new(SharedBuf::new(0)));
+        let sys_shared = Arc::new(Mutex::new(SharedBuf::new(0)));
 
         let mut audio = AudioController::new();
         let startup_capture = match audio.start(
@@ -72,16 +104,20 @@ impl Runtime {
         #[allow(clippy::cast_precision_loss)]
         let sr = sr_u32 as f32;
 
-        Ok(Self {
-            fft_size,
+        let fft_request = FftRequest::new(cfg);
+        let mut runtime = Self {
+            fft_size: 0,
+            fft_request,
             audio,
             mic_shared,
             sys_shared,
             sr,
-            cap,
+            cap: 0,
             sr_u32,
             diagnostics,
-        })
+        };
+        runtime.resize(fft_request.resolve(sr));
+        Ok(runtime)
     }
 
     pub fn handle_key(
@@ -95,8 +131,25 @@ impl Runtime {
             ring_cap: self.cap,
         };
 
-        if matches!(handle_key(code, &mut ctx)?, KeyAction::Quit) {
-            return Ok(InputAction::Quit);
+        match handle_key(code, &mut ctx)? {
+            KeyAction::Quit => return Ok(InputAction::Quit),
+            KeyAction::Calibrate => {
+                return Ok(InputAction::Calibrate);
+            }
+            KeyAction::NextView => return Ok(InputAction::NextView),
+            KeyAction::NextBothDisplay => {
+                return Ok(InputAction::NextBothDisplay);
+            }
+            KeyAction::TogglePeaks => {
+                return Ok(InputAction::TogglePeaks);
+            }
+            KeyAction::Reference(action) => {
+                return Ok(InputAction::Reference(action));
+            }
+            KeyAction::Spl(action) => {
+                return Ok(InputAction::Spl(action));
+            }
+            KeyAction::Continue => {}
         }
 
         if self.update_sample_rate() {
@@ -106,7 +159,14 @@ impl Runtime {
         Ok(InputAction::Continue)
     }
 
-    pub fn set_fft_size(&mut self, fft_size: usize) {
+    /// Takes the FFT size from `cfg`, resolving `auto` at the current
+    /// sample rate.
+    pub fn set_fft_size(&mut self, cfg: &Config) {
+        self.fft_request = FftRequest::new(cfg);
+        self.resize(self.fft_request.resolve(self.sr));
+    }
+
+    fn resize(&mut self, fft_size: usize) {
         if fft_size == self.fft_size {
             return;
         }
@@ -128,6 +188,11 @@ impl Runtime {
         self.fft_size
     }
 
+    /// Samples each source's ring buffer holds.
+    pub const fn ring_cap(&self) -> usize {
+        self.cap
+    }
+
     pub const fn sample_rate(&self) -> f32 {
         self.sr
     }
@@ -140,18 +205,60 @@ impl Runtime {
         &self.diagnostics
     }
 
-    pub fn copy_mic_tail(&self, tail: &mut Vec<f32>) -> bool {
-        self.mic_shared
-            .try_lock()
-            .ok()
-            .is_some_and(|b| b.copy_last_n_into(self.fft_size, tail))
+    /// Copies every sample written after the write counter `since`,
+    /// and at least the latest `fft_size`, and returns the buffer's
+    /// write counter at the time of the copy. Samples that no longer
+    /// fit in the ring are lost.
+    pub fn copy_mic_since(
+        &self,
+        since: u64,
+        tail: &mut Vec<f32>,
+    ) -> Option<u64> {
+        copy_since(&self.mic_shared, since, self.fft_size, tail)
     }
 
-    pub fn copy_system_tail(&self, tail: &mut Vec<f32>) -> bool {
-        self.sys_shared
-            .try_lock()
-            .ok()
-            .is_some_and(|b| b.copy_last_n_into(self.fft_size, tail))
+    pub fn copy_system_since(
+        &self,
+        since: u64,
+        tail: &mut Vec<f32>,
+    ) -> Option<u64> {
+        copy_since(&self.sys_shared, since, self.fft_size, tail)
+    }
+
+    /// Copies the latest `n` samples, for analyses longer than one
+    /// FFT frame. `n` must not exceed [`Self::ring_cap`].
+    pub fn copy_mic_last(
+        &self,
+        n: usize,
+        tail: &mut Vec<f32>,
+    ) -> bool {
+        copy_tail(&self.mic_shared, n, tail).is_some()
+    }
+
+    pub fn copy_system_last(
+        &self,
+        n: usize,
+        tail: &mut Vec<f32>,
+    ) -> bool {
+        copy_tail(&self.sys_shared, n, tail).is_some()
+    }
+
+    /// Keeps the left and right channels of both sources while `on`,
+    /// for the stereo view. Other views leave them unallocated.
+    pub fn keep_stereo(&self, on: bool) {
+        for shared in [&self.mic_shared, &self.sys_shared] {
+            if let Ok(mut buffer) = shared.try_lock() {
+                buffer.set_stereo(on);
+            }
+        }
+    }
+
+    pub fn copy_mic_stereo(&self, tail: &mut StereoTail) -> bool {
+        copy_stereo(&self.mic_shared, self.fft_size, tail)
+    }
+
+    pub fn copy_system_stereo(&self, tail: &mut StereoTail) -> bool {
+        copy_stereo(&self.sys_shared, self.fft_size, tail)
     }
 
     fn update_sample_rate(&mut self) -> bool {
@@ -165,10 +272,47 @@ impl Runtime {
         {
             self.sr = self.sr_u32 as f32;
         }
+        self.resize(self.fft_request.resolve(self.sr));
         true
     }
 }
 
+fn copy_tail(
+    shared: &Mutex<SharedBuf>,
+    n: usize,
+    tail: &mut Vec<f32>,
+) -> Option<u64> {
+    let buffer = shared.try_lock().ok()?;
+    buffer.copy_last_n_into(n, tail).then(|| buffer.written())
+}
+
+fn copy_since(
+    shared: &Mutex<SharedBuf>,
+    since: u64,
+    min_n: usize,
+    tail: &mut Vec<f32>,
+) -> Option<u64> {
+    let buffer = shared.try_lock().ok()?;
+    let written = buffer.written();
+    let fresh = usize::try_from(written.wrapping_sub(since))
+        .unwrap_or(usize::MAX);
+    let n = fresh.min(buffer.len()).max(min_n);
+    buffer.copy_last_n_into(n, tail).then_some(written)
+}
+
+/// Left and right channel samples.
+pub type StereoTail = (Vec<f32>, Vec<f32>);
+
+fn copy_stereo(
+    shared: &Mutex<SharedBuf>,
+    n: usize,
+    (left, right): &mut StereoTail,
+) -> bool {
+    shared.try_lock().is_ok_and(|buffer| {
+        buffer.copy_last_n_stereo_into(n, left, right)
+    })
+}
+
 #[allow(clippy::arithmetic_side_effects)]
 fn ring_cap(fft_size: usize) -> usize {
     ((48_000usize / 10).max(fft_size * 3))
@@ -1,28 +1,81 @@
 use anyhow::Result;
 use lookas::{
-    analyzer::{FlowSpringParams, SpectrumAnalyzer},
-    config::Config,
-    filterbank::{FilterbankParams, build_filterbank},
+    analyzer::SpectrumAnalyzer,
+    audio::AudioMode,
+    beat::BeatTracker,
+    chroma::PITCH_CLASSES,
+    config::{BothDisplay, Config, GateMode, RgbColor, View},
+    dsp::ema_tc,
+    filterbank::{
+        FilterbankMode, FilterbankParams, build_filterbank,
+        build_note_filterbank,
+    },
+    loudness::{ChannelLayout, LoudnessMeter},
+    vad::{VadFeatures, VadInput, VadParams, VoiceDetector},
 };
 use std::io::Write;
 
+mod analysis;
+mod chroma;
 mod config;
+mod distortion;
+mod feedback;
 mod fft;
+mod meter;
+mod multires;
+mod noise;
 mod paint;
+mod partials;
+mod reference;
 mod samples;
+mod source;
+mod spl;
+mod stereo;
+mod tuner;
 
-use super::{Runtime, gate::GateState};
+use super::{
+    ReferenceAction, Runtime, SplAction, StereoTail, gate::GateState,
+};
+use analysis::Analysis;
+use chroma::FrameChroma;
 use config::FrameConfig;
-use fft::FftState;
-use paint::FramePaint;
+use distortion::FrameDistortion;
+use feedback::{FrameFeedback, HowlReport};
+use noise::{FrameNoise, NoiseFrame};
+use paint::{
+    FramePaint, LabelledDisplay, LinesDisplay, MeasurementDisplay,
+    PaintStyle, StereoDisplay, TunerDisplay,
+};
+use partials::{FramePartials, PartialsFrame};
+use reference::{FrameReference, ReferenceFrame};
 use samples::FrameSamples;
+use source::{SourcePipeline, Step, animate};
+use spl::FrameSpl;
+use stereo::{FrameStereo, StereoFrame};
+use tuner::FrameTuner;
 
 pub struct Frame {
     cfg: FrameConfig,
     analyzer: SpectrumAnalyzer,
     gate: GateState,
-    fft: FftState,
+    analysis: Analysis,
+    beat: BeatTracker,
+    pulse: f32,
+    loudness: LoudnessMeter,
+    meter_text: String,
+    spl: FrameSpl,
+    noise: FrameNoise,
+    feedback: FrameFeedback,
+    reference: FrameReference,
+    partials: FramePartials,
+    view: View,
+    chroma: FrameChroma,
+    tuner: FrameTuner,
+    stereo: FrameStereo,
+    distortion: FrameDistortion,
     samples: FrameSamples,
+    both: BothDisplay,
+    system: Option<SourcePipeline>,
     paint: FramePaint,
     dt_s: f32,
 }
@@ -35,14 +88,49 @@ impl Frame {
         h: u16,
     ) -> Self {
         let fft_size = runtime.fft_size();
-        let fft = FftState::new(fft_size);
+        let analysis = Analysis::new(cfg.analysis, fft_size);
+        let noise = FrameNoise::new(
+            cfg.calibrate_on_start.then_some(cfg.calibrate_s),
+        );
+        let gate = make_gate(cfg, &noise);
+        let frame_cfg = FrameConfig::new(cfg);
+        let mut analyzer = SpectrumAnalyzer::new(analysis.half());
+        analyzer.set_band_gating(band_gate_db(
+            cfg.gate_mode,
+            noise.gate_db(cfg.gate_db),
+        ));
 
         Self {
-            cfg: FrameConfig::new(cfg),
-            analyzer: SpectrumAnalyzer::new(fft.half),
-            gate: make_gate(cfg),
-            fft,
-            samples: FrameSamples::new(fft_size),
+            feedback: FrameFeedback::new(frame_cfg.feedback_params()),
+            partials: FramePartials::new(frame_cfg.peak_readout),
+            reference: FrameReference::new(
+                frame_cfg.ltas_window,
+                frame_cfg.ltas,
+            ),
+            tuner: FrameTuner::new(frame_cfg.tuning()),
+            spl: FrameSpl::new(
+                frame_cfg.spl_params(),
+                frame_cfg.spl_reference_db,
+            ),
+            cfg: frame_cfg,
+            analyzer,
+            gate,
+            analysis,
+            beat: BeatTracker::new(),
+            pulse: 0.0,
+            loudness: LoudnessMeter::new(
+                runtime.sample_rate(),
+                ChannelLayout::DualMono,
+            ),
+            meter_text: String::new(),
+            noise,
+            view: cfg.view,
+            chroma: FrameChroma::new(),
+            stereo: FrameStereo::new(fft_size),
+            distortion: FrameDistortion::new(),
+            samples: FrameSamples::new(fft_size, cfg.preprocess),
+            both: cfg.both_display,
+            system: None,
             paint: FramePaint::new(w, h),
             dt_s: 0.0,
         }
@@ -54,30 +142,100 @@ impl Frame {
 
     pub fn reset_gate(&mut self) {
         self.gate.reset();
+        if let Some(gates) = &mut self.analyzer.band_gates {
+            gates.reset();
+        }
+        if let Some(system) = &mut self.system {
+            system.reset_gate();
+        }
     }
 
     pub fn apply_config(&mut self, cfg: &Config, runtime: &Runtime) {
         let filterbank_changed = self.cfg.filterbank_changed(cfg);
+        let analysis_changed = self.cfg.analysis_changed(cfg);
         let fft_size = runtime.fft_size();
         let fft_changed = self.samples.len() != fft_size;
 
+        if self.cfg.physics != cfg.physics {
+            self.analyzer.bars_v.fill(0.0);
+            if let Some(system) = &mut self.system {
+                system.analyzer.bars_v.fill(0.0);
+            }
+        }
+        if self.cfg.view != cfg.view {
+            self.set_view(cfg.view);
+        }
+        if self.cfg.both_display != cfg.both_display {
+            self.both = cfg.both_display;
+        }
+        if self.cfg.ltas != cfg.ltas {
+            self.reference.show_ltas(cfg.ltas);
+        }
+        if self.cfg.peak_readout != cfg.peak_readout {
+            self.partials.show(cfg.peak_readout);
+        }
+        if self.cfg.both_display != cfg.both_display
+            || fft_changed
+            || filterbank_changed
+            || analysis_changed
+        {
+            self.system = None;
+        }
+
         self.cfg.apply(cfg);
-        self.gate.open_db = cfg.gate_db;
-        self.gate.close_db = (cfg.gate_db - 3.0).max(-80.0);
+        self.reference.set_window(self.cfg.ltas_window);
+        self.samples.set_preprocess(cfg.preprocess);
+        if let Some(system) = &mut self.system {
+            system.set_preprocess(cfg.preprocess);
+        }
+        self.tuner.set_tuning(self.cfg.tuning());
+        self.spl.configure(
+            self.cfg.spl_params(),
+            self.cfg.spl_reference_db,
+        );
+        self.feedback.set_params(self.cfg.feedback_params());
+        if !self.cfg.feedback_detect {
+            self.feedback.reset();
+        }
+        self.configure_gate();
+        self.install_noise(runtime);
 
         if fft_changed {
-            self.fft.resize(fft_size);
-            self.samples.resize(fft_size);
-            self.analyzer.spec_pow_smooth = vec![0.0; self.fft.half];
-            self.reset_gate();
+            self.resize_fft(fft_size);
+        } else if analysis_changed {
+            self.analysis = Analysis::new(cfg.analysis, fft_size);
         }
 
-        if filterbank_changed || fft_changed {
+        if filterbank_changed {
             self.clear_filters();
-            self.analyzer.eq_ref.fill(1e-6);
-            self.analyzer.db_low = -60.0;
-            self.analyzer.db_high = -20.0;
+            self.reset_levels();
+        }
+    }
+
+    /// Follows a new capture device or sample rate, which may have
+    /// changed the FFT size.
+    pub fn audio_changed(&mut self, runtime: &Runtime) {
+        if self.samples.len() != runtime.fft_size() {
+            self.system = None;
+            self.resize_fft(runtime.fft_size());
         }
+        self.clear_filters();
+    }
+
+    fn resize_fft(&mut self, fft_size: usize) {
+        self.analysis = Analysis::new(self.cfg.analysis, fft_size);
+        self.samples.resize(fft_size);
+        self.analyzer.spec_pow_smooth =
+            vec![0.0; self.analysis.half()];
+        self.reset_gate();
+        self.clear_filters();
+        self.reset_levels();
+    }
+
+    fn reset_levels(&mut self) {
+        self.analyzer.eq_ref.fill(1e-6);
+        self.analyzer.db_low = -60.0;
+        self.analyzer.db_high = -20.0;
     }
 
     pub fn clear_filters(&mut self) {
@@ -85,18 +243,129 @@ impl Frame {
     }
 
     pub fn ensure_filterbank(&mut self, runtime: &Runtime) {
+        self.paint
+            .set_keyboard(self.cfg.keyboard(), self.cfg.piano_labels);
         if self.analyzer.filters.len() == self.paint.bars() {
             return;
         }
 
-        self.analyzer.filters = build_filterbank(FilterbankParams {
-            sr: runtime.sample_rate(),
-            fft_size: runtime.fft_size(),
-            bands: self.paint.bars(),
-            fmin: self.cfg.fmin,
-            fmax: self.cfg.fmax,
+        let sr = runtime.sample_rate();
+        let fft_size = runtime.fft_size();
+        self.analyzer.filters = match self.cfg.filterbank {
+            FilterbankMode::Mel => {
+                build_filterbank(FilterbankParams {
+                    sr,
+                    fft_size,
+                    bands: self.paint.bars(),
+                    fmin: self.cfg.fmin,
+                    fmax: self.cfg.fmax,
+                })
+            }
+            FilterbankMode::Piano => build_note_filterbank(
+                self.cfg.note_filterbank_params(sr, fft_size),
+            ),
+        };
+        self.analyzer.resize(self.analyzer.filters.len());
+        self.install_noise(runtime);
+    }
+
+    pub fn start_calibration(&mut self) {
+        self.noise.start(self.cfg.calibrate_s);
+    }
+
+    pub fn next_view(&mut self) {
+        self.set_view(self.view.next());
+    }
+
+    pub fn next_both_display(&mut self) {
+        self.both = self.both.next();
+        self.system = None;
+    }
+
+    pub const fn toggle_peaks(&mut self) {
+        self.partials.toggle();
+    }
+
+    pub fn reference(&mut self, action: ReferenceAction) {
+        self.reference.act(
+            action,
+            &self.analyzer,
+            self.cfg.snapshot_path.as_deref(),
+        );
+    }
+
+    pub fn spl(&mut self, action: SplAction) {
+        if self.cfg.spl_meter {
+            self.spl.act(action);
+        }
+    }
+
+    /// Whether the microphone and system audio get a spectrum each.
+    fn separate(&self, runtime: &Runtime) -> bool {
+        self.both != BothDisplay::Mix
+            && self.view == View::Spectrum
+            && runtime.mode() == AudioMode::Both
+    }
+
+    /// Takes this frame's samples, keeping the two sources apart when
+    /// they are drawn apart.
+    fn prepare(&mut self, runtime: &Runtime) -> bool {
+        if !self.separate(runtime) {
+            self.system = None;
+            return self.samples.prepare(runtime);
+        }
+
+        let system = self.system.get_or_insert_with(|| {
+            let mut gate = self.gate.clone();
+            gate.reset();
+            SourcePipeline::new(&self.cfg, &self.samples, gate)
         });
-        self.analyzer.resize(self.paint.bars());
+        system.sync_filters(&self.analyzer);
+        self.samples.prepare_source(runtime, AudioMode::Mic)
+            && system.process(runtime, &self.cfg, self.dt_s)
+    }
+
+    fn set_view(&mut self, view: View) {
+        if view != self.view {
+            match view {
+                View::Chroma => self.chroma.reset(),
+                View::Tuner => self.tuner.reset(),
+                View::Stereo => self.stereo.reset(),
+                View::Spectrum | View::Distortion => {}
+            }
+            if self.view == View::Distortion {
+                self.distortion.reset();
+            }
+        }
+        self.view = view;
+    }
+
+    fn configure_gate(&mut self) {
+        let gate_db = self.noise.gate_db(self.cfg.gate_db);
+        self.gate.configure(
+            &self.cfg.dynamics,
+            gate_db,
+            self.cfg.gate_mode,
+        );
+        if let Some(system) = &mut self.system {
+            system.gate.configure(
+                &self.cfg.dynamics,
+                gate_db,
+                self.cfg.gate_mode,
+            );
+        }
+        self.analyzer.set_band_gating(band_gate_db(
+            self.cfg.gate_mode,
+            gate_db,
+        ));
+    }
+
+    fn install_noise(&mut self, runtime: &Runtime) {
+        self.analyzer.noise_pow = self.noise.noise_pow(
+            runtime.sample_rate(),
+            runtime.fft_size(),
+            self.cfg.noise_subtraction,
+        );
     }
 
     pub fn set_delta(&mut self, dt_s: f32) {
@@ -108,49 +377,334 @@ impl Frame {
         runtime: &Runtime,
         out: &mut W,
     ) -> Result<()> {
-        if !self.samples.prepare(runtime) {
+        runtime.keep_stereo(self.view == View::Stereo);
+        if !self.prepare(runtime) {
             return Ok(());
         }
 
-        self.gate.tick(
-            sample_power(self.samples.mix(), runtime.fft_size()),
-            self.dt_s,
+        let power = sample_power(
+            self.samples.processed(),
+            runtime.fft_size(),
+        );
+        self.analysis
+            .compute(self.samples.processed(), runtime.fft_size());
+        let voice = voice_features(
+            &self.gate,
+            &self.analysis,
+            self.samples.processed(),
+            runtime,
         );
-        self.fft.compute(self.samples.mix(), runtime.fft_size());
-        self.analyze();
-        self.paint.draw(&mut self.analyzer, out)
+        self.gate.tick(power, voice.as_ref(), self.dt_s);
+        self.calibrate(runtime, power);
+        self.analyze(runtime);
+        self.track_beat();
+        self.measure_loudness(runtime);
+        if self.cfg.spl_meter {
+            self.spl.process(
+                self.samples.mic_fresh(),
+                runtime.sample_rate(),
+                self.dt_s,
+            );
+        }
+        self.draw_status(out)?;
+        let style = PaintStyle {
+            bar: self.cfg.color,
+            tint: self.tint(),
+            caps: self.cfg.cap_color(),
+            highlight: self
+                .feedback
+                .nearest_band(&self.analyzer.filters),
+        };
+
+        match self.view {
+            View::Spectrum => self.draw_spectrum(&style, out),
+            View::Chroma => {
+                let display = LabelledDisplay {
+                    values: self.chroma.values(),
+                    labels: &PITCH_CLASSES,
+                };
+                self.paint.draw_labelled(&display, &style, out)
+            }
+            View::Tuner => {
+                let display = TunerDisplay {
+                    note: self.tuner.note(),
+                    detail: self.tuner.detail(),
+                    cents: self.tuner.cents(),
+                };
+                self.paint.draw_tuner(&display, &style, out)
+            }
+            View::Stereo => {
+                let display = StereoDisplay {
+                    canvas: self.stereo.canvas(),
+                    bands: self.stereo.bands(),
+                    correlation: self.stereo.correlation(),
+                };
+                self.paint.draw_stereo(&display, &style, out)
+            }
+            View::Distortion => {
+                let display = MeasurementDisplay {
+                    value: self.distortion.value(),
+                    lines: self.distortion.lines(),
+                };
+                self.paint.draw_measurement(&display, &style, out)
+            }
+        }
+    }
+
+    fn draw_spectrum<W: Write>(
+        &mut self,
+        style: &PaintStyle,
+        out: &mut W,
+    ) -> Result<()> {
+        let Some(system) = &mut self.system else {
+            self.paint.draw(&mut self.analyzer, style, out)?;
+            let display = LinesDisplay {
+                analyzer: &self.analyzer,
+                lines: &self.reference.lines(&self.cfg),
+            };
+            return self.paint.draw_lines(&display, style, out);
+        };
+
+        let system_style = PaintStyle {
+            bar: self.cfg.system_color,
+            tint: None,
+            caps: self.cfg.cap_color_over(self.cfg.system_color),
+            highlight: None,
+        };
+        let sources = [
+            (&mut self.analyzer, style),
+            (&mut system.analyzer, &system_style),
+        ];
+        match self.both {
+            BothDisplay::Overlay => {
+                self.paint.draw_overlay(sources, out)
+            }
+            BothDisplay::Split | BothDisplay::Mix => {
+                self.paint.draw_split(sources, out)
+            }
+        }
+    }
+
+    fn calibrate(&mut self, runtime: &Runtime, power: f32) {
+        let frame = NoiseFrame {
+            spec_pow: self.analysis.spec_pow(),
+            power,
+            dt_s: self.dt_s,
+        };
+        let measured = self.noise.calibrate(&frame, runtime);
+        if measured {
+            self.configure_gate();
+            self.install_noise(runtime);
+        }
     }
 
-    fn analyze(&mut self) {
-        self.analyzer.update_spectrum(
-            &self.fft.spec_pow,
-            self.cfg.tau_spec,
-            self.dt_s,
+    fn measure_loudness(&mut self, runtime: &Runtime) {
+        if !self.cfg.loudness_meter {
+            return;
+        }
+
+        let sr = runtime.sample_rate();
+        if self.loudness.sample_rate().to_bits() != sr.to_bits() {
+            self.loudness =
+                LoudnessMeter::new(sr, ChannelLayout::DualMono);
+        }
+
+        self.loudness.process(self.samples.fresh());
+        meter::format_loudness(
+            &mut self.meter_text,
+            &self.loudness.reading(),
         );
-        self.analyzer.analyze_bands(self.dt_s, self.gate.open);
-        self.analyzer.apply_flow_and_spring(
-            &FlowSpringParams {
-                flow_k: self.cfg.flow_k,
-                spr_k: self.cfg.spr_k,
-                spr_zeta: self.cfg.spr_zeta,
+    }
+
+    fn draw_status<W: Write>(&mut self, out: &mut W) -> Result<()> {
+        let alert = self
+            .noise
+            .status()
+            .or_else(|| self.feedback.status())
+            .or_else(|| self.reference.message());
+        let readout = self
+            .reference
+            .readout()
+            .or_else(|| self.partials.readout());
+        let status = match (alert, self.view) {
+            (Some(text), _) => Some(text),
+            (None, View::Spectrum) if readout.is_some() => readout,
+            (None, View::Chroma) => Some(self.chroma.text()),
+            (None, View::Tuner) => Some(self.tuner.status()),
+            (None, View::Stereo) => Some(self.stereo.text()),
+            (None, View::Distortion) => {
+                Some(self.distortion.status())
+            }
+            (None, View::Spectrum) if self.cfg.loudness_meter => {
+                Some(self.meter_text.as_str())
+            }
+            (None, View::Spectrum) if self.cfg.spl_meter => {
+                Some(self.spl.text())
+            }
+            (None, View::Spectrum) => self.source_status(),
+        };
+        self.paint.set_status_row(status.is_some());
+        self.paint.draw_status(status.unwrap_or_default(), out)
+    }
+
+    /// Which source is which, while they are drawn apart.
+    fn source_status(&self) -> Option<&'static str> {
+        self.system.as_ref()?;
+        Some(match self.both {
+            BothDisplay::Overlay => {
+                " overlay: microphone in color, system audio in \
+                 system_color"
+            }
+            BothDisplay::Split | BothDisplay::Mix => {
+                " split: microphone above, system audio below"
+            }
+        })
+    }
+
+    fn track_beat(&mut self) {
+        let beat = self
+            .beat
+            .process(&self.analyzer.spec_pow_smooth, self.dt_s);
+
+        self.pulse = if beat.is_some() && self.gate.open {
+            1.0
+        } else {
+            ema_tc(self.pulse, 0.0, BEAT_PULSE_TAU_S, self.dt_s)
+        };
+    }
+
+    fn tint(&self) -> Option<RgbColor> {
+        self.cfg.beat_pulse.then(|| {
+            self.cfg
+                .color
+                .lerp(RgbColor::WHITE, self.pulse * BEAT_PULSE_MIX)
+        })
+    }
+
+    fn analyze(&mut self, runtime: &Runtime) {
+        #[allow(clippy::cast_precision_loss)]
+        let bin_hz =
+            runtime.sample_rate() / runtime.fft_size() as f32;
+        let step = Step {
+            bin_hz,
+            dt_s: self.dt_s,
+            gate_open: self.gate.open,
+        };
+        animate(
+            &mut self.analyzer,
+            self.analysis.spec_pow(),
+            &self.cfg,
+            &step,
+        );
+        self.reference.process(
+            &self.analyzer,
+            &ReferenceFrame {
+                spec_pow: self.analysis.spec_pow(),
+                dt_s: self.dt_s,
+                gate_open: self.gate.open,
+                bands: self.cfg.band_params(),
             },
-            self.dt_s,
-            self.gate.open,
         );
+        self.partials.process(
+            &PartialsFrame {
+                spec_pow: self.analysis.spec_pow(),
+                samples: self.samples.processed(),
+                bin_hz,
+                dt_s: self.dt_s,
+            },
+            &self.cfg,
+        );
+        if self.cfg.feedback_detect {
+            self.feedback.process(
+                self.analysis.spec_pow(),
+                &step,
+                &HowlReport {
+                    tuning: &self.cfg.tuning(),
+                    hook: self.cfg.feedback_hook.as_deref(),
+                },
+            );
+        }
+        match self.view {
+            View::Chroma => {
+                self.chroma.process(self.analysis.spec_pow(), &step);
+            }
+            View::Tuner => self.tuner.process(
+                self.samples.processed_fresh(),
+                runtime,
+                &step,
+            ),
+            View::Stereo => {
+                let area = self.paint.stereo_area();
+                self.stereo.resize(area);
+                self.stereo.process(
+                    runtime,
+                    &StereoFrame {
+                        area,
+                        fmin: self.cfg.fmin,
+                        fmax: self.cfg.fmax,
+                        dt_s: self.dt_s,
+                        gate_open: self.gate.open,
+                    },
+                );
+            }
+            View::Distortion => self.distortion.process(
+                runtime,
+                self.cfg.test_tone(),
+                &step,
+            ),
+            View::Spectrum => {}
+        }
     }
 }
 
-fn make_gate(cfg: &Config) -> GateState {
-    GateState {
+const BEAT_PULSE_TAU_S: f32 = 0.12;
+const BEAT_PULSE_MIX: f32 = 0.6;
+
+fn make_gate(cfg: &Config, noise: &FrameNoise) -> GateState {
+    let mut gate = GateState {
         power_ema: 0.0,
         open: false,
+        loud: false,
         below_s: 0.0,
-        attack_s: 0.012,
-        release_s: 0.22,
-        open_db: cfg.gate_db,
-        close_db: (cfg.gate_db - 3.0).max(-80.0),
-        confirm_s: 0.12,
-    }
+        attack_s: 0.0,
+        release_s: 0.0,
+        open_db: 0.0,
+        close_db: 0.0,
+        confirm_s: 0.0,
+        mode: cfg.gate_mode,
+        voice: VoiceDetector::new(VadParams::DEFAULT),
+    };
+    gate.configure(
+        &cfg.dynamics,
+        noise.gate_db(cfg.gate_db),
+        cfg.gate_mode,
+    );
+    gate
+}
+
+/// Threshold under the band gates, when the gate mode uses them.
+fn band_gate_db(mode: GateMode, gate_db: f32) -> Option<f32> {
+    matches!(mode, GateMode::Bands).then_some(gate_db)
+}
+
+/// Speech features of a frame, measured only when the gate listens
+/// for voice.
+fn voice_features(
+    gate: &GateState,
+    analysis: &Analysis,
+    samples: &[f32],
+    runtime: &Runtime,
+) -> Option<VadFeatures> {
+    let params = gate.vad_params()?;
+    #[allow(clippy::cast_precision_loss)]
+    let bin_hz = runtime.sample_rate() / runtime.fft_size() as f32;
+    let input = VadInput {
+        spec_pow: analysis.spec_pow(),
+        bin_hz,
+        samples,
+        sample_rate: runtime.sample_rate(),
+    };
+    Some(VadFeatures::measure(&input, params))
 }
 
 fn sample_power(tail: &[f32], fft_size: usize) -> f32 {
@@ -0,0 +1,44 @@
+use lookas::config::AnalysisMode;
+
+use super::{fft::FftState, multires::MultiResState};
+
+pub enum Analysis {
+    Single(FftState),
+    MultiRes(MultiResState),
+}
+
+impl Analysis {
+    pub fn new(mode: AnalysisMode, fft_size: usize) -> Self {
+        match mode {
+            AnalysisMode::Fft => {
+                Self::Single(FftState::new(fft_size))
+            }
+            AnalysisMode::MultiRes => {
+                Self::MultiRes(MultiResState::new(fft_size))
+            }
+        }
+    }
+
+    pub const fn half(&self) -> usize {
+        match self {
+            Self::Single(fft) => fft.half,
+            Self::MultiRes(multires) => multires.half,
+        }
+    }
+
+    pub fn spec_pow(&self) -> &[f32] {
+        match self {
+            Self::Single(fft) => &fft.spec_pow,
+            Self::MultiRes(multires) => &multires.spec_pow,
+        }
+    }
+
+    pub fn compute(&mut self, samples: &[f32], fft_size: usize) {
+        match self {
+            Self::Single(fft) => fft.compute(samples, fft_size),
+            Self::MultiRes(multires) => {
+                multires.compute(samples, fft_size);
+            }
+        }
+    }
+}
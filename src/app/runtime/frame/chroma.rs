@@ -0,0 +1,67 @@
+use lookas::chroma::{A4_HZ, Chromagram};
+use std::fmt::Write as _;
+
+use super::source::Step;
+
+pub struct FrameChroma {
+    chromagram: Chromagram,
+    silence: Vec<f32>,
+    text: String,
+}
+
+impl FrameChroma {
+    pub const fn new() -> Self {
+        Self {
+            chromagram: Chromagram::new(),
+            silence: Vec::new(),
+            text: String::new(),
+        }
+    }
+
+    pub fn reset(&mut self) {
+        self.chromagram.reset();
+    }
+
+    /// Folds one frame into the chromagram. While the gate is closed
+    /// the display decays but the key estimate is kept.
+    pub fn process(&mut self, spec_pow: &[f32], step: &Step) {
+        let Step {
+            bin_hz,
+            dt_s,
+            gate_open,
+        } = *step;
+        if gate_open {
+            self.chromagram.process(spec_pow, bin_hz, dt_s);
+        } else {
+            self.silence.resize(spec_pow.len(), 0.0);
+            self.chromagram.process(&self.silence, bin_hz, dt_s);
+        }
+        self.format();
+    }
+
+    pub const fn values(&self) -> &[f32; 12] {
+        self.chromagram.chroma()
+    }
+
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    fn format(&mut self) {
+        self.text.clear();
+        let cents = self.chromagram.tuning_cents();
+        let a4 = A4_HZ * (cents / 1200.0).exp2();
+
+        let _ = match self.chromagram.key() {
+            Some(est) => write!(
+                self.text,
+                " key {:<9}  fit {:>4.2}   tuning {:>+3.0} ct   A4 {:.1} Hz",
+                est.key.to_string(),
+                est.correlation,
+                cents,
+                a4
+            ),
+            None => write!(self.text, " key --"),
+        };
+    }
+}
@@ -1,37 +1,334 @@
-use lookas::config::Config;
+use lookas::{
+    analyzer::{
+        BandParams, DynamicsParams, FlowSpringParams, PeakParams,
+        PhysicsModel, ScalingMode, ScalingParams, SmoothingParams,
+    },
+    config::{
+        AnalysisMode, BothDisplay, Config, GateMode, RgbColor, View,
+    },
+    feedback::FeedbackParams,
+    filterbank::{FilterbankMode, NoteFilterbankParams},
+    partials::Interpolation,
+    spl::{
+        ExposureStandard, FrequencyWeighting, SplParams,
+        TimeWeighting,
+    },
+    tuner::{Temperament, Tuning},
+};
 
+#[allow(clippy::struct_excessive_bools)]
 pub struct FrameConfig {
+    pub analysis: AnalysisMode,
+    pub filterbank: FilterbankMode,
+    pub piano_low: i32,
+    pub piano_high: i32,
+    pub piano_labels: bool,
+    pub view: View,
     pub tau_spec: f32,
+    pub attack_tau: f32,
+    pub tau_tilt: f32,
+    pub scaling: ScalingMode,
+    pub db_floor: f32,
+    pub db_ceiling: f32,
+    pub gate_db: f32,
+    pub gate_mode: GateMode,
+    pub noise_subtraction: f32,
+    pub calibrate_s: f32,
+    pub physics: PhysicsModel,
     pub flow_k: f32,
     pub spr_k: f32,
     pub spr_zeta: f32,
+    pub gravity: f32,
+    pub ema_tau: f32,
+    pub monstercat: f32,
+    pub dynamics: DynamicsParams,
     pub fmin: f32,
     pub fmax: f32,
+    pub color: RgbColor,
+    pub both_display: BothDisplay,
+    pub system_color: RgbColor,
+    pub peak_caps: bool,
+    pub peak_hold: f32,
+    pub peak_gravity: f32,
+    pub peak_color: Option<RgbColor>,
+    pub beat_pulse: bool,
+    pub loudness_meter: bool,
+    pub peak_readout: bool,
+    pub peak_count: usize,
+    pub peak_interpolation: Interpolation,
+    pub peak_reassign: bool,
+    pub feedback_detect: bool,
+    pub feedback_persist_s: f32,
+    pub feedback_hook: Option<String>,
+    pub ltas: bool,
+    pub ltas_window: Option<f32>,
+    pub ltas_color: RgbColor,
+    pub snapshot_color: RgbColor,
+    pub snapshot_path: Option<String>,
+    pub thd_generator: bool,
+    pub thd_tone_hz: f32,
+    pub thd_tone_db: f32,
+    pub spl_meter: bool,
+    pub spl_weighting: FrequencyWeighting,
+    pub spl_time: TimeWeighting,
+    pub spl_offset_db: Option<f32>,
+    pub spl_reference_db: f32,
+    pub spl_standard: ExposureStandard,
+    pub a4_hz: f32,
+    pub temperament: Temperament,
+    pub temperament_root: usize,
 }
 
 impl FrameConfig {
-    pub const fn new(cfg: &Config) -> Self {
+    pub fn new(cfg: &Config) -> Self {
         Self {
+            analysis: cfg.analysis,
+            filterbank: cfg.filterbank,
+            piano_low: cfg.piano_low,
+            piano_high: cfg.piano_high,
+            piano_labels: cfg.piano_labels,
+            view: cfg.view,
             tau_spec: cfg.tau_spec,
+            attack_tau: cfg.attack_tau,
+            tau_tilt: cfg.tau_tilt,
+            scaling: cfg.scaling,
+            db_floor: cfg.db_floor,
+            db_ceiling: cfg.db_ceiling,
+            gate_db: cfg.gate_db,
+            gate_mode: cfg.gate_mode,
+            noise_subtraction: cfg.noise_subtraction,
+            calibrate_s: cfg.calibrate_s,
+            physics: cfg.physics,
             flow_k: cfg.flow_k,
             spr_k: cfg.spr_k,
             spr_zeta: cfg.spr_zeta,
+            gravity: cfg.gravity,
+            ema_tau: cfg.ema_tau,
+            monstercat: cfg.monstercat,
+            dynamics: cfg.dynamics,
             fmin: cfg.fmin,
             fmax: cfg.fmax,
+            color: cfg.color,
+            both_display: cfg.both_display,
+            system_color: cfg.system_color,
+            peak_caps: cfg.peak_caps,
+            peak_hold: cfg.peak_hold,
+            peak_gravity: cfg.peak_gravity,
+            peak_color: cfg.peak_color,
+            beat_pulse: cfg.beat_pulse,
+            loudness_meter: cfg.loudness_meter,
+            peak_readout: cfg.peak_readout,
+            peak_count: cfg.peak_count,
+            peak_interpolation: cfg.peak_interpolation,
+            peak_reassign: cfg.peak_reassign,
+            feedback_detect: cfg.feedback_detect,
+            feedback_persist_s: cfg.feedback_persist_s,
+            feedback_hook: cfg.feedback_hook.clone(),
+            ltas: cfg.ltas,
+            ltas_window: cfg.ltas_window(),
+            ltas_color: cfg.ltas_color,
+            snapshot_color: cfg.snapshot_color,
+            snapshot_path: cfg.snapshot_path.clone(),
+            thd_generator: cfg.thd_generator,
+            thd_tone_hz: cfg.thd_tone_hz,
+            thd_tone_db: cfg.thd_tone_db,
+            spl_meter: cfg.spl_meter,
+            spl_weighting: cfg.spl_weighting,
+            spl_time: cfg.spl_time,
+            spl_offset_db: cfg.spl_offset_db,
+            spl_reference_db: cfg.spl_reference_db,
+            spl_standard: cfg.spl_standard,
+            a4_hz: cfg.a4_hz,
+            temperament: cfg.temperament,
+            temperament_root: cfg.temperament_root,
         }
     }
 
+    pub const fn smoothing_params(
+        &self,
+        bin_hz: f32,
+    ) -> SmoothingParams {
+        SmoothingParams {
+            attack_tau: self.attack_tau,
+            release_tau: self.tau_spec,
+            tilt: self.tau_tilt,
+            bin_hz,
+        }
+    }
+
+    pub const fn scaling_params(&self) -> ScalingParams {
+        ScalingParams {
+            mode: self.scaling,
+            floor_db: self.db_floor,
+            ceiling_db: self.db_ceiling,
+        }
+    }
+
+    pub const fn band_params(&self) -> BandParams {
+        BandParams {
+            scaling: self.scaling_params(),
+            dynamics: self.dynamics,
+        }
+    }
+
+    pub const fn flow_spring_params(&self) -> FlowSpringParams {
+        FlowSpringParams {
+            model: self.physics,
+            flow_k: self.flow_k,
+            spr_k: self.spr_k,
+            spr_zeta: self.spr_zeta,
+            gravity: self.gravity,
+            ema_tau_s: self.ema_tau,
+            monstercat: self.monstercat,
+            silence_tau_s: self.dynamics.silence_tau_s,
+        }
+    }
+
+    pub const fn peak_params(&self) -> PeakParams {
+        PeakParams {
+            hold_s: self.peak_hold,
+            gravity: self.peak_gravity,
+        }
+    }
+
+    pub const fn feedback_params(&self) -> FeedbackParams {
+        FeedbackParams {
+            persist_s: self.feedback_persist_s,
+            ..FeedbackParams::DEFAULT
+        }
+    }
+
+    pub const fn tuning(&self) -> Tuning {
+        Tuning {
+            a4_hz: self.a4_hz,
+            temperament: self.temperament,
+            root: self.temperament_root,
+        }
+    }
+
+    /// Caps default to the bar color lifted halfway toward white.
+    pub fn cap_color(&self) -> Option<RgbColor> {
+        self.cap_color_over(self.color)
+    }
+
+    /// Cap color over bars drawn in `bar`.
+    pub fn cap_color_over(&self, bar: RgbColor) -> Option<RgbColor> {
+        self.peak_caps.then(|| {
+            self.peak_color
+                .unwrap_or_else(|| bar.lerp(RgbColor::WHITE, 0.5))
+        })
+    }
+
     pub fn filterbank_changed(&self, cfg: &Config) -> bool {
-        self.fmin.to_bits() != cfg.fmin.to_bits()
+        let piano = cfg.filterbank == FilterbankMode::Piano
+            && (self.piano_low != cfg.piano_low
+                || self.piano_high != cfg.piano_high
+                || self.a4_hz.to_bits() != cfg.a4_hz.to_bits());
+
+        self.filterbank != cfg.filterbank
+            || piano
+            || self.fmin.to_bits() != cfg.fmin.to_bits()
             || self.fmax.to_bits() != cfg.fmax.to_bits()
     }
 
+    /// Key range drawn as a keyboard, when bands follow the notes.
+    pub fn keyboard(&self) -> Option<(i32, i32)> {
+        (self.filterbank == FilterbankMode::Piano)
+            .then_some((self.piano_low, self.piano_high))
+    }
+
+    pub const fn note_filterbank_params(
+        &self,
+        sr: f32,
+        fft_size: usize,
+    ) -> NoteFilterbankParams {
+        NoteFilterbankParams {
+            sr,
+            fft_size,
+            low_midi: self.piano_low,
+            high_midi: self.piano_high,
+            a4_hz: self.a4_hz,
+        }
+    }
+
+    /// The tone the distortion view plays, while the generator is on.
+    pub fn test_tone(&self) -> Option<(f32, f32)> {
+        self.thd_generator
+            .then_some((self.thd_tone_hz, self.thd_tone_db))
+    }
+
+    pub const fn spl_params(&self) -> SplParams {
+        SplParams {
+            frequency: self.spl_weighting,
+            time: self.spl_time,
+            offset_db: self.spl_offset_db,
+            standard: self.spl_standard,
+        }
+    }
+
+    pub fn analysis_changed(&self, cfg: &Config) -> bool {
+        self.analysis != cfg.analysis
+    }
+
     pub fn apply(&mut self, cfg: &Config) {
+        self.analysis = cfg.analysis;
+        self.filterbank = cfg.filterbank;
+        self.piano_low = cfg.piano_low;
+        self.piano_high = cfg.piano_high;
+        self.piano_labels = cfg.piano_labels;
+        self.view = cfg.view;
         self.tau_spec = cfg.tau_spec;
+        self.attack_tau = cfg.attack_tau;
+        self.tau_tilt = cfg.tau_tilt;
+        self.scaling = cfg.scaling;
+        self.db_floor = cfg.db_floor;
+        self.db_ceiling = cfg.db_ceiling;
+        self.gate_db = cfg.gate_db;
+        self.gate_mode = cfg.gate_mode;
+        self.noise_subtraction = cfg.noise_subtraction;
+        self.calibrate_s = cfg.calibrate_s;
+        self.physics = cfg.physics;
         self.flow_k = cfg.flow_k;
         self.spr_k = cfg.spr_k;
         self.spr_zeta = cfg.spr_zeta;
+        self.gravity = cfg.gravity;
+        self.ema_tau = cfg.ema_tau;
+        self.monstercat = cfg.monstercat;
+        self.dynamics = cfg.dynamics;
         self.fmin = cfg.fmin;
         self.fmax = cfg.fmax;
+        self.color = cfg.color;
+        self.both_display = cfg.both_display;
+        self.system_color = cfg.system_color;
+        self.peak_caps = cfg.peak_caps;
+        self.peak_hold = cfg.peak_hold;
+        self.peak_gravity = cfg.peak_gravity;
+        self.peak_color = cfg.peak_color;
+        self.beat_pulse = cfg.beat_pulse;
+        self.loudness_meter = cfg.loudness_meter;
+        self.peak_readout = cfg.peak_readout;
+        self.peak_count = cfg.peak_count;
+        self.peak_interpolation = cfg.peak_interpolation;
+        self.peak_reassign = cfg.peak_reassign;
+        self.feedback_detect = cfg.feedback_detect;
+        self.feedback_persist_s = cfg.feedback_persist_s;
+        self.feedback_hook.clone_from(&cfg.feedback_hook);
+        self.ltas = cfg.ltas;
+        self.ltas_window = cfg.ltas_window();
+        self.ltas_color = cfg.ltas_color;
+        self.snapshot_color = cfg.snapshot_color;
+        self.snapshot_path.clone_from(&cfg.snapshot_path);
+        self.thd_generator = cfg.thd_generator;
+        self.thd_tone_hz = cfg.thd_tone_hz;
+        self.thd_tone_db = cfg.thd_tone_db;
+        self.spl_meter = cfg.spl_meter;
+        self.spl_weighting = cfg.spl_weighting;
+        self.spl_time = cfg.spl_time;
+        self.spl_offset_db = cfg.spl_offset_db;
+        self.spl_reference_db = cfg.spl_reference_db;
+        self.spl_standard = cfg.spl_standard;
+        self.a4_hz = cfg.a4_hz;
+        self.temperament = cfg.temperament;
+        self.temperament_root = cfg.temperament_root;
     }
 }
@@ -0,0 +1,224 @@
+use lookas::{
+    audio::{AudioMode, ToneGenerator},
+    distortion::{
+        DistortionInput, DistortionMeter, DistortionParams,
+        DistortionReading, ratio_db,
+    },
+};
+use std::fmt::Write as _;
+
+use super::{Runtime, source::Step};
+
+/// Longest frame measured, about 0.34 s at 48 kHz. Shorter ring
+/// buffers measure what they hold.
+const MEASURE_LEN: usize = 16_384;
+/// Harmonics listed by level under the totals.
+const LISTED_HARMONICS: usize = 5;
+
+/// The distortion view: a THD+N reading of the test tone, from the
+/// built-in generator or an external source.
+pub struct FrameDistortion {
+    meter: Option<DistortionMeter>,
+    tone: Option<(f32, f32)>,
+    generator: Option<ToneGenerator>,
+    error: Option<String>,
+    main: Vec<f32>,
+    other: Vec<f32>,
+    value: String,
+    lines: Vec<String>,
+    status: String,
+}
+
+impl FrameDistortion {
+    pub const fn new() -> Self {
+        Self {
+            meter: None,
+            tone: None,
+            generator: None,
+            error: None,
+            main: Vec::new(),
+            other: Vec::new(),
+            value: String::new(),
+            lines: Vec::new(),
+            status: String::new(),
+        }
+    }
+
+    /// Stops the generator and forgets the reading, when the view is
+    /// left.
+    pub fn reset(&mut self) {
+        self.tone = None;
+        self.generator = None;
+        self.error = None;
+        if let Some(meter) = &mut self.meter {
+            meter.reset();
+        }
+    }
+
+    /// Plays `tone` (Hz, dBFS) while it is set, measures the latest
+    /// samples and formats the reading.
+    pub fn process(
+        &mut self,
+        runtime: &Runtime,
+        tone: Option<(f32, f32)>,
+        step: &Step,
+    ) {
+        self.play(tone);
+
+        let n = MEASURE_LEN.min(runtime.ring_cap());
+        if self.meter.as_ref().map(DistortionMeter::len) != Some(n) {
+            self.meter = Some(DistortionMeter::new(
+                n,
+                DistortionParams::DEFAULT,
+            ));
+        }
+
+        let copied = step.gate_open && self.copy_tails(runtime, n);
+        let hint_hz = self.generator.as_ref().map(ToneGenerator::hz);
+        if let Some(meter) = &mut self.meter {
+            if !step.gate_open {
+                meter.reset();
+            } else if copied {
+                let _ = meter.process(&DistortionInput {
+                    samples: &self.main,
+                    sample_rate: runtime.sample_rate(),
+                    hint_hz,
+                    dt_s: step.dt_s,
+                });
+            }
+        }
+        self.format(runtime);
+    }
+
+    /// Copies `n` samples of the current source into `main`,
+    /// averaging the two sources when both are captured.
+    fn copy_tails(&mut self, runtime: &Runtime, n: usize) -> bool {
+        let main = &mut self.main;
+        match runtime.mode() {
+            AudioMode::Mic => runtime.copy_mic_last(n, main),
+            AudioMode::System => runtime.copy_system_last(n, main),
+            AudioMode::Both => {
+                let ok = runtime.copy_mic_last(n, main)
+                    && runtime.copy_system_last(n, &mut self.other);
+                if ok {
+                    for (d, &x) in main.iter_mut().zip(&self.other) {
+                        *d = (*d + x) * 0.5;
+                    }
+                }
+                ok
+            }
+        }
+    }
+
+    /// Starts the generator, or restarts it when the tone changed. A
+    /// failed start is not retried until then.
+    fn play(&mut self, tone: Option<(f32, f32)>) {
+        if self.tone != tone {
+            self.tone = tone;
+            self.generator = None;
+            self.error = None;
+        }
+        let Some((hz, level_db)) = tone else {
+            return;
+        };
+        if self.generator.is_some() || self.error.is_some() {
+            return;
+        }
+
+        match ToneGenerator::start(hz, level_db) {
+            Ok(generator) => self.generator = Some(generator),
+            Err(err) => self.error = Some(format!("{err:#}")),
+        }
+    }
+
+    pub fn value(&self) -> &str {
+        &self.value
+    }
+
+    pub fn lines(&self) -> &[String] {
+        &self.lines
+    }
+
+    pub fn status(&self) -> &str {
+        &self.status
+    }
+
+    fn format(&mut self, runtime: &Runtime) {
+        self.format_status(runtime.mode());
+        self.value.clear();
+        self.lines.clear();
+
+        let Some(r) =
+            self.meter.as_ref().and_then(DistortionMeter::reading)
+        else {
+            self.value.push_str("--");
+            self.lines.push(String::from("waiting for a test tone"));
+            return;
+        };
+
+        let thd_n = r.thd_n() * 100.0;
+        let _ = write!(self.value, "{}%", percent(thd_n));
+        self.lines.push(format!(
+            "fundamental  {:.2} Hz  {:.2} dBFS",
+            r.fundamental_hz,
+            r.fundamental_dbfs()
+        ));
+        self.lines.push(format!(
+            "THD          {}%  {:.1} dB",
+            percent(r.thd() * 100.0),
+            ratio_db(r.thd())
+        ));
+        self.lines.push(format!(
+            "THD+N        {}%  {:.1} dB",
+            percent(thd_n),
+            ratio_db(r.thd_n())
+        ));
+        self.lines
+            .push(format!("SINAD        {:.1} dB", r.sinad_db()));
+        self.lines.push(harmonics_line(r));
+    }
+
+    fn format_status(&mut self, mode: AudioMode) {
+        self.status.clear();
+        self.status.push_str(" distortion   ");
+        let _ = match (&self.generator, &self.error) {
+            (Some(g), _) => write!(
+                self.status,
+                "generator {:.0} Hz at {} Hz",
+                g.hz(),
+                g.sample_rate()
+            ),
+            (None, Some(err)) => {
+                write!(self.status, "generator failed: {err}")
+            }
+            (None, None) => write!(self.status, "external test tone"),
+        };
+        if mode == AudioMode::Both {
+            self.status.push_str("   sources averaged");
+        }
+    }
+}
+
+/// Levels of the first few harmonics relative to the fundamental.
+fn harmonics_line(r: &DistortionReading) -> String {
+    let mut line = String::from("harmonics   ");
+    for n in 2..LISTED_HARMONICS + 2 {
+        if let Some(db) = r.harmonic_dbc(n) {
+            let _ = write!(line, " H{n} {db:.0}");
+        }
+    }
+    line.push_str(" dBc");
+    line
+}
+
+/// A percentage with about three significant digits.
+fn percent(value: f32) -> String {
+    let digits = match value {
+        v if v < 0.01 => 5,
+        v if v < 0.1 => 4,
+        v if v < 1.0 => 3,
+        v if v < 10.0 => 2,
+        _ => 1,
+    };
+    format!("{value:.digits$}")
+}
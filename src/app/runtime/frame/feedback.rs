@@ -0,0 +1,150 @@
+use lookas::{
+    feedback::{FeedbackDetector, FeedbackParams, Howl},
+    filterbank::Tri,
+    tuner::Tuning,
+};
+use std::{
+    fmt::Write as _,
+    process::{Child, Command, Stdio},
+};
+
+use super::source::Step;
+
+/// How long a note must stay quiet before a howl on it counts as a
+/// new one.
+const HOWL_GAP_S: f32 = 5.0;
+
+/// How a flagged howl is named, and the command run for it.
+pub struct HowlReport<'a> {
+    pub tuning: &'a Tuning,
+    pub hook: Option<&'a str>,
+}
+
+pub struct FrameFeedback {
+    detector: FeedbackDetector,
+    howl: Option<Howl>,
+    note: String,
+    hooked_note: String,
+    since_howl_s: f32,
+    hook: Option<Child>,
+    text: String,
+}
+
+impl FrameFeedback {
+    pub const fn new(params: FeedbackParams) -> Self {
+        Self {
+            detector: FeedbackDetector::new(params),
+            howl: None,
+            note: String::new(),
+            hooked_note: String::new(),
+            since_howl_s: HOWL_GAP_S,
+            hook: None,
+            text: String::new(),
+        }
+    }
+
+    pub fn set_params(&mut self, params: FeedbackParams) {
+        if *self.detector.params() != params {
+            self.detector = FeedbackDetector::new(params);
+            self.howl = None;
+        }
+    }
+
+    pub fn reset(&mut self) {
+        self.detector.reset();
+        self.howl = None;
+    }
+
+    /// Checks one full-resolution spectrum for feedback, running the
+    /// hook when a howl starts or moves to another note.
+    pub fn process(
+        &mut self,
+        spec_pow: &[f32],
+        step: &Step,
+        report: &HowlReport,
+    ) {
+        self.reap_hook();
+        self.since_howl_s += step.dt_s;
+        self.howl =
+            self.detector.process(spec_pow, step.bin_hz, step.dt_s);
+        let Some(howl) = self.howl else {
+            return;
+        };
+        let tuning = report.tuning;
+
+        self.note.clear();
+        self.text.clear();
+        let _ = write!(self.text, " FEEDBACK  {:.1} Hz", howl.hz);
+        if let Some(r) = tuning.nearest(howl.hz) {
+            let _ = write!(self.note, "{}{}", r.name(), r.octave);
+            let _ = write!(
+                self.text,
+                "  {} {:+.0} ct",
+                self.note, r.cents
+            );
+        }
+        let _ = write!(self.text, "  {:.0} dB", howl.db);
+
+        let same = self.note == self.hooked_note
+            && self.since_howl_s < HOWL_GAP_S;
+        self.since_howl_s = 0.0;
+        if same {
+            return;
+        }
+        self.hooked_note.clone_from(&self.note);
+        if let Some(cmd) = report.hook {
+            self.run_hook(cmd, howl.hz);
+        }
+    }
+
+    /// Starts `cmd` through the shell without waiting for it, with
+    /// the howl in `LOOKAS_FEEDBACK_HZ` and `LOOKAS_FEEDBACK_NOTE`.
+    /// Skipped while the previous run is still going. The terminal
+    /// belongs to the display, so the hook gets no input and its
+    /// output is discarded.
+    fn run_hook(&mut self, cmd: &str, hz: f32) {
+        if self.hook.is_some() {
+            return;
+        }
+        let (shell, flag) = if cfg!(windows) {
+            ("cmd", "/C")
+        } else {
+            ("sh", "-c")
+        };
+        self.hook = Command::new(shell)
+            .arg(flag)
+            .arg(cmd)
+            .env("LOOKAS_FEEDBACK_HZ", format!("{hz:.1}"))
+            .env("LOOKAS_FEEDBACK_NOTE", &self.note)
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn()
+            .ok();
+    }
+
+    /// Collects the hook once it has exited.
+    fn reap_hook(&mut self) {
+        if let Some(child) = &mut self.hook {
+            if !matches!(child.try_wait(), Ok(None)) {
+                self.hook = None;
+            }
+        }
+    }
+
+    /// Status line while a howl is flagged.
+    pub fn status(&self) -> Option<&str> {
+        self.howl.map(|_| self.text.as_str())
+    }
+
+    /// Index of the band closest to the howl in pitch.
+    pub fn nearest_band(&self, filters: &[Tri]) -> Option<usize> {
+        let hz = self.howl?.hz;
+        filters
+            .iter()
+            .map(|tri| (tri.center_hz.max(1e-3) / hz).log2().abs())
+            .enumerate()
+            .min_by(|a, b| a.1.total_cmp(&b.1))
+            .map(|(i, _)| i)
+    }
+}
@@ -30,10 +30,6 @@ impl FftState {
         }
     }
 
-    pub fn resize(&mut self, fft_size: usize) {
-        *self = Self::new(fft_size);
-    }
-
     pub fn compute(&mut self, samples: &[f32], fft_size: usize) {
         prepare_fft_input_inplace(
             samples,
@@ -0,0 +1,11 @@
+use lookas::loudness::LoudnessReading;
+use std::fmt::Write;
+
+pub fn format_loudness(text: &mut String, r: &LoudnessReading) {
+    text.clear();
+    let _ = write!(
+        text,
+        " M {:>6.1}  S {:>6.1}  I {:>6.1} LUFS   LRA {:>5.1} LU   TP {:>5.1} dBTP",
+        r.momentary, r.short_term, r.integrated, r.range, r.true_peak,
+    );
+}
@@ -0,0 +1,49 @@
+use lookas::multires::{
+    MultiResParams, ResolutionBand, fold_power, plan_resolutions,
+};
+
+use super::fft::FftState;
+
+const MULTIRES_LEVELS: usize = 3;
+
+pub struct MultiResState {
+    pub half: usize,
+    pub spec_pow: Vec<f32>,
+    bands: Vec<ResolutionBand>,
+    levels: Vec<FftState>,
+}
+
+impl MultiResState {
+    pub fn new(fft_size: usize) -> Self {
+        let half = fft_size / 2;
+        let bands = plan_resolutions(MultiResParams {
+            fft_size,
+            levels: MULTIRES_LEVELS,
+        });
+        let levels =
+            bands.iter().map(|b| FftState::new(b.fft_size)).collect();
+
+        Self {
+            half,
+            spec_pow: vec![0.0f32; half],
+            bands,
+            levels,
+        }
+    }
+
+    pub fn compute(&mut self, samples: &[f32], fft_size: usize) {
+        for (band, fft) in
+            self.bands.iter().zip(self.levels.iter_mut())
+        {
+            let start = samples.len().saturating_sub(band.fft_size);
+            let tail = samples.get(start..).unwrap_or(samples);
+            fft.compute(tail, band.fft_size);
+            fold_power(
+                &mut self.spec_pow,
+                &fft.spec_pow,
+                band,
+                fft_size,
+            );
+        }
+    }
+}
@@ -0,0 +1,130 @@
+use lookas::noise::{NoiseCalibrator, NoiseProfile};
+use std::fmt::Write as _;
+
+use super::Runtime;
+
+const MESSAGE_S: f32 = 3.0;
+
+/// One raw frame for a running calibration.
+pub struct NoiseFrame<'a> {
+    pub spec_pow: &'a [f32],
+    pub power: f32,
+    pub dt_s: f32,
+}
+
+pub struct FrameNoise {
+    profile: Option<NoiseProfile>,
+    calibrator: Option<NoiseCalibrator>,
+    status: String,
+    message_s: f32,
+}
+
+impl FrameNoise {
+    pub fn new(calibrate_s: Option<f32>) -> Self {
+        let mut noise = Self {
+            profile: None,
+            calibrator: calibrate_s.map(NoiseCalibrator::new),
+            status: String::new(),
+            message_s: 0.0,
+        };
+
+        if calibrate_s.is_none() {
+            match NoiseProfile::load() {
+                Ok(profile) => noise.profile = profile,
+                Err(err) => {
+                    let _ = write!(
+                        noise.status,
+                        " noise profile not loaded: {err:#}"
+                    );
+                    noise.message_s = MESSAGE_S;
+                }
+            }
+        }
+        noise
+    }
+
+    pub fn start(&mut self, duration_s: f32) {
+        self.calibrator = Some(NoiseCalibrator::new(duration_s));
+    }
+
+    /// The configured threshold, raised to sit above the measured
+    /// noise floor when a profile is present.
+    pub fn gate_db(&self, gate_db: f32) -> f32 {
+        self.profile
+            .as_ref()
+            .map_or(gate_db, |p| p.gate_db().max(gate_db))
+    }
+
+    pub fn noise_pow(
+        &self,
+        sample_rate: f32,
+        fft_size: usize,
+        subtraction: f32,
+    ) -> Vec<f32> {
+        match &self.profile {
+            Some(p) if subtraction > 0.0 => {
+                let mut bins = p.bins_for(sample_rate, fft_size);
+                for b in &mut bins {
+                    *b *= subtraction;
+                }
+                bins
+            }
+            _ => Vec::new(),
+        }
+    }
+
+    /// Feeds one raw frame to a running calibration. Returns `true`
+    /// when a new profile has just been measured.
+    pub fn calibrate(
+        &mut self,
+        frame: &NoiseFrame,
+        runtime: &Runtime,
+    ) -> bool {
+        let NoiseFrame {
+            spec_pow,
+            power,
+            dt_s,
+        } = *frame;
+        self.message_s = (self.message_s - dt_s).max(0.0);
+
+        let Some(cal) = self.calibrator.as_mut() else {
+            return false;
+        };
+
+        self.status.clear();
+        if !cal.push(spec_pow, power, dt_s) {
+            let _ = write!(
+                self.status,
+                " calibrating noise floor, stay quiet ... {:.1} s",
+                cal.remaining_s()
+            );
+            return false;
+        }
+
+        self.profile =
+            cal.finish(runtime.sample_rate(), runtime.fft_size());
+        self.calibrator = None;
+        self.message_s = MESSAGE_S;
+
+        if let Some(p) = &self.profile {
+            let _ = match p.save() {
+                Ok(()) => write!(
+                    self.status,
+                    " noise floor {:.1} dB, gate {:.1} dB",
+                    p.floor_db,
+                    p.gate_db()
+                ),
+                Err(err) => write!(
+                    self.status,
+                    " noise profile not saved: {err:#}"
+                ),
+            };
+        }
+        true
+    }
+
+    pub fn status(&self) -> Option<&str> {
+        (self.calibrator.is_some() || self.message_s > 0.0)
+            .then_some(self.status.as_str())
+    }
+}
@@ -1,14 +1,117 @@
 use anyhow::Result;
-use crossterm::{cursor, queue};
+use crossterm::{
+    cursor, queue,
+    style::{Color, SetBackgroundColor, SetForegroundColor},
+};
 use lookas::{
     analyzer::SpectrumAnalyzer,
-    render::{Layout, draw_blocks_vertical, layout_for},
+    config::RgbColor,
+    render::{
+        BIG_TEXT_ROWS, BrailleCanvas, GAUGE_RANGE_CENTS, Layout,
+        OverlayLayer, PeakCaps, bar_glyph, big_text_width,
+        draw_big_text_row, draw_blocks_overlay, draw_blocks_vertical,
+        draw_gauge_end_labels, draw_gauge_labels, draw_gauge_needle,
+        draw_gauge_scale, draw_labels, keyboard_labels,
+        layout_centered, layout_for,
+    },
+};
+use std::{cmp::Ordering, io::Write};
+
+use super::stereo::StereoArea;
+
+/// Colors for one frame. `tint` overrides the terminal color for the
+/// bars, `caps` enables peak caps in the given color, and
+/// `highlight` marks one bar in [`HIGHLIGHT`].
+pub struct PaintStyle {
+    pub bar: RgbColor,
+    pub tint: Option<RgbColor>,
+    pub caps: Option<RgbColor>,
+    pub highlight: Option<usize>,
+}
+
+/// Color of a highlighted bar.
+const HIGHLIGHT: RgbColor = RgbColor {
+    r: 255,
+    g: 64,
+    b: 64,
 };
-use std::io::Write;
+/// Fills the empty part of a highlighted bar's column.
+const HIGHLIGHT_SHADE: &str = "\u{2591}";
+
+/// A spectrum and the style it is drawn in, when the microphone and
+/// system audio are shown apart.
+pub type Source<'a> = (&'a mut SpectrumAnalyzer, &'a PaintStyle);
+
+/// A reference level per bar, drawn as a thin line over the bars.
+/// Bars without a level are skipped.
+pub struct ReferenceLine<'a> {
+    pub heights: &'a [Option<f32>],
+    pub color: RgbColor,
+}
+
+/// Reference lines over the bars of `analyzer`, later lines in front.
+pub struct LinesDisplay<'a> {
+    pub analyzer: &'a SpectrumAnalyzer,
+    pub lines: &'a [ReferenceLine<'a>],
+}
+
+/// Glyphs for a line in the lower, middle and upper third of a cell.
+const LINE_GLYPHS: [&str; 3] = ["\u{2581}", "\u{2500}", "\u{2594}"];
+
+/// What the chroma view shows: one bar per value, each with a label
+/// under it.
+pub struct LabelledDisplay<'a> {
+    pub values: &'a [f32],
+    pub labels: &'a [&'a str],
+}
+
+/// What the tuner view shows: the note in big letters, a line of
+/// detail under it and the cents gauge.
+pub struct TunerDisplay<'a> {
+    pub note: &'a str,
+    pub detail: &'a str,
+    pub cents: Option<f32>,
+}
+
+/// What the distortion view shows: the headline value in big
+/// letters and the detail lines under it.
+pub struct MeasurementDisplay<'a> {
+    pub value: &'a str,
+    pub lines: &'a [String],
+}
+
+/// What the stereo view shows: the goniometer, the balance of each
+/// band and the correlation gauge.
+pub struct StereoDisplay<'a> {
+    pub canvas: &'a BrailleCanvas,
+    pub bands: &'a [f32],
+    pub correlation: f32,
+}
+
+/// Widest the cents gauge gets; odd so zero has its own column.
+const GAUGE_MAX_W: usize = 81;
+
+/// Rows of the tuner view: big note, gap, detail, gap, gauge.
+const NOTE_LAST_ROW: usize = BIG_TEXT_ROWS - 1;
+const DETAIL_ROW: usize = BIG_TEXT_ROWS + 1;
+const NEEDLE_ROW: usize = BIG_TEXT_ROWS + 3;
+const SCALE_ROW: usize = BIG_TEXT_ROWS + 4;
+const LABEL_ROW: usize = BIG_TEXT_ROWS + 5;
+const TUNER_ROWS: usize = BIG_TEXT_ROWS + 6;
+
+/// Rows under the stereo plots: gap, needle, scale and labels.
+const STEREO_GAUGE_ROWS: usize = 4;
+/// Columns between the goniometer and the band balance.
+const STEREO_GAP: usize = 2;
 
 pub struct FramePaint {
     lay: Layout,
     render: Vec<u8>,
+    fulls: Vec<usize>,
+    fracs: Vec<f32>,
+    keyboard: Option<(i32, i32)>,
+    key_labels: bool,
+    labels: Vec<String>,
     w: u16,
     h: u16,
     top_pad: u16,
@@ -24,6 +127,11 @@ impl FramePaint {
         Self {
             lay: layout_for(w, h, top_pad),
             render: Vec::with_capacity(frame_cap),
+            fulls: Vec::new(),
+            fracs: Vec::new(),
+            keyboard: None,
+            key_labels: false,
+            labels: Vec::new(),
             w,
             h,
             top_pad,
@@ -37,27 +145,644 @@ impl FramePaint {
     pub fn resize(&mut self, w: u16, h: u16) {
         self.w = w;
         self.h = h;
-        self.lay = layout_for(self.w, self.h, self.top_pad);
+        self.relayout();
+    }
+
+    /// Switches the spectrum to one bar per key over an inclusive
+    /// MIDI range, optionally with a keyboard label row, or back to
+    /// bars filling the terminal width.
+    pub fn set_keyboard(
+        &mut self,
+        range: Option<(i32, i32)>,
+        labels: bool,
+    ) {
+        if self.keyboard != range || self.key_labels != labels {
+            self.keyboard = range;
+            self.key_labels = labels;
+            self.relayout();
+        }
+    }
+
+    fn relayout(&mut self) {
+        self.labels.clear();
+        let Some((low, high)) = self.keyboard else {
+            self.lay = layout_for(self.w, self.h, self.top_pad);
+            return;
+        };
+
+        let keys = usize::try_from(high.saturating_sub(low))
+            .unwrap_or(0)
+            .saturating_add(1);
+        self.lay = layout_centered(self.w, keys, self.top_pad);
+        if self.key_labels {
+            self.labels =
+                keyboard_labels(low, high, self.lay.slot_w());
+        }
+    }
+
+    /// Reserves the top row for a text status line.
+    pub fn set_status_row(&mut self, enabled: bool) {
+        let top_pad = u16::from(enabled);
+        if top_pad != self.top_pad {
+            self.top_pad = top_pad;
+            self.relayout();
+        }
+    }
+
+    pub fn draw_status<W: Write>(
+        &self,
+        text: &str,
+        out: &mut W,
+    ) -> Result<()> {
+        if self.top_pad == 0 {
+            return Ok(());
+        }
+
+        // Padding in `write!` counts characters, as `truncate` does.
+        let cols = usize::from(self.w);
+        let shown = truncate(text, cols);
+        queue!(out, cursor::MoveTo(0, 0))?;
+        write!(out, "{shown:<cols$}")?;
+        Ok(())
     }
 
     pub fn draw<W: Write>(
         &mut self,
         analyzer: &mut SpectrumAnalyzer,
+        style: &PaintStyle,
         out: &mut W,
     ) -> Result<()> {
-        queue!(out, cursor::MoveTo(0, self.top_pad))?;
-        self.render.clear();
+        self.begin(style, out)?;
+        let caps = style.caps.map(|color| PeakCaps {
+            heights: &analyzer.peak_y,
+            color,
+            bar_color: style.tint.unwrap_or(style.bar),
+        });
+        let label_row = self.h.saturating_sub(1);
         draw_blocks_vertical(
             &mut self.render,
-            &analyzer.bars_y,
+            &analyzer.bars_render,
             self.w,
-            self.h,
+            if self.labels.is_empty() {
+                self.h
+            } else {
+                label_row
+            },
             &self.lay,
             &mut analyzer.render_fulls,
             &mut analyzer.render_fracs,
+            caps.as_ref(),
         )?;
         out.write_all(&self.render)?;
+
+        if !self.labels.is_empty() {
+            queue!(out, cursor::MoveTo(0, label_row))?;
+            draw_labels(out, &self.labels, self.w, &self.lay)?;
+        }
+        self.draw_highlight(analyzer, style, out)?;
+        out.flush()?;
+        Ok(())
+    }
+
+    /// Redraws the highlighted bar in [`HIGHLIGHT`], shading the rest
+    /// of its column so it stands out even when short.
+    #[allow(clippy::arithmetic_side_effects)]
+    fn draw_highlight<W: Write>(
+        &self,
+        analyzer: &SpectrumAnalyzer,
+        style: &PaintStyle,
+        out: &mut W,
+    ) -> Result<()> {
+        let Some(bar) = style.highlight else {
+            return Ok(());
+        };
+        let (Some(&full), Some(&frac)) = (
+            analyzer.render_fulls.get(bar),
+            analyzer.render_fracs.get(bar),
+        ) else {
+            return Ok(());
+        };
+        if bar >= self.lay.bars {
+            return Ok(());
+        }
+
+        let col =
+            usize::from(self.lay.left_pad) + bar * self.lay.slot_w();
+        let Ok(col) = u16::try_from(col) else {
+            return Ok(());
+        };
+        queue!(out, SetForegroundColor(rgb(HIGHLIGHT)))?;
+        let rows = self.bars_bottom().saturating_sub(self.top_pad);
+        for y in 0..rows {
+            let glyph = match bar_glyph(
+                full,
+                frac,
+                usize::from(rows - 1 - y),
+            ) {
+                " " => HIGHLIGHT_SHADE,
+                g => g,
+            };
+            queue!(out, cursor::MoveTo(col, self.top_pad + y))?;
+            for _ in 0..self.lay.bar_w {
+                out.write_all(glyph.as_bytes())?;
+            }
+        }
+        queue!(
+            out,
+            SetForegroundColor(rgb(style.tint.unwrap_or(style.bar)))
+        )?;
+        Ok(())
+    }
+
+    /// Draws reference lines over bars already drawn by [`Self::draw`].
+    /// Where a line crosses a bar it keeps the bar color behind it.
+    pub fn draw_lines<W: Write>(
+        &self,
+        display: &LinesDisplay<'_>,
+        style: &PaintStyle,
+        out: &mut W,
+    ) -> Result<()> {
+        let LinesDisplay { analyzer, lines } = *display;
+        let rows = self.bars_bottom().saturating_sub(self.top_pad);
+        if rows == 0 {
+            return Ok(());
+        }
+
+        let bar_color = rgb(style.tint.unwrap_or(style.bar));
+        for line in lines.iter().filter(|l| !l.heights.is_empty()) {
+            queue!(out, SetForegroundColor(rgb(line.color)))?;
+            for (bar, height) in
+                line.heights.iter().enumerate().take(self.lay.bars)
+            {
+                let Some((col, y, row, glyph)) =
+                    height.and_then(|h| self.line_cell(bar, h, rows))
+                else {
+                    continue;
+                };
+                let inside = analyzer
+                    .render_fulls
+                    .get(bar)
+                    .is_some_and(|&full| full > row);
+                if inside {
+                    queue!(out, SetBackgroundColor(bar_color))?;
+                }
+                queue!(out, cursor::MoveTo(col, y))?;
+                for _ in 0..self.lay.bar_w {
+                    out.write_all(glyph.as_bytes())?;
+                }
+                if inside {
+                    queue!(out, SetBackgroundColor(Color::Reset))?;
+                }
+            }
+        }
+        queue!(out, SetForegroundColor(bar_color))?;
         out.flush()?;
         Ok(())
     }
+
+    /// Column, screen row, row counted up from the bottom and glyph of
+    /// a line at `height` over bar `bar`.
+    #[allow(
+        clippy::cast_precision_loss,
+        clippy::cast_possible_truncation,
+        clippy::cast_sign_loss,
+        clippy::arithmetic_side_effects
+    )]
+    fn line_cell(
+        &self,
+        bar: usize,
+        height: f32,
+        rows: u16,
+    ) -> Option<(u16, u16, usize, &'static str)> {
+        let col = u16::try_from(
+            usize::from(self.lay.left_pad) + bar * self.lay.slot_w(),
+        )
+        .ok()?;
+        let level = height.clamp(0.0, 1.0) * f32::from(rows);
+        let row = (level.floor() as u16).min(rows - 1);
+        let third = ((level - f32::from(row)) * 3.0) as usize;
+        let glyph = LINE_GLYPHS.get(third.min(2)).copied()?;
+        let y = self.top_pad + rows - 1 - row;
+        Some((col, y, usize::from(row), glyph))
+    }
+
+    /// Draws two spectra over the same bars, the first in front.
+    pub fn draw_overlay<W: Write>(
+        &mut self,
+        sources: [Source<'_>; 2],
+        out: &mut W,
+    ) -> Result<()> {
+        let [(front, front_style), (back, back_style)] = sources;
+        self.begin(front_style, out)?;
+        let bottom = self.bars_bottom();
+        let layers = [
+            &OverlayLayer {
+                bars: &front.bars_render,
+                color: front_style.tint.unwrap_or(front_style.bar),
+            },
+            &OverlayLayer {
+                bars: &back.bars_render,
+                color: back_style.tint.unwrap_or(back_style.bar),
+            },
+        ];
+        draw_blocks_overlay(
+            &mut self.render,
+            layers,
+            self.w,
+            bottom,
+            &self.lay,
+        )?;
+        out.write_all(&self.render)?;
+        self.finish(front_style.bar, out)
+    }
+
+    /// Draws two spectra stacked, the first in the top half.
+    #[allow(clippy::arithmetic_side_effects)]
+    pub fn draw_split<W: Write>(
+        &mut self,
+        sources: [Source<'_>; 2],
+        out: &mut W,
+    ) -> Result<()> {
+        let bottom = self.bars_bottom();
+        let half = bottom.saturating_sub(self.top_pad) / 2;
+        let restore = sources[0].1.bar;
+        let spans = [
+            (self.top_pad, self.top_pad + half),
+            (self.top_pad + half, bottom),
+        ];
+
+        for ((analyzer, style), (top, end)) in
+            sources.into_iter().zip(spans)
+        {
+            let color = style.tint.unwrap_or(style.bar);
+            queue!(
+                out,
+                SetForegroundColor(rgb(color)),
+                cursor::MoveTo(0, top)
+            )?;
+            self.render.clear();
+            let caps = style.caps.map(|c| PeakCaps {
+                heights: &analyzer.peak_y,
+                color: c,
+                bar_color: color,
+            });
+            draw_blocks_vertical(
+                &mut self.render,
+                &analyzer.bars_render,
+                self.w,
+                end,
+                &Layout {
+                    top_pad: top,
+                    ..self.lay
+                },
+                &mut analyzer.render_fulls,
+                &mut analyzer.render_fracs,
+                caps.as_ref(),
+            )?;
+            out.write_all(&self.render)?;
+        }
+        self.finish(restore, out)
+    }
+
+    /// Row after the last bar row, leaving room for labels.
+    fn bars_bottom(&self) -> u16 {
+        if self.labels.is_empty() {
+            self.h
+        } else {
+            self.h.saturating_sub(1)
+        }
+    }
+
+    /// Draws the label row, if any, and goes back to the bar color.
+    fn finish<W: Write>(
+        &self,
+        restore: RgbColor,
+        out: &mut W,
+    ) -> Result<()> {
+        if !self.labels.is_empty() {
+            queue!(out, cursor::MoveTo(0, self.bars_bottom()))?;
+            draw_labels(out, &self.labels, self.w, &self.lay)?;
+        }
+        queue!(out, SetForegroundColor(rgb(restore)))?;
+        out.flush()?;
+        Ok(())
+    }
+
+    /// Draws a fixed set of centered bars with a label under each,
+    /// for views that are not tied to the terminal width.
+    pub fn draw_labelled<W: Write>(
+        &mut self,
+        display: &LabelledDisplay,
+        style: &PaintStyle,
+        out: &mut W,
+    ) -> Result<()> {
+        let LabelledDisplay { values, labels } = *display;
+        self.begin(style, out)?;
+        self.fulls.resize(values.len(), 0);
+        self.fracs.resize(values.len(), 0.0);
+
+        let lay = layout_centered(self.w, values.len(), self.top_pad);
+        let label_row = self.h.saturating_sub(1);
+        draw_blocks_vertical(
+            &mut self.render,
+            values,
+            self.w,
+            label_row,
+            &lay,
+            &mut self.fulls,
+            &mut self.fracs,
+            None,
+        )?;
+        out.write_all(&self.render)?;
+
+        queue!(out, cursor::MoveTo(0, label_row))?;
+        draw_labels(out, labels, self.w, &lay)?;
+        out.flush()?;
+        Ok(())
+    }
+
+    fn begin<W: Write>(
+        &mut self,
+        style: &PaintStyle,
+        out: &mut W,
+    ) -> Result<()> {
+        if let Some(c) = style.tint {
+            queue!(out, SetForegroundColor(rgb(c)))?;
+        }
+        queue!(out, cursor::MoveTo(0, self.top_pad))?;
+        self.render.clear();
+        Ok(())
+    }
+
+    /// Draws the tuner view centered in the area below the status
+    /// row, blanking every other row.
+    #[allow(clippy::arithmetic_side_effects)]
+    pub fn draw_tuner<W: Write>(
+        &mut self,
+        tuner: &TunerDisplay<'_>,
+        style: &PaintStyle,
+        out: &mut W,
+    ) -> Result<()> {
+        self.begin(style, out)?;
+        let cols = usize::from(self.w);
+        let area = usize::from(self.h.saturating_sub(self.top_pad));
+        let first = area.saturating_sub(TUNER_ROWS) / 2;
+        let gauge_w = (cols.saturating_sub(4).min(GAUGE_MAX_W)) | 1;
+        let note_w = big_text_width(tuner.note);
+
+        for y in 0..area {
+            let line = y.wrapping_sub(first);
+            let w = match line {
+                0..=NOTE_LAST_ROW => note_w,
+                DETAIL_ROW => tuner.detail.chars().count(),
+                NEEDLE_ROW..TUNER_ROWS => gauge_w,
+                _ => 0,
+            }
+            .min(cols);
+            let left = (cols - w) / 2;
+            write!(self.render, "{:left$}", "")?;
+
+            match line {
+                0..=NOTE_LAST_ROW => {
+                    draw_big_text_row(
+                        &mut self.render,
+                        tuner.note,
+                        line,
+                    )?;
+                }
+                DETAIL_ROW => {
+                    let shown = truncate(tuner.detail, w);
+                    self.render.extend_from_slice(shown.as_bytes());
+                }
+                NEEDLE_ROW => {
+                    draw_gauge_needle(
+                        &mut self.render,
+                        tuner.cents,
+                        w,
+                    )?;
+                }
+                SCALE_ROW => draw_gauge_scale(&mut self.render, w)?,
+                LABEL_ROW => draw_gauge_labels(&mut self.render, w)?,
+                _ => {}
+            }
+
+            let rest = cols - left - w;
+            write!(self.render, "{:rest$}", "")?;
+            if y + 1 < area {
+                self.render.extend_from_slice(b"\r\n");
+            }
+        }
+
+        out.write_all(&self.render)?;
+        out.flush()?;
+        Ok(())
+    }
+
+    /// Draws the distortion view centered in the area below the
+    /// status row, the detail lines aligned as one block.
+    #[allow(clippy::arithmetic_side_effects)]
+    pub fn draw_measurement<W: Write>(
+        &mut self,
+        display: &MeasurementDisplay<'_>,
+        style: &PaintStyle,
+        out: &mut W,
+    ) -> Result<()> {
+        self.begin(style, out)?;
+        let cols = usize::from(self.w);
+        let area = usize::from(self.h.saturating_sub(self.top_pad));
+        let rows = BIG_TEXT_ROWS + 1 + display.lines.len();
+        let first = area.saturating_sub(rows) / 2;
+        let value_w = big_text_width(display.value).min(cols);
+        let block_w = display
+            .lines
+            .iter()
+            .map(|t| t.chars().count())
+            .max()
+            .unwrap_or(0)
+            .min(cols);
+
+        for y in 0..area {
+            let line = y.wrapping_sub(first);
+            let w = if line <= NOTE_LAST_ROW {
+                value_w
+            } else {
+                block_w
+            };
+            let left = (cols - w) / 2;
+            write!(self.render, "{:left$}", "")?;
+            if line <= NOTE_LAST_ROW {
+                draw_big_text_row(
+                    &mut self.render,
+                    display.value,
+                    line,
+                )?;
+            } else {
+                let text = line
+                    .checked_sub(BIG_TEXT_ROWS + 1)
+                    .and_then(|i| display.lines.get(i))
+                    .map_or("", |t| truncate(t, w));
+                write!(self.render, "{text:w$}")?;
+            }
+
+            let rest = cols - left - w;
+            write!(self.render, "{:rest$}", "")?;
+            if y + 1 < area {
+                self.render.extend_from_slice(b"\r\n");
+            }
+        }
+
+        out.write_all(&self.render)?;
+        out.flush()?;
+        Ok(())
+    }
+
+    /// Room for the stereo view: a goniometer about as tall as it is
+    /// wide, and one balance band per two columns to its right.
+    #[allow(clippy::arithmetic_side_effects)]
+    pub fn stereo_area(&self) -> StereoArea {
+        let cols = usize::from(self.w);
+        let area = usize::from(self.h.saturating_sub(self.top_pad));
+        let rows = area.saturating_sub(STEREO_GAUGE_ROWS).max(1);
+        let gonio = (2 * rows).min(cols / 2);
+        // the first two columns hold the L and R marks
+        let bands = cols.saturating_sub(gonio + STEREO_GAP + 2) / 2;
+        StereoArea {
+            cols: gonio,
+            rows,
+            bands,
+        }
+    }
+
+    /// Draws the goniometer with the band balance to its right, above
+    /// the correlation gauge.
+    #[allow(clippy::arithmetic_side_effects)]
+    pub fn draw_stereo<W: Write>(
+        &mut self,
+        stereo: &StereoDisplay<'_>,
+        style: &PaintStyle,
+        out: &mut W,
+    ) -> Result<()> {
+        self.begin(style, out)?;
+        let cols = usize::from(self.w);
+        let area = usize::from(self.h.saturating_sub(self.top_pad));
+        let plot = self.stereo_area();
+
+        for y in 0..area {
+            let used = if y < plot.rows {
+                self.draw_stereo_row(stereo, &plot, y)?
+            } else {
+                self.draw_correlation_row(
+                    stereo.correlation,
+                    y - plot.rows,
+                )?
+            };
+            let rest = cols.saturating_sub(used);
+            write!(self.render, "{:rest$}", "")?;
+            if y + 1 < area {
+                self.render.extend_from_slice(b"\r\n");
+            }
+        }
+
+        out.write_all(&self.render)?;
+        out.flush()?;
+        Ok(())
+    }
+
+    /// One row of the goniometer and band balance, returning the
+    /// columns written. Bands leaning right grow up from the middle
+    /// row and bands leaning left grow down.
+    #[allow(clippy::arithmetic_side_effects)]
+    fn draw_stereo_row(
+        &mut self,
+        stereo: &StereoDisplay<'_>,
+        plot: &StereoArea,
+        y: usize,
+    ) -> Result<usize> {
+        stereo.canvas.draw_row(&mut self.render, y)?;
+        if plot.bands == 0 {
+            return Ok(plot.cols);
+        }
+
+        let mid = plot.rows / 2;
+        let mark = match y {
+            0 => "R",
+            _ if y + 1 == plot.rows => "L",
+            _ => " ",
+        };
+        write!(self.render, "{:STEREO_GAP$}{mark} ", "")?;
+        for &b in stereo.bands.iter().take(plot.bands) {
+            let cell = balance_cell(b, y, mid, plot.rows);
+            write!(self.render, "{cell} ")?;
+        }
+        Ok(plot.cols + STEREO_GAP + 2 + 2 * plot.bands)
+    }
+
+    /// One of the rows under the plots: a gap, then the correlation
+    /// needle, scale and labels, centred.
+    #[allow(clippy::arithmetic_side_effects)]
+    fn draw_correlation_row(
+        &mut self,
+        correlation: f32,
+        line: usize,
+    ) -> Result<usize> {
+        let cols = usize::from(self.w);
+        let w = (cols.saturating_sub(4).min(GAUGE_MAX_W)) | 1;
+        let w = if line == 0 { 0 } else { w.min(cols) };
+        let left = (cols - w) / 2;
+        write!(self.render, "{:left$}", "")?;
+
+        match line {
+            1 => draw_gauge_needle(
+                &mut self.render,
+                Some(correlation * GAUGE_RANGE_CENTS),
+                w,
+            )?,
+            2 => draw_gauge_scale(&mut self.render, w)?,
+            3 => draw_gauge_end_labels(
+                &mut self.render,
+                "-1",
+                "+1",
+                w,
+            )?,
+            _ => {}
+        }
+        Ok(left + w)
+    }
+}
+
+/// The first `cols` characters of `text`, each drawn in one column.
+fn truncate(text: &str, cols: usize) -> &str {
+    text.char_indices()
+        .nth(cols)
+        .and_then(|(end, _)| text.get(..end))
+        .unwrap_or(text)
+}
+
+/// Cell of a balance band `b` on row `y` of a graph `rows` tall
+/// centred on row `mid`.
+#[allow(clippy::arithmetic_side_effects, clippy::cast_precision_loss)]
+fn balance_cell(
+    b: f32,
+    y: usize,
+    mid: usize,
+    rows: usize,
+) -> &'static str {
+    let (reach, dist) = match y.cmp(&mid) {
+        Ordering::Less => (b * mid as f32, mid - y),
+        Ordering::Greater => (-b * (rows - 1 - mid) as f32, y - mid),
+        Ordering::Equal => return "\u{2500}",
+    };
+    if reach >= dist as f32 - 0.5 {
+        "\u{2588}"
+    } else {
+        " "
+    }
+}
+
+const fn rgb(c: RgbColor) -> Color {
+    Color::Rgb {
+        r: c.r,
+        g: c.g,
+        b: c.b,
+    }
 }
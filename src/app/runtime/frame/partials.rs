@@ -0,0 +1,122 @@
+use lookas::{
+    partials::{
+        Partial, PartialParams, Reassigner, strongest_partials,
+    },
+    tuner::Tuning,
+};
+use std::fmt::Write as _;
+
+use super::config::FrameConfig;
+
+/// How often the readout text changes, so it stays readable.
+const REFRESH_S: f32 = 0.1;
+
+/// One frame's spectrum and the samples it was taken from.
+pub struct PartialsFrame<'a> {
+    pub spec_pow: &'a [f32],
+    pub samples: &'a [f32],
+    pub bin_hz: f32,
+    pub dt_s: f32,
+}
+
+/// The strongest partials of the full-resolution spectrum, shown as
+/// a line of frequencies, notes and levels.
+pub struct FramePartials {
+    shown: bool,
+    reassigner: Option<Reassigner>,
+    partials: Vec<Partial>,
+    since_s: f32,
+    text: String,
+}
+
+impl FramePartials {
+    pub const fn new(shown: bool) -> Self {
+        Self {
+            shown,
+            reassigner: None,
+            partials: Vec::new(),
+            since_s: REFRESH_S,
+            text: String::new(),
+        }
+    }
+
+    pub const fn show(&mut self, shown: bool) {
+        self.shown = shown;
+    }
+
+    pub const fn toggle(&mut self) {
+        self.shown = !self.shown;
+        self.since_s = REFRESH_S;
+    }
+
+    /// Finds the partials of one spectrum, refining their frequency
+    /// by reassignment over `samples` when `peak_reassign` is on.
+    pub fn process(
+        &mut self,
+        frame: &PartialsFrame,
+        cfg: &FrameConfig,
+    ) {
+        if !self.shown {
+            self.reassigner = None;
+            return;
+        }
+        self.since_s += frame.dt_s;
+        if self.since_s < REFRESH_S {
+            return;
+        }
+        self.since_s = 0.0;
+
+        let params = PartialParams {
+            bin_hz: frame.bin_hz,
+            interpolation: cfg.peak_interpolation,
+            count: cfg.peak_count,
+        };
+        strongest_partials(
+            frame.spec_pow,
+            &params,
+            &mut self.partials,
+        );
+        if cfg.peak_reassign {
+            self.reassign(frame.samples, frame.bin_hz);
+        } else {
+            self.reassigner = None;
+        }
+        self.format(&cfg.tuning());
+    }
+
+    fn reassign(&mut self, samples: &[f32], bin_hz: f32) {
+        let reassigner = match &mut self.reassigner {
+            Some(r) if r.len() == samples.len() => r,
+            slot => slot.insert(Reassigner::new(samples.len())),
+        };
+        if !reassigner.process(samples) {
+            return;
+        }
+        for p in &mut self.partials {
+            if let Some(hz) = reassigner.frequency(p.bin, bin_hz) {
+                p.hz = hz;
+            }
+        }
+    }
+
+    fn format(&mut self, tuning: &Tuning) {
+        self.text.clear();
+        self.text.push_str(" peaks");
+        if self.partials.is_empty() {
+            self.text.push_str("  none");
+        }
+        for p in &self.partials {
+            let _ = write!(self.text, "  {:.1} Hz", p.hz);
+            if let Some(r) = tuning.nearest(p.hz) {
+                let _ =
+                    write!(self.text, " {}{}", r.name(), r.octave);
+            }
+            let _ = write!(self.text, " {:.0} dB ", p.db);
+        }
+    }
+
+    /// The readout, when it is on.
+    pub fn readout(&self) -> Option<&str> {
+        self.shown.then_some(self.text.as_str())
+    }
+}
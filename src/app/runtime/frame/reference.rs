@@ -0,0 +1,301 @@
+use lookas::{
+    analyzer::{BandParams, SpectrumAnalyzer, band_level_db},
+    ltas::{Ltas, Snapshot, octave_deltas},
+};
+use std::{fmt::Write as _, path::PathBuf};
+
+use super::{
+    ReferenceAction, config::FrameConfig, paint::ReferenceLine,
+};
+
+const MESSAGE_S: f32 = 3.0;
+
+/// One frame's raw spectrum and how its bars are scaled.
+pub struct ReferenceFrame<'a> {
+    pub spec_pow: &'a [f32],
+    pub dt_s: f32,
+    pub gate_open: bool,
+    pub bands: BandParams,
+}
+
+/// The long-term average and the frozen snapshot the live spectrum
+/// is compared against, with their heights per bar and the delta
+/// readout.
+pub struct FrameReference {
+    ltas: Ltas,
+    show_ltas: bool,
+    snapshot: Option<Snapshot>,
+    readout: bool,
+    live_db: Vec<f32>,
+    ltas_db: Vec<Option<f32>>,
+    snapshot_db: Vec<Option<f32>>,
+    ltas_heights: Vec<Option<f32>>,
+    snapshot_heights: Vec<Option<f32>>,
+    centers_hz: Vec<f32>,
+    deltas: Vec<(f32, f32)>,
+    levels: Vec<f32>,
+    text: String,
+    message: String,
+    message_s: f32,
+}
+
+impl FrameReference {
+    pub const fn new(window_s: Option<f32>, show_ltas: bool) -> Self {
+        Self {
+            ltas: Ltas::new(window_s),
+            show_ltas,
+            snapshot: None,
+            readout: false,
+            live_db: Vec::new(),
+            ltas_db: Vec::new(),
+            snapshot_db: Vec::new(),
+            ltas_heights: Vec::new(),
+            snapshot_heights: Vec::new(),
+            centers_hz: Vec::new(),
+            deltas: Vec::new(),
+            levels: Vec::new(),
+            text: String::new(),
+            message: String::new(),
+            message_s: 0.0,
+        }
+    }
+
+    pub const fn set_window(&mut self, window_s: Option<f32>) {
+        self.ltas.set_window(window_s);
+    }
+
+    pub const fn show_ltas(&mut self, show: bool) {
+        self.show_ltas = show;
+    }
+
+    /// Runs one key, using the analyzer's smoothed spectrum as the
+    /// live one.
+    pub fn act(
+        &mut self,
+        action: ReferenceAction,
+        analyzer: &SpectrumAnalyzer,
+        path: Option<&str>,
+    ) {
+        self.message.clear();
+        match action {
+            ReferenceAction::Freeze => {
+                self.snapshot = Some(Snapshot::from_spectrum(
+                    &analyzer.filters,
+                    &analyzer.spec_pow_smooth,
+                ));
+                self.message.push_str(" snapshot taken");
+            }
+            ReferenceAction::FreezeLtas => self.freeze_ltas(analyzer),
+            ReferenceAction::ClearSnapshot => {
+                self.snapshot = None;
+                self.message.push_str(" snapshot cleared");
+            }
+            ReferenceAction::ToggleLtas => {
+                self.show_ltas = !self.show_ltas;
+            }
+            ReferenceAction::ResetLtas => {
+                self.ltas.reset();
+                self.message.push_str(" long-term average reset");
+            }
+            ReferenceAction::Save => self.save(path),
+            ReferenceAction::Load => self.load(path),
+            ReferenceAction::ToggleReadout => {
+                self.readout = !self.readout;
+            }
+        }
+        self.message_s = if self.message.is_empty() {
+            0.0
+        } else {
+            MESSAGE_S
+        };
+    }
+
+    fn freeze_ltas(&mut self, analyzer: &SpectrumAnalyzer) {
+        if self.ltas.is_empty() {
+            self.message.push_str(" no long-term average yet");
+            return;
+        }
+        self.snapshot = Some(Snapshot::from_spectrum(
+            &analyzer.filters,
+            self.ltas.power(),
+        ));
+        let _ = write!(
+            self.message,
+            " snapshot of {:.0} s long-term average taken",
+            self.ltas.elapsed_s()
+        );
+    }
+
+    fn save(&mut self, path: Option<&str>) {
+        let Some(snapshot) = &self.snapshot else {
+            self.message.push_str(" no snapshot to save");
+            return;
+        };
+        let _ = match snapshot_path(path) {
+            Ok(path) => match snapshot.save_to(&path) {
+                Ok(()) => write!(
+                    self.message,
+                    " snapshot saved to {}",
+                    path.display()
+                ),
+                Err(err) => write!(self.message, " {err:#}"),
+            },
+            Err(err) => write!(self.message, " {err:#}"),
+        };
+    }
+
+    fn load(&mut self, path: Option<&str>) {
+        let loaded = snapshot_path(path).and_then(|path| {
+            Snapshot::load_from(&path)
+                .map(|snapshot| (path, snapshot))
+        });
+        let _ = match loaded {
+            Ok((path, snapshot)) => {
+                self.snapshot = Some(snapshot);
+                write!(
+                    self.message,
+                    " snapshot loaded from {}",
+                    path.display()
+                )
+            }
+            Err(err) => write!(self.message, " {err:#}"),
+        };
+    }
+
+    /// Feeds one raw spectrum to the long-term average while the gate
+    /// is open, then refreshes the lines and the readout.
+    pub fn process(
+        &mut self,
+        analyzer: &SpectrumAnalyzer,
+        frame: &ReferenceFrame,
+    ) {
+        self.message_s = (self.message_s - frame.dt_s).max(0.0);
+        if frame.gate_open {
+            self.ltas.process(frame.spec_pow, frame.dt_s);
+        }
+
+        self.levels_for(analyzer);
+        fill_heights(
+            &mut self.ltas_heights,
+            &self.ltas_db,
+            analyzer,
+            &frame.bands,
+        );
+        fill_heights(
+            &mut self.snapshot_heights,
+            &self.snapshot_db,
+            analyzer,
+            &frame.bands,
+        );
+        self.format_readout();
+    }
+
+    fn levels_for(&mut self, analyzer: &SpectrumAnalyzer) {
+        let filters = &analyzer.filters;
+        self.centers_hz.clear();
+        self.centers_hz.extend(filters.iter().map(|t| t.center_hz));
+
+        self.live_db.clear();
+        if self.readout {
+            self.live_db.extend(filters.iter().map(|tri| {
+                band_level_db(tri, &analyzer.spec_pow_smooth)
+            }));
+        }
+
+        self.ltas_db.clear();
+        if self.show_ltas || self.readout {
+            self.ltas.levels_db(filters, &mut self.levels);
+            self.ltas_db
+                .extend(self.levels.iter().copied().map(Some));
+        }
+
+        self.snapshot_db.clear();
+        if let Some(snapshot) = &self.snapshot {
+            self.snapshot_db.extend(
+                self.centers_hz
+                    .iter()
+                    .map(|&hz| snapshot.level_at(hz)),
+            );
+        }
+    }
+
+    fn format_readout(&mut self) {
+        self.text.clear();
+        if !self.readout {
+            return;
+        }
+
+        let (name, reference) = if self.snapshot.is_some() {
+            ("snapshot", &self.snapshot_db)
+        } else {
+            ("average", &self.ltas_db)
+        };
+        octave_deltas(
+            &self.centers_hz,
+            &self.live_db,
+            reference,
+            &mut self.deltas,
+        );
+        if self.deltas.is_empty() {
+            let _ = write!(
+                self.text,
+                " live vs {name}: no reference yet"
+            );
+            return;
+        }
+
+        let _ = write!(self.text, " live vs {name} dB:");
+        for &(hz, delta) in &self.deltas {
+            let _ = if hz >= 1000.0 {
+                write!(self.text, "  {:.0}k {delta:+.1}", hz / 1000.0)
+            } else {
+                write!(self.text, "  {hz:.0} {delta:+.1}")
+            };
+        }
+    }
+
+    /// Lines to draw over the bars, the snapshot in front.
+    pub fn lines(&self, cfg: &FrameConfig) -> [ReferenceLine<'_>; 2] {
+        let ltas: &[Option<f32>] = if self.show_ltas {
+            &self.ltas_heights
+        } else {
+            &[]
+        };
+        [
+            ReferenceLine {
+                heights: ltas,
+                color: cfg.ltas_color,
+            },
+            ReferenceLine {
+                heights: &self.snapshot_heights,
+                color: cfg.snapshot_color,
+            },
+        ]
+    }
+
+    /// A save or load result while it is fresh.
+    pub fn message(&self) -> Option<&str> {
+        (self.message_s > 0.0).then_some(self.message.as_str())
+    }
+
+    /// The delta readout, when it is on.
+    pub fn readout(&self) -> Option<&str> {
+        self.readout.then_some(self.text.as_str())
+    }
+}
+
+fn fill_heights(
+    heights: &mut Vec<Option<f32>>,
+    levels_db: &[Option<f32>],
+    analyzer: &SpectrumAnalyzer,
+    params: &BandParams,
+) {
+    heights.clear();
+    heights.extend(levels_db.iter().enumerate().map(|(band, db)| {
+        db.map(|db| analyzer.level_height(band, db, params))
+    }));
+}
+
+fn snapshot_path(path: Option<&str>) -> anyhow::Result<PathBuf> {
+    path.map_or_else(Snapshot::path, |p| Ok(PathBuf::from(p)))
+}
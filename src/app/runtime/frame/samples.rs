@@ -1,88 +1,205 @@
-use lookas::audio::AudioMode;
+use lookas::{
+    audio::AudioMode,
+    dsp::{Preprocess, PreprocessParams},
+};
 
 use super::Runtime;
 
 pub struct FrameSamples {
+    fft_size: usize,
     mic_tail: Vec<f32>,
     sys_tail: Vec<f32>,
+    /// This frame's samples: every one that arrived since the
+    /// previous frame, and at least the latest `fft_size`.
     mix: Vec<f32>,
+    /// The latest `fft_size` samples after the pre-processing chain.
+    processed: Vec<f32>,
+    processed_fresh: Vec<f32>,
+    chain: Preprocess,
+    mic_written: u64,
+    sys_written: u64,
+    mic_fresh: usize,
+    fresh: usize,
 }
 
 struct AudioReady {
-    mic: bool,
-    system: bool,
+    mic: Option<usize>,
+    system: Option<usize>,
 }
 
 impl FrameSamples {
-    pub fn new(fft_size: usize) -> Self {
+    pub fn new(fft_size: usize, params: PreprocessParams) -> Self {
         Self {
+            fft_size,
             mic_tail: Vec::with_capacity(fft_size),
             sys_tail: Vec::with_capacity(fft_size),
             mix: vec![0.0f32; fft_size],
+            processed: vec![0.0f32; fft_size],
+            processed_fresh: Vec::with_capacity(fft_size),
+            chain: Preprocess::new(params, 0.0),
+            mic_written: 0,
+            sys_written: 0,
+            mic_fresh: 0,
+            fresh: 0,
         }
     }
 
-    pub fn len(&self) -> usize {
-        self.mix.len()
+    pub const fn len(&self) -> usize {
+        self.fft_size
     }
 
-    pub fn mix(&self) -> &[f32] {
-        &self.mix
+    /// The latest `fft_size` samples after the pre-processing chain.
+    pub fn processed(&self) -> &[f32] {
+        &self.processed
+    }
+
+    /// Every sample that arrived since the previous frame, after the
+    /// pre-processing chain.
+    pub fn processed_fresh(&self) -> &[f32] {
+        &self.processed_fresh
+    }
+
+    /// Every sample that arrived since the previous frame.
+    pub fn fresh(&self) -> &[f32] {
+        tail(&self.mix, self.fresh)
+    }
+
+    /// The microphone samples that arrived since the previous frame,
+    /// whichever source is analysed. Empty while the microphone is
+    /// not captured.
+    pub fn mic_fresh(&self) -> &[f32] {
+        tail(&self.mic_tail, self.mic_fresh)
     }
 
     pub fn resize(&mut self, fft_size: usize) {
+        self.fft_size = fft_size;
         self.mic_tail = Vec::with_capacity(fft_size);
         self.sys_tail = Vec::with_capacity(fft_size);
         self.mix = vec![0.0; fft_size];
+        self.processed = vec![0.0; fft_size];
+        self.processed_fresh.clear();
+        self.mic_written = 0;
+        self.sys_written = 0;
+        self.mic_fresh = 0;
+        self.fresh = 0;
+    }
+
+    pub const fn preprocess(&self) -> PreprocessParams {
+        *self.chain.params()
+    }
+
+    pub fn set_preprocess(&mut self, params: PreprocessParams) {
+        if *self.chain.params() != params {
+            self.chain =
+                Preprocess::new(params, self.chain.sample_rate());
+        }
     }
 
     pub fn prepare(&mut self, runtime: &Runtime) -> bool {
+        self.prepare_source(runtime, runtime.mode())
+    }
+
+    /// Like [`FrameSamples::prepare`], taking the samples of `mode`
+    /// rather than of the current mode.
+    pub fn prepare_source(
+        &mut self,
+        runtime: &Runtime,
+        mode: AudioMode,
+    ) -> bool {
         let ready = self.copy_tails(runtime);
+        self.mic_fresh = ready.mic.unwrap_or(0);
 
-        match runtime.mode() {
+        let fresh = match mode {
             AudioMode::Mic => self.copy_mic(ready.mic),
             AudioMode::System => self.copy_system(ready.system),
-            AudioMode::Both => {
-                self.mix_samples(runtime.fft_size(), &ready)
-            }
+            AudioMode::Both => self.mix_samples(&ready),
+        };
+
+        if let Some(fresh) = fresh {
+            self.fresh = fresh;
+            self.filter_fresh(runtime.sample_rate());
         }
+        fresh.is_some()
     }
 
-    fn copy_tails(&mut self, runtime: &Runtime) -> AudioReady {
-        AudioReady {
-            mic: runtime.copy_mic_tail(&mut self.mic_tail),
-            system: runtime.copy_system_tail(&mut self.sys_tail),
+    /// Runs only the newly arrived samples through the chain, so each
+    /// sample passes the stateful filters exactly once.
+    fn filter_fresh(&mut self, sample_rate: f32) {
+        if self.chain.sample_rate().to_bits() != sample_rate.to_bits()
+        {
+            self.chain =
+                Preprocess::new(*self.chain.params(), sample_rate);
         }
-    }
 
-    fn copy_mic(&mut self, ready: bool) -> bool {
-        if ready {
-            self.mix.copy_from_slice(&self.mic_tail);
+        self.processed_fresh.clear();
+        for &x in tail(&self.mix, self.fresh) {
+            self.processed_fresh.push(self.chain.process(x));
+        }
+
+        let n = self.processed_fresh.len().min(self.processed.len());
+        self.processed.copy_within(n.., 0);
+
+        let start = self.processed.len().saturating_sub(n);
+        if let Some(dst) = self.processed.get_mut(start..) {
+            dst.copy_from_slice(tail(&self.processed_fresh, n));
         }
-        ready
     }
 
-    fn copy_system(&mut self, ready: bool) -> bool {
-        if ready {
-            self.mix.copy_from_slice(&self.sys_tail);
+    /// Copies what each source wrote since the previous frame, and
+    /// counts it.
+    fn copy_tails(&mut self, runtime: &Runtime) -> AudioReady {
+        let mic = runtime
+            .copy_mic_since(self.mic_written, &mut self.mic_tail)
+            .map(|w| advance(&mut self.mic_written, w));
+        let system = runtime
+            .copy_system_since(self.sys_written, &mut self.sys_tail)
+            .map(|w| advance(&mut self.sys_written, w));
+
+        AudioReady {
+            mic: mic.map(|n| n.min(self.mic_tail.len())),
+            system: system.map(|n| n.min(self.sys_tail.len())),
         }
-        ready
     }
 
-    fn mix_samples(
-        &mut self,
-        fft_size: usize,
-        ready: &AudioReady,
-    ) -> bool {
-        if !ready.mic || !ready.system {
-            return false;
+    fn copy_mic(&mut self, fresh: Option<usize>) -> Option<usize> {
+        if fresh.is_some() {
+            self.mix.clone_from(&self.mic_tail);
         }
+        fresh
+    }
 
-        #[allow(clippy::indexing_slicing)]
-        for i in 0..fft_size {
-            self.mix[i] = (self.mic_tail[i] + self.sys_tail[i]) * 0.5;
+    fn copy_system(&mut self, fresh: Option<usize>) -> Option<usize> {
+        if fresh.is_some() {
+            self.mix.clone_from(&self.sys_tail);
         }
+        fresh
+    }
+
+    /// Averages the two sources over what both wrote since the
+    /// previous frame.
+    fn mix_samples(&mut self, ready: &AudioReady) -> Option<usize> {
+        let fresh = ready.mic?.min(ready.system?);
+        let n = fresh.max(self.fft_size);
 
-        true
+        self.mix.clear();
+        self.mix.extend(
+            tail(&self.mic_tail, n)
+                .iter()
+                .zip(tail(&self.sys_tail, n))
+                .map(|(&m, &s)| (m + s) * 0.5),
+        );
+        Some(fresh)
     }
 }
+
+/// The last `n` samples of `samples`, or all of them when shorter.
+fn tail(samples: &[f32], n: usize) -> &[f32] {
+    let start = samples.len().saturating_sub(n);
+    samples.get(start..).unwrap_or(samples)
+}
+
+fn advance(prev: &mut u64, now: u64) -> usize {
+    let n = now.wrapping_sub(*prev);
+    *prev = now;
+    usize::try_from(n).unwrap_or(usize::MAX)
+}
@@ -0,0 +1,135 @@
+use lookas::{
+    analyzer::{BandGates, SpectrumAnalyzer},
+    audio::AudioMode,
+    dsp::PreprocessParams,
+};
+
+use super::{
+    GateState, Runtime, analysis::Analysis, config::FrameConfig,
+    sample_power, samples::FrameSamples, voice_features,
+};
+
+/// Bin width, frame time and gate state for one animation step.
+pub struct Step {
+    pub bin_hz: f32,
+    pub dt_s: f32,
+    pub gate_open: bool,
+}
+
+/// Moves the bars of `analyzer` toward a new power spectrum.
+pub fn animate(
+    analyzer: &mut SpectrumAnalyzer,
+    spec_pow: &[f32],
+    cfg: &FrameConfig,
+    step: &Step,
+) {
+    analyzer.update_spectrum(
+        spec_pow,
+        &cfg.smoothing_params(step.bin_hz),
+        step.dt_s,
+    );
+    analyzer.analyze_bands(
+        &cfg.band_params(),
+        step.dt_s,
+        step.gate_open,
+    );
+    analyzer.apply_flow_and_spring(
+        &cfg.flow_spring_params(),
+        step.dt_s,
+        step.gate_open,
+    );
+    if cfg.peak_caps {
+        analyzer.update_peaks(&cfg.peak_params(), step.dt_s);
+    }
+}
+
+/// Spectrum of the system audio on its own, drawn next to the
+/// microphone when both are captured. It has its own samples, gate
+/// and bar state, and copies filters and noise from the main
+/// analyzer.
+pub struct SourcePipeline {
+    samples: FrameSamples,
+    analysis: Analysis,
+    pub analyzer: SpectrumAnalyzer,
+    pub gate: GateState,
+}
+
+impl SourcePipeline {
+    /// A pipeline matching the main one, which feeds `main`.
+    pub fn new(
+        cfg: &FrameConfig,
+        main: &FrameSamples,
+        gate: GateState,
+    ) -> Self {
+        let fft_size = main.len();
+        let analysis = Analysis::new(cfg.analysis, fft_size);
+        Self {
+            samples: FrameSamples::new(fft_size, main.preprocess()),
+            analyzer: SpectrumAnalyzer::new(analysis.half()),
+            analysis,
+            gate,
+        }
+    }
+
+    /// Takes the filters of `main` when its bands have changed, and
+    /// its noise profile and gating.
+    pub fn sync_filters(&mut self, main: &SpectrumAnalyzer) {
+        if self.analyzer.filters.len() != main.filters.len() {
+            self.analyzer.filters.clone_from(&main.filters);
+            self.analyzer.resize(main.filters.len());
+        }
+        self.analyzer.noise_pow.clone_from(&main.noise_pow);
+        self.analyzer.set_band_gating(
+            main.band_gates.as_ref().map(BandGates::min_db),
+        );
+    }
+
+    pub fn set_preprocess(&mut self, params: PreprocessParams) {
+        self.samples.set_preprocess(params);
+    }
+
+    pub fn reset_gate(&mut self) {
+        self.gate.reset();
+        if let Some(gates) = &mut self.analyzer.band_gates {
+            gates.reset();
+        }
+    }
+
+    /// Analyses the latest system samples, returning `false` when not
+    /// enough have arrived.
+    pub fn process(
+        &mut self,
+        runtime: &Runtime,
+        cfg: &FrameConfig,
+        dt_s: f32,
+    ) -> bool {
+        if !self.samples.prepare_source(runtime, AudioMode::System) {
+            return false;
+        }
+
+        let fft_size = runtime.fft_size();
+        let power = sample_power(self.samples.processed(), fft_size);
+        self.analysis.compute(self.samples.processed(), fft_size);
+        let voice = voice_features(
+            &self.gate,
+            &self.analysis,
+            self.samples.processed(),
+            runtime,
+        );
+        self.gate.tick(power, voice.as_ref(), dt_s);
+
+        #[allow(clippy::cast_precision_loss)]
+        let bin_hz = runtime.sample_rate() / fft_size as f32;
+        animate(
+            &mut self.analyzer,
+            self.analysis.spec_pow(),
+            cfg,
+            &Step {
+                bin_hz,
+                dt_s,
+                gate_open: self.gate.open,
+            },
+        );
+        true
+    }
+}
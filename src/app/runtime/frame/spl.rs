@@ -0,0 +1,186 @@
+use lookas::spl::{SplMeter, SplParams, SplReading};
+use std::fmt::Write as _;
+
+use super::SplAction;
+
+/// How long an SPL calibration listens to the calibrator.
+const CALIBRATE_S: f32 = 3.0;
+const MESSAGE_S: f32 = 5.0;
+
+/// The sound level meter shown on the status row.
+pub struct FrameSpl {
+    meter: Option<SplMeter>,
+    /// The configured settings, with a measured offset in place of
+    /// the configured one.
+    params: SplParams,
+    /// `spl_offset_db` as last configured.
+    configured_offset_db: Option<f32>,
+    /// The offset measured with `L`, kept until the configured
+    /// offset changes.
+    calibrated_db: Option<f32>,
+    reference_db: f32,
+    text: String,
+    message: String,
+    message_s: f32,
+}
+
+impl FrameSpl {
+    pub const fn new(params: SplParams, reference_db: f32) -> Self {
+        Self {
+            meter: None,
+            params,
+            configured_offset_db: params.offset_db,
+            calibrated_db: None,
+            reference_db,
+            text: String::new(),
+            message: String::new(),
+            message_s: 0.0,
+        }
+    }
+
+    /// Takes new settings, restarting the session when they change.
+    /// A measured calibration survives unless the configured offset
+    /// changes.
+    pub fn configure(
+        &mut self,
+        params: SplParams,
+        reference_db: f32,
+    ) {
+        if self.configured_offset_db != params.offset_db {
+            self.configured_offset_db = params.offset_db;
+            self.calibrated_db = None;
+        }
+        let params = SplParams {
+            offset_db: self.calibrated_db.or(params.offset_db),
+            ..params
+        };
+        if self.params != params {
+            self.params = params;
+            self.meter = None;
+        }
+        self.reference_db = reference_db;
+    }
+
+    pub fn act(&mut self, action: SplAction) {
+        let Some(meter) = &mut self.meter else {
+            return;
+        };
+        self.message.clear();
+        match action {
+            SplAction::NextTimeWeighting => {
+                meter.set_time_weighting(meter.params().time.next());
+            }
+            SplAction::Calibrate => {
+                meter.calibrate(self.reference_db, CALIBRATE_S);
+            }
+            SplAction::Reset => meter.reset(),
+        }
+    }
+
+    /// Measures the microphone samples that arrived this frame. The
+    /// clock stands still while there are none.
+    pub fn process(
+        &mut self,
+        fresh: &[f32],
+        sample_rate: f32,
+        dt_s: f32,
+    ) {
+        if self.meter.as_ref().is_some_and(|m| {
+            m.sample_rate().to_bits() != sample_rate.to_bits()
+        }) {
+            self.meter = None;
+        }
+        let params = self.params;
+        let meter = self.meter.get_or_insert_with(|| {
+            SplMeter::new(sample_rate, params)
+        });
+
+        let was_calibrating = meter.calibrating();
+        meter.process(fresh);
+        self.message_s = (self.message_s - dt_s).max(0.0);
+        if was_calibrating && !meter.calibrating() {
+            self.message.clear();
+            if let Some(offset) = meter.params().offset_db {
+                self.calibrated_db = Some(offset);
+                self.params.offset_db = Some(offset);
+                let _ = write!(
+                    self.message,
+                    "   calibrated: spl_offset_db = {offset:.1}"
+                );
+                self.message_s = MESSAGE_S;
+            }
+        }
+        self.format();
+    }
+
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    fn format(&mut self) {
+        self.text.clear();
+        let Some(meter) = &self.meter else {
+            return;
+        };
+        let p = meter.params();
+        let r = meter.reading();
+        let (f, t) = (p.frequency.letter(), p.time.letter());
+        let unit = if p.offset_db.is_some() { "dB" } else { "dBFS" };
+        let _ = write!(
+            self.text,
+            " L{f}{t} {} {unit}   L{f}eq {}   L{f}{t}max {}",
+            level(r.level),
+            level(r.leq),
+            level(r.lmax),
+        );
+
+        if meter.calibrating() {
+            let _ = write!(
+                self.text,
+                "   calibrating to {:.1} dB...",
+                self.reference_db
+            );
+            return;
+        }
+        match r.dose {
+            Some(dose) => {
+                let _ = write!(
+                    self.text,
+                    "   dose {dose:.1}% {}",
+                    p.standard.name()
+                );
+            }
+            None => {
+                let _ = write!(
+                    self.text,
+                    "   uncalibrated: L with a {:.0} dB calibrator",
+                    self.reference_db
+                );
+            }
+        }
+        format_elapsed(&mut self.text, &r);
+        if self.message_s > 0.0 {
+            self.text.push_str(&self.message);
+        }
+    }
+}
+
+fn level(db: f32) -> String {
+    if db.is_finite() {
+        format!("{db:>5.1}")
+    } else {
+        String::from("  --")
+    }
+}
+
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn format_elapsed(text: &mut String, r: &SplReading) {
+    let s = r.elapsed_s.max(0.0) as u64;
+    let _ = write!(
+        text,
+        "   {}:{:02}:{:02}",
+        s / 3600,
+        s / 60 % 60,
+        s % 60
+    );
+}
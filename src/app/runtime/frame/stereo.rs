@@ -0,0 +1,216 @@
+use lookas::{
+    audio::AudioMode,
+    dsp::ema_tc,
+    filterbank::{FilterbankParams, Tri, build_filterbank},
+    render::BrailleCanvas,
+    stereo::{StereoMeter, StereoSpectra, goniometer_point},
+};
+use std::fmt::Write as _;
+
+use super::{Runtime, StereoTail, fft::FftState};
+
+/// Decay of the goniometer zoom, so the trace fills the canvas at
+/// any level.
+const SCALE_TAU_S: f32 = 1.5;
+/// Balance still shown as centred, in dB.
+const CENTRE_DB: f32 = 0.5;
+
+/// Size of the stereo view: the goniometer in cells and the number
+/// of balance bands next to it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct StereoArea {
+    pub cols: usize,
+    pub rows: usize,
+    pub bands: usize,
+}
+
+/// What the stereo view measures this frame: its size, the
+/// frequency range of its bands and the frame step.
+pub struct StereoFrame {
+    pub area: StereoArea,
+    pub fmin: f32,
+    pub fmax: f32,
+    pub dt_s: f32,
+    pub gate_open: bool,
+}
+
+pub struct FrameStereo {
+    meter: StereoMeter,
+    main: StereoTail,
+    other: StereoTail,
+    fft: (FftState, FftState),
+    filters: Vec<Tri>,
+    params: Option<FilterbankParams>,
+    canvas: BrailleCanvas,
+    scale: f32,
+    text: String,
+}
+
+impl FrameStereo {
+    pub fn new(fft_size: usize) -> Self {
+        Self {
+            meter: StereoMeter::new(),
+            main: StereoTail::default(),
+            other: StereoTail::default(),
+            fft: (FftState::new(fft_size), FftState::new(fft_size)),
+            filters: Vec::new(),
+            params: None,
+            canvas: BrailleCanvas::default(),
+            scale: 0.0,
+            text: String::new(),
+        }
+    }
+
+    pub fn reset(&mut self) {
+        self.meter.reset();
+        self.canvas.clear();
+        self.scale = 0.0;
+    }
+
+    pub fn resize(&mut self, area: StereoArea) {
+        if self.canvas.cols() != area.cols
+            || self.canvas.rows() != area.rows
+        {
+            self.canvas.resize(area.cols, area.rows);
+        }
+        if self.filters.len() != area.bands {
+            self.params = None;
+        }
+    }
+
+    /// Measures the current source and redraws the goniometer. While
+    /// the gate is closed the readings hold and the trace is blank.
+    pub fn process(
+        &mut self,
+        runtime: &Runtime,
+        frame: &StereoFrame,
+    ) {
+        self.canvas.clear();
+        if !frame.gate_open || !self.copy_tails(runtime) {
+            self.format();
+            return;
+        }
+
+        let fft_size = runtime.fft_size();
+        self.ensure_filters(FilterbankParams {
+            sr: runtime.sample_rate(),
+            fft_size,
+            bands: frame.area.bands,
+            fmin: frame.fmin,
+            fmax: frame.fmax,
+        });
+
+        let (left, right) = &self.main;
+        self.meter.process(left, right, frame.dt_s);
+        self.fft.0.compute(left, fft_size);
+        self.fft.1.compute(right, fft_size);
+        self.meter.process_bands(
+            &StereoSpectra {
+                filters: &self.filters,
+                left_pow: &self.fft.0.spec_pow,
+                right_pow: &self.fft.1.spec_pow,
+            },
+            frame.dt_s,
+        );
+        self.plot(frame.dt_s);
+        self.format();
+    }
+
+    pub const fn canvas(&self) -> &BrailleCanvas {
+        &self.canvas
+    }
+
+    pub fn bands(&self) -> &[f32] {
+        self.meter.bands()
+    }
+
+    pub fn correlation(&self) -> f32 {
+        self.meter.correlation()
+    }
+
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    fn copy_tails(&mut self, runtime: &Runtime) -> bool {
+        match runtime.mode() {
+            AudioMode::Mic => runtime.copy_mic_stereo(&mut self.main),
+            AudioMode::System => {
+                runtime.copy_system_stereo(&mut self.main)
+            }
+            AudioMode::Both => {
+                let ok = runtime.copy_mic_stereo(&mut self.main)
+                    && runtime.copy_system_stereo(&mut self.other);
+                if ok {
+                    self.mix_other();
+                }
+                ok
+            }
+        }
+    }
+
+    fn mix_other(&mut self) {
+        let (left, right) = &mut self.main;
+        let (other_left, other_right) = &self.other;
+        for (d, &x) in left
+            .iter_mut()
+            .zip(other_left)
+            .chain(right.iter_mut().zip(other_right))
+        {
+            *d = (*d + x) * 0.5;
+        }
+    }
+
+    fn ensure_filters(&mut self, params: FilterbankParams) {
+        if self.fft.0.half != params.fft_size / 2 {
+            self.fft = (
+                FftState::new(params.fft_size),
+                FftState::new(params.fft_size),
+            );
+        }
+        if self.params != Some(params) {
+            self.filters = build_filterbank(params);
+            self.params = Some(params);
+        }
+    }
+
+    fn plot(&mut self, dt_s: f32) {
+        let (left, right) = &self.main;
+        let peak = left
+            .iter()
+            .zip(right)
+            .map(|(&l, &r)| {
+                let (x, y) = goniometer_point(l, r);
+                x.abs().max(y.abs())
+            })
+            .fold(0.0, f32::max);
+        self.scale =
+            peak.max(ema_tc(self.scale, 0.0, SCALE_TAU_S, dt_s));
+        if self.scale <= 1e-6 {
+            return;
+        }
+
+        let inv = self.scale.recip();
+        for (&l, &r) in left.iter().zip(right) {
+            let (x, y) = goniometer_point(l, r);
+            self.canvas.plot(x * inv, y * inv);
+        }
+    }
+
+    fn format(&mut self) {
+        self.text.clear();
+        let db = self.meter.balance_db();
+        let _ = write!(
+            self.text,
+            " stereo   correlation {:>+5.2}   balance ",
+            self.meter.correlation()
+        );
+        let _ = if db.abs() < CENTRE_DB {
+            write!(self.text, "centre")
+        } else if db < 0.0 {
+            write!(self.text, "L {:.1} dB", -db)
+        } else {
+            write!(self.text, "R {db:.1} dB")
+        };
+    }
+}
@@ -0,0 +1,150 @@
+use lookas::{
+    audio::AudioMode,
+    dsp::ema_tc,
+    tuner::{Temperament, Tuner, Tuning, YinParams},
+};
+use std::fmt::Write as _;
+
+use super::{Runtime, source::Step};
+
+/// How long the last note stays on screen after the pitch is lost.
+const HOLD_S: f32 = 1.0;
+/// Smoothing of the needle while the same note is held.
+const NEEDLE_TAU_S: f32 = 0.08;
+/// Deviation still shown as in tune, in cents.
+const IN_TUNE_CENTS: f32 = 5.0;
+
+pub struct FrameTuner {
+    tuner: Tuner,
+    tuning: Tuning,
+    midi: Option<i32>,
+    cents: f32,
+    held_s: f32,
+    note: String,
+    detail: String,
+    status: String,
+}
+
+impl FrameTuner {
+    pub fn new(tuning: Tuning) -> Self {
+        Self {
+            tuner: Tuner::new(YinParams::DEFAULT, 0.0),
+            tuning,
+            midi: None,
+            cents: 0.0,
+            held_s: HOLD_S,
+            note: String::from("--"),
+            detail: String::new(),
+            status: String::new(),
+        }
+    }
+
+    pub const fn set_tuning(&mut self, tuning: Tuning) {
+        self.tuning = tuning;
+    }
+
+    pub fn reset(&mut self) {
+        self.tuner.reset();
+        self.clear();
+    }
+
+    /// Feeds every sample that arrived since the previous frame and
+    /// updates the reading. A closed gate counts as no pitch.
+    pub fn process(
+        &mut self,
+        fresh: &[f32],
+        runtime: &Runtime,
+        step: &Step,
+    ) {
+        let Step {
+            dt_s, gate_open, ..
+        } = *step;
+        let sr = runtime.sample_rate();
+        if self.tuner.sample_rate().to_bits() != sr.to_bits() {
+            self.tuner = Tuner::new(YinParams::DEFAULT, sr);
+        }
+        self.tuner.push(fresh);
+        self.format_status(runtime.mode());
+
+        let hz = gate_open
+            .then(|| self.tuner.estimate())
+            .flatten()
+            .map(|p| p.hz);
+        let Some((hz, r)) =
+            hz.and_then(|hz| Some((hz, self.tuning.nearest(hz)?)))
+        else {
+            self.held_s += dt_s;
+            if self.held_s > HOLD_S {
+                self.clear();
+            }
+            return;
+        };
+
+        self.held_s = 0.0;
+        self.cents = if self.midi == Some(r.midi) {
+            ema_tc(self.cents, r.cents, NEEDLE_TAU_S, dt_s)
+        } else {
+            r.cents
+        };
+        self.midi = Some(r.midi);
+
+        self.note.clear();
+        let _ = write!(self.note, "{}{}", r.name(), r.octave);
+        self.detail.clear();
+        let _ = write!(
+            self.detail,
+            "{:+.1} cents   {hz:.2} Hz   target {:.2} Hz",
+            self.cents, r.target_hz,
+        );
+        if self.cents.abs() <= IN_TUNE_CENTS {
+            self.detail.push_str("   in tune");
+        }
+    }
+
+    pub fn note(&self) -> &str {
+        &self.note
+    }
+
+    pub fn cents(&self) -> Option<f32> {
+        self.midi.map(|_| self.cents)
+    }
+
+    pub fn detail(&self) -> &str {
+        &self.detail
+    }
+
+    pub fn status(&self) -> &str {
+        &self.status
+    }
+
+    fn clear(&mut self) {
+        self.midi = None;
+        self.note.clear();
+        self.note.push_str("--");
+        self.detail.clear();
+    }
+
+    fn format_status(&mut self, mode: AudioMode) {
+        self.status.clear();
+        let temperament = match self.tuning.temperament {
+            Temperament::Equal => "equal",
+            Temperament::Just => "just",
+            Temperament::Pythagorean => "pythagorean",
+            Temperament::Werckmeister => "werckmeister",
+        };
+        let _ = write!(
+            self.status,
+            " tuner   A4 {:.1} Hz   {temperament} temperament",
+            self.tuning.a4_hz
+        );
+        if self.tuning.temperament != Temperament::Equal {
+            let root = lookas::chroma::PITCH_CLASSES
+                .get(self.tuning.root)
+                .unwrap_or(&"?");
+            let _ = write!(self.status, " on {root}");
+        }
+        if mode != AudioMode::Mic {
+            self.status.push_str("   press 1 for microphone input");
+        }
+    }
+}
@@ -1,24 +1,65 @@
-use lookas::dsp::ema_tc;
+use lookas::{
+    analyzer::DynamicsParams,
+    config::GateMode,
+    dsp::ema_tc,
+    vad::{VadFeatures, VadParams, VoiceDetector},
+};
 
+#[derive(Clone)]
 pub struct GateState {
     pub power_ema: f32,
     pub open: bool,
+    /// Whether the power alone would hold the gate open.
+    pub loud: bool,
     pub below_s: f32,
     pub attack_s: f32,
     pub release_s: f32,
     pub open_db: f32,
     pub close_db: f32,
     pub confirm_s: f32,
+    pub mode: GateMode,
+    pub voice: VoiceDetector,
 }
 
 impl GateState {
+    pub fn configure(
+        &mut self,
+        dynamics: &DynamicsParams,
+        gate_db: f32,
+        mode: GateMode,
+    ) {
+        self.attack_s = dynamics.gate_attack_s;
+        self.release_s = dynamics.gate_release_s;
+        self.confirm_s = dynamics.gate_confirm_s;
+        self.open_db = gate_db;
+        self.close_db = (gate_db - 3.0).max(-80.0);
+        self.mode = mode;
+    }
+
     pub fn reset(&mut self) {
         self.power_ema = 0.0;
         self.open = false;
+        self.loud = false;
         self.below_s = 0.0;
+        self.voice.reset();
+    }
+
+    /// Thresholds for the voice features, when the gate listens for
+    /// speech.
+    pub const fn vad_params(&self) -> Option<&VadParams> {
+        match self.mode {
+            GateMode::Power | GateMode::Bands => None,
+            GateMode::Voice => Some(self.voice.params()),
+        }
     }
 
-    pub fn tick(&mut self, power: f32, dt_s: f32) {
+    /// Takes the power of a frame and, in voice mode, its features.
+    pub fn tick(
+        &mut self,
+        power: f32,
+        features: Option<&VadFeatures>,
+        dt_s: f32,
+    ) {
         if self.power_ema == 0.0 {
             self.power_ema = power;
         } else {
@@ -32,11 +73,11 @@ impl GateState {
 
         let power_db = 10.0 * self.power_ema.max(1e-12).log10();
 
-        if self.open {
+        if self.loud {
             if power_db < self.close_db {
                 self.below_s += dt_s;
                 if self.below_s >= self.confirm_s {
-                    self.open = false;
+                    self.loud = false;
                     self.below_s = 0.0;
                 }
             } else {
@@ -45,8 +86,17 @@ impl GateState {
         } else {
             self.below_s = 0.0;
             if power_db > self.open_db {
-                self.open = true;
+                self.loud = true;
             }
         }
+
+        self.open = match self.mode {
+            GateMode::Power | GateMode::Bands => self.loud,
+            GateMode::Voice => self.voice.process(
+                &features.copied().unwrap_or_default(),
+                self.loud,
+                dt_s,
+            ),
+        };
     }
 }
@@ -9,6 +9,33 @@ use std::sync::{Arc, Mutex};
 pub enum KeyAction {
     Continue,
     Quit,
+    Calibrate,
+    NextView,
+    NextBothDisplay,
+    TogglePeaks,
+    Reference(ReferenceAction),
+    Spl(SplAction),
+}
+
+/// Keys for the long-term average and the snapshot.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReferenceAction {
+    Freeze,
+    FreezeLtas,
+    ClearSnapshot,
+    ToggleLtas,
+    ResetLtas,
+    Save,
+    Load,
+    ToggleReadout,
+}
+
+/// Keys for the sound level meter.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SplAction {
+    NextTimeWeighting,
+    Calibrate,
+    Reset,
 }
 
 pub struct InputContext<'a> {
@@ -24,6 +51,10 @@ pub fn handle_key(
 ) -> Result<KeyAction> {
     match code {
         KeyCode::Char('q') => return Ok(KeyAction::Quit),
+        KeyCode::Char('c') => return Ok(KeyAction::Calibrate),
+        KeyCode::Char('v') => return Ok(KeyAction::NextView),
+        KeyCode::Char('s') => return Ok(KeyAction::NextBothDisplay),
+        KeyCode::Char('p') => return Ok(KeyAction::TogglePeaks),
         KeyCode::Char('1') => switch_mode(AudioMode::Mic, ctx)?,
         KeyCode::Char('2') => switch_mode(AudioMode::System, ctx)?,
         KeyCode::Char('3') => switch_mode(AudioMode::Both, ctx)?,
@@ -34,11 +65,42 @@ pub fn handle_key(
                 ctx.sys_shared.clone(),
             )?;
         }
+        KeyCode::Char(c) => {
+            if let Some(action) = reference_action(c) {
+                return Ok(KeyAction::Reference(action));
+            }
+            if let Some(action) = spl_action(c) {
+                return Ok(KeyAction::Spl(action));
+            }
+        }
         _ => {}
     }
     Ok(KeyAction::Continue)
 }
 
+const fn reference_action(c: char) -> Option<ReferenceAction> {
+    Some(match c {
+        'f' => ReferenceAction::Freeze,
+        'F' => ReferenceAction::FreezeLtas,
+        'x' => ReferenceAction::ClearSnapshot,
+        'a' => ReferenceAction::ToggleLtas,
+        'A' => ReferenceAction::ResetLtas,
+        'w' => ReferenceAction::Save,
+        'o' => ReferenceAction::Load,
+        'd' => ReferenceAction::ToggleReadout,
+        _ => return None,
+    })
+}
+
+const fn spl_action(c: char) -> Option<SplAction> {
+    Some(match c {
+        'l' => SplAction::NextTimeWeighting,
+        'L' => SplAction::Calibrate,
+        'z' => SplAction::Reset,
+        _ => return None,
+    })
+}
+
 fn switch_mode(
     mode: AudioMode,
     ctx: &mut InputContext<'_>,
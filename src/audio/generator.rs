@@ -0,0 +1,100 @@
+use anyhow::{Context, Result};
+use cpal::traits::{DeviceTrait, HostTrait, StreamTrait};
+use cpal::{
+    Device, FromSample, Sample, SampleFormat, SizedSample,
+    StreamConfig,
+};
+use std::f64::consts::TAU;
+
+/// A sine test tone on the default output device, for measuring a
+/// playback chain through the microphone or system capture. The tone
+/// plays until the generator is dropped.
+pub struct ToneGenerator {
+    _stream: cpal::Stream,
+    hz: f32,
+    sample_rate: u32,
+}
+
+impl ToneGenerator {
+    /// Starts a tone of `hz` at `level_db` dBFS peak on every channel.
+    pub fn start(hz: f32, level_db: f32) -> Result<Self> {
+        let device = cpal::default_host()
+            .default_output_device()
+            .context("No default output device")?;
+        let supported_cfg = device.default_output_config()?;
+        let cfg = supported_cfg.config();
+        let sample_rate = cfg.sample_rate.0;
+        let tone = Tone {
+            step: TAU * f64::from(hz) / f64::from(sample_rate),
+            amp: 10.0f32.powf(level_db.min(0.0) / 20.0),
+        };
+
+        let stream = match supported_cfg.sample_format() {
+            SampleFormat::F32 => {
+                build_tone::<f32>(&device, &cfg, tone)?
+            }
+            SampleFormat::I16 => {
+                build_tone::<i16>(&device, &cfg, tone)?
+            }
+            SampleFormat::U16 => {
+                build_tone::<u16>(&device, &cfg, tone)?
+            }
+            _ => anyhow::bail!("Unsupported sample format"),
+        };
+
+        stream.play()?;
+
+        Ok(Self {
+            _stream: stream,
+            hz,
+            sample_rate,
+        })
+    }
+
+    #[must_use]
+    pub const fn hz(&self) -> f32 {
+        self.hz
+    }
+
+    #[must_use]
+    pub const fn sample_rate(&self) -> u32 {
+        self.sample_rate
+    }
+}
+
+#[derive(Clone, Copy)]
+struct Tone {
+    step: f64,
+    amp: f32,
+}
+
+fn build_tone<T>(
+    device: &Device,
+    cfg: &StreamConfig,
+    tone: Tone,
+) -> Result<cpal::Stream>
+where
+    T: Sample + SizedSample + FromSample<f32>,
+{
+    let ch = usize::from(cfg.channels).max(1);
+    let err_fn = |err: cpal::StreamError| {
+        eprintln!("[lookas] tone generator error: {err}");
+    };
+    let mut phase = 0.0f64;
+
+    let stream = device.build_output_stream(
+        cfg,
+        move |data: &mut [T], _| {
+            for frame in data.chunks_exact_mut(ch) {
+                #[allow(clippy::cast_possible_truncation)]
+                let s = tone.amp * phase.sin() as f32;
+                frame.fill(T::from_sample(s));
+                phase = (phase + tone.step) % TAU;
+            }
+        },
+        err_fn,
+        None,
+    )?;
+
+    Ok(stream)
+}
@@ -1,9 +1,11 @@
 mod device;
+mod generator;
 mod mic;
 mod stream;
 mod system;
 
 pub use device::{best_config_for, pick_input_device};
+pub use generator::ToneGenerator;
 pub use stream::build_stream;
 pub use system::SystemHandle;
 
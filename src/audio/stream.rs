@@ -28,8 +28,14 @@ where
                     for &s in frame {
                         acc += s.to_sample::<f32>();
                     }
+                    let left = frame
+                        .first()
+                        .map_or(0.0, |s| s.to_sample::<f32>());
+                    let right = frame
+                        .get(1)
+                        .map_or(left, |s| s.to_sample::<f32>());
                     #[allow(clippy::cast_precision_loss)]
-                    buf.push(acc / ch as f32);
+                    buf.push_stereo(acc / ch as f32, left, right);
                 }
             }
         },
@@ -144,16 +144,23 @@ fn push_frames(
     for f in 0..frames {
         let base = f * frame_bytes;
         let mut acc = 0.0f32;
+        let mut lr = [0.0f32; 2];
         for c in 0..channels {
             let off = base + c * 4;
             if let Some(bytes) = carry.get(off..off.saturating_add(4))
             {
                 let mut chunk = [0u8; 4];
                 chunk.copy_from_slice(bytes);
-                acc += f32::from_le_bytes(chunk);
+                let x = f32::from_le_bytes(chunk);
+                acc += x;
+                if let Some(slot) = lr.get_mut(c) {
+                    *slot = x;
+                }
             }
         }
-        ring.push(acc / channels as f32);
+        let [left, right] = lr;
+        let right = if channels > 1 { right } else { left };
+        ring.push_stereo(acc / channels as f32, left, right);
     }
 }
 
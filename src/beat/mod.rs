@@ -0,0 +1,74 @@
+mod onset;
+mod tempo;
+
+pub use onset::{OnsetDetector, spectral_flux};
+pub use tempo::TempoTracker;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BeatEvent {
+    pub time_s: f32,
+    pub bpm: f32,
+}
+
+/// Runs onset detection and tempo tracking over successive spectra
+/// and reports a [`BeatEvent`] whenever the tracked beat phase wraps.
+pub struct BeatTracker {
+    onset: OnsetDetector,
+    tempo: TempoTracker,
+    time_s: f32,
+}
+
+impl Default for BeatTracker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl BeatTracker {
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            onset: OnsetDetector::new(),
+            tempo: TempoTracker::new(),
+            time_s: 0.0,
+        }
+    }
+
+    pub fn reset(&mut self) {
+        *self = Self::new();
+    }
+
+    pub fn process(
+        &mut self,
+        spectrum: &[f32],
+        dt_s: f32,
+    ) -> Option<BeatEvent> {
+        self.time_s += dt_s;
+
+        let onset = self.onset.process(spectrum, dt_s);
+        self.tempo.push(self.onset.novelty(), dt_s);
+
+        let bpm = self.tempo.bpm()?;
+        self.tempo.advance_phase(dt_s, onset.is_some()).then_some(
+            BeatEvent {
+                time_s: self.time_s,
+                bpm,
+            },
+        )
+    }
+
+    #[must_use]
+    pub const fn bpm(&self) -> Option<f32> {
+        self.tempo.bpm()
+    }
+
+    #[must_use]
+    pub const fn phase(&self) -> f32 {
+        self.tempo.phase()
+    }
+
+    #[must_use]
+    pub const fn onset(&self) -> &OnsetDetector {
+        &self.onset
+    }
+}
@@ -0,0 +1,117 @@
+const HISTORY_LEN: usize = 64;
+const THRESHOLD_K: f32 = 1.5;
+const THRESHOLD_FLOOR: f32 = 0.01;
+const REFRACTORY_S: f32 = 0.10;
+const LOG_GAMMA: f32 = 100.0;
+
+/// Mean positive change in log-compressed magnitude between `prev`
+/// and `spectrum` (both power spectra). `prev` is updated in place.
+#[must_use]
+#[allow(clippy::cast_precision_loss)]
+pub fn spectral_flux(prev: &mut Vec<f32>, spectrum: &[f32]) -> f32 {
+    if prev.len() != spectrum.len() {
+        prev.clear();
+        prev.extend(spectrum.iter().map(|&p| log_mag(p)));
+        return 0.0;
+    }
+
+    let mut acc = 0.0f32;
+    for (old, &p) in prev.iter_mut().zip(spectrum) {
+        let cur = log_mag(p);
+        acc += (cur - *old).max(0.0);
+        *old = cur;
+    }
+
+    acc / spectrum.len().max(1) as f32
+}
+
+fn log_mag(pow: f32) -> f32 {
+    (LOG_GAMMA * pow.max(0.0).sqrt()).ln_1p()
+}
+
+pub struct OnsetDetector {
+    prev: Vec<f32>,
+    history: Vec<f32>,
+    pos: usize,
+    flux: f32,
+    novelty: f32,
+    since_onset_s: f32,
+}
+
+impl Default for OnsetDetector {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl OnsetDetector {
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            prev: Vec::new(),
+            history: vec![0.0; HISTORY_LEN],
+            pos: 0,
+            flux: 0.0,
+            novelty: 0.0,
+            since_onset_s: REFRACTORY_S,
+        }
+    }
+
+    /// Returns the onset strength when `spectrum` starts a new onset.
+    pub fn process(
+        &mut self,
+        spectrum: &[f32],
+        dt_s: f32,
+    ) -> Option<f32> {
+        self.since_onset_s += dt_s;
+        self.flux = spectral_flux(&mut self.prev, spectrum);
+
+        let (mean, threshold) = self.threshold();
+        self.novelty = (self.flux - mean).max(0.0);
+        self.record(self.flux);
+
+        if self.flux > threshold && self.since_onset_s >= REFRACTORY_S
+        {
+            self.since_onset_s = 0.0;
+            return Some(self.flux);
+        }
+
+        None
+    }
+
+    #[must_use]
+    pub const fn flux(&self) -> f32 {
+        self.flux
+    }
+
+    /// Flux above its recent mean, the signal fed to tempo tracking.
+    #[must_use]
+    pub const fn novelty(&self) -> f32 {
+        self.novelty
+    }
+
+    #[allow(clippy::cast_precision_loss)]
+    fn threshold(&self) -> (f32, f32) {
+        let n = self.history.len().max(1) as f32;
+        let mean = self.history.iter().sum::<f32>() / n;
+        let var = self
+            .history
+            .iter()
+            .map(|&x| (x - mean) * (x - mean))
+            .sum::<f32>()
+            / n;
+
+        (
+            mean,
+            THRESHOLD_K.mul_add(var.sqrt(), mean) + THRESHOLD_FLOOR,
+        )
+    }
+
+    #[allow(clippy::arithmetic_side_effects)]
+    fn record(&mut self, flux: f32) {
+        if let Some(slot) = self.history.get_mut(self.pos) {
+            *slot = flux;
+        }
+        self.pos = (self.pos + 1) % HISTORY_LEN;
+    }
+}
@@ -0,0 +1,188 @@
+const ENVELOPE_RATE: f32 = 100.0;
+const ENVELOPE_LEN: usize = 600;
+const MIN_FILLED: usize = 300;
+const ESTIMATE_EVERY: usize = 25;
+const MIN_BPM: f32 = 60.0;
+const MAX_BPM: f32 = 200.0;
+const PRIOR_BPM: f32 = 120.0;
+const PRIOR_OCTAVES: f32 = 1.0;
+const PHASE_GAIN: f32 = 0.2;
+
+/// Estimates tempo by autocorrelating an onset-strength envelope
+/// resampled to a fixed rate, then follows the beat phase with a
+/// simple phase-locked loop nudged by detected onsets.
+pub struct TempoTracker {
+    envelope: Vec<f32>,
+    pos: usize,
+    filled: usize,
+    pending: usize,
+    carry_s: f32,
+    held: f32,
+    bpm: Option<f32>,
+    phase: f32,
+    since_beat_s: f32,
+}
+
+impl Default for TempoTracker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TempoTracker {
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            envelope: vec![0.0; ENVELOPE_LEN],
+            pos: 0,
+            filled: 0,
+            pending: 0,
+            carry_s: 0.0,
+            held: 0.0,
+            bpm: None,
+            phase: 0.0,
+            since_beat_s: 0.0,
+        }
+    }
+
+    #[must_use]
+    pub const fn bpm(&self) -> Option<f32> {
+        self.bpm
+    }
+
+    /// Position within the current beat, `0.0` on the beat.
+    #[must_use]
+    pub const fn phase(&self) -> f32 {
+        self.phase
+    }
+
+    #[allow(
+        clippy::cast_precision_loss,
+        clippy::cast_possible_truncation,
+        clippy::cast_sign_loss
+    )]
+    pub fn push(&mut self, strength: f32, dt_s: f32) {
+        self.held = self.held.max(strength);
+        self.carry_s += dt_s.max(0.0);
+
+        let slots = (self.carry_s * ENVELOPE_RATE).floor() as usize;
+        self.carry_s -= slots as f32 / ENVELOPE_RATE;
+
+        for _ in 0..slots.min(ENVELOPE_LEN) {
+            self.write(self.held);
+            self.held = 0.0;
+        }
+
+        if self.pending >= ESTIMATE_EVERY && self.filled >= MIN_FILLED
+        {
+            self.pending = 0;
+            self.bpm = self.estimate();
+        }
+    }
+
+    /// Advances the beat phase and returns `true` when it wraps.
+    pub fn advance_phase(&mut self, dt_s: f32, onset: bool) -> bool {
+        let Some(bpm) = self.bpm else {
+            return false;
+        };
+        let period_s = 60.0 / bpm;
+
+        self.since_beat_s += dt_s;
+        self.phase += dt_s / period_s;
+
+        if onset {
+            let err = self.phase - self.phase.round();
+            self.phase = err.mul_add(-PHASE_GAIN, self.phase);
+        }
+
+        if self.phase < 1.0 {
+            return false;
+        }
+
+        self.phase = self.phase.fract();
+        if self.since_beat_s < 0.5 * period_s {
+            return false;
+        }
+
+        self.since_beat_s = 0.0;
+        true
+    }
+
+    #[allow(clippy::arithmetic_side_effects)]
+    fn write(&mut self, value: f32) {
+        if let Some(slot) = self.envelope.get_mut(self.pos) {
+            *slot = value;
+        }
+        self.pos = (self.pos + 1) % ENVELOPE_LEN;
+        self.filled = (self.filled + 1).min(ENVELOPE_LEN);
+        self.pending = self.pending.saturating_add(1);
+    }
+
+    #[allow(clippy::arithmetic_side_effects)]
+    fn at(&self, i: usize) -> f32 {
+        let start = self.pos + ENVELOPE_LEN - self.filled;
+        self.envelope
+            .get((start + i) % ENVELOPE_LEN)
+            .copied()
+            .unwrap_or(0.0)
+    }
+
+    #[allow(
+        clippy::cast_precision_loss,
+        clippy::arithmetic_side_effects
+    )]
+    fn autocorrelation(&self, lag: usize) -> f32 {
+        if lag >= self.filled {
+            return 0.0;
+        }
+
+        let sum = (lag..self.filled)
+            .map(|i| self.at(i) * self.at(i - lag))
+            .sum::<f32>();
+        sum / (self.filled - lag) as f32
+    }
+
+    #[allow(
+        clippy::cast_precision_loss,
+        clippy::cast_possible_truncation,
+        clippy::cast_sign_loss,
+        clippy::arithmetic_side_effects
+    )]
+    fn estimate(&self) -> Option<f32> {
+        let lag_min =
+            (ENVELOPE_RATE * 60.0 / MAX_BPM).floor() as usize;
+        let lag_max =
+            (ENVELOPE_RATE * 60.0 / MIN_BPM).ceil() as usize;
+
+        let weighted = |lag: usize| {
+            let bpm = ENVELOPE_RATE * 60.0 / lag as f32;
+            self.autocorrelation(lag) * tempo_prior(bpm)
+        };
+
+        let (best, score) = (lag_min..=lag_max)
+            .map(|lag| (lag, weighted(lag)))
+            .max_by(|a, b| a.1.total_cmp(&b.1))?;
+
+        if score <= 0.0 {
+            return None;
+        }
+
+        let prev = weighted(best - 1);
+        let next = weighted(best + 1);
+        let den = 2.0f32.mul_add(-score, prev + next);
+        let offset = if den.abs() > f32::EPSILON {
+            (0.5 * (prev - next) / den).clamp(-0.5, 0.5)
+        } else {
+            0.0
+        };
+
+        Some(ENVELOPE_RATE * 60.0 / (best as f32 + offset))
+    }
+}
+
+/// Log-normal preference for tempi near 120 BPM, which resolves
+/// half/double-tempo ambiguity the way listeners usually do.
+fn tempo_prior(bpm: f32) -> f32 {
+    let octaves = (bpm / PRIOR_BPM).log2() / PRIOR_OCTAVES;
+    (-0.5 * octaves * octaves).exp()
+}
@@ -1,7 +1,17 @@
+/// Ring buffer of captured audio. `data` holds the mono mix, and
+/// `left` and `right` the first two channels at the same positions.
+///
+/// The channel rings are empty, and cost nothing to push, until
+/// [`SharedBuf::set_stereo`] asks for them.
 pub struct SharedBuf {
     data: Vec<f32>,
+    left: Vec<f32>,
+    right: Vec<f32>,
     write_idx: usize,
     filled: bool,
+    written: u64,
+    /// `written` when the channel rings were allocated.
+    stereo_from: u64,
 }
 
 impl SharedBuf {
@@ -10,23 +20,69 @@ impl SharedBuf {
         let cap = cap.checked_next_power_of_two().unwrap_or(0);
         Self {
             data: vec![0.0; cap],
+            left: Vec::new(),
+            right: Vec::new(),
             write_idx: 0,
             filled: false,
+            written: 0,
+            stereo_from: 0,
         }
     }
 
+    /// Starts keeping the left and right channels, or stops and frees
+    /// them. The channels can be read once enough frames have been
+    /// pushed since.
+    pub fn set_stereo(&mut self, on: bool) {
+        if on == self.is_stereo() {
+            return;
+        }
+        if on {
+            self.left = vec![0.0; self.data.len()];
+            self.right = vec![0.0; self.data.len()];
+            self.stereo_from = self.written;
+        } else {
+            self.left = Vec::new();
+            self.right = Vec::new();
+        }
+    }
+
+    #[must_use]
+    pub fn is_stereo(&self) -> bool {
+        !self.left.is_empty()
+    }
+
+    /// Pushes a mono sample, heard equally on both channels.
     #[inline]
-    #[allow(clippy::arithmetic_side_effects)]
     pub fn push(&mut self, x: f32) {
+        self.push_stereo(x, x, x);
+    }
+
+    /// Pushes one frame: the mix of all channels, and the left and
+    /// right channels on their own while they are kept.
+    #[inline]
+    #[allow(clippy::arithmetic_side_effects)]
+    pub fn push_stereo(&mut self, mix: f32, left: f32, right: f32) {
         let cap = self.data.len();
         if cap == 0 {
             return;
         }
 
-        if let Some(slot) = self.data.get_mut(self.write_idx) {
-            *slot = x;
+        let i = self.write_idx;
+        if let Some(slot) = self.data.get_mut(i) {
+            *slot = mix;
+        }
+        if self.is_stereo() {
+            for (ring, x) in
+                [(&mut self.left, left), (&mut self.right, right)]
+            {
+                if let Some(slot) = ring.get_mut(i) {
+                    *slot = x;
+                }
+            }
         }
 
+        self.written = self.written.wrapping_add(1);
+
         // `new` rounds every nonzero request to a power-of-two capacity.
         let mask = cap - 1;
         self.write_idx = (self.write_idx + 1) & mask;
@@ -45,24 +101,58 @@ impl SharedBuf {
         }
     }
 
+    /// Total number of samples pushed since creation, wrapping.
+    #[inline]
+    #[must_use]
+    pub const fn written(&self) -> u64 {
+        self.written
+    }
+
     #[inline]
     #[must_use]
     pub const fn is_empty(&self) -> bool {
         !self.filled && self.write_idx == 0
     }
 
-    #[allow(clippy::arithmetic_side_effects)]
     pub fn copy_last_n_into(
         &self,
         n: usize,
         out: &mut Vec<f32>,
+    ) -> bool {
+        self.copy_ring(&self.data, n, out)
+    }
+
+    /// Like [`SharedBuf::copy_last_n_into`] for the left and right
+    /// channels. Fails unless they have been kept for `n` frames.
+    pub fn copy_last_n_stereo_into(
+        &self,
+        n: usize,
+        left: &mut Vec<f32>,
+        right: &mut Vec<f32>,
+    ) -> bool {
+        let kept = self.written.wrapping_sub(self.stereo_from);
+        if !self.is_stereo()
+            || kept < u64::try_from(n).unwrap_or(u64::MAX)
+        {
+            return false;
+        }
+        self.copy_ring(&self.left, n, left)
+            && self.copy_ring(&self.right, n, right)
+    }
+
+    #[allow(clippy::arithmetic_side_effects)]
+    fn copy_ring(
+        &self,
+        ring: &[f32],
+        n: usize,
+        out: &mut Vec<f32>,
     ) -> bool {
         if n == 0 {
             out.clear();
             return true;
         }
 
-        let cap = self.data.len();
+        let cap = ring.len();
         let len = self.len();
         if len < n {
             return false;
@@ -73,18 +163,16 @@ impl SharedBuf {
         if self.filled {
             let start = (self.write_idx + cap - n) % cap;
             if start + n <= cap {
-                let Some(src) = self.data.get(start..start + n)
-                else {
+                let Some(src) = ring.get(start..start + n) else {
                     return false;
                 };
                 out.copy_from_slice(src);
             } else {
                 let first = cap - start;
                 let (head, tail) = out.split_at_mut(first);
-                let (Some(first_src), Some(second_src)) = (
-                    self.data.get(start..cap),
-                    self.data.get(..(n - first)),
-                ) else {
+                let (Some(first_src), Some(second_src)) =
+                    (ring.get(start..cap), ring.get(..(n - first)))
+                else {
                     return false;
                 };
                 head.copy_from_slice(first_src);
@@ -92,8 +180,7 @@ impl SharedBuf {
             }
         } else {
             let start = self.write_idx - n;
-            let Some(src) = self.data.get(start..self.write_idx)
-            else {
+            let Some(src) = ring.get(start..self.write_idx) else {
                 return false;
             };
             out.copy_from_slice(src);
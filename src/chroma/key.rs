@@ -0,0 +1,92 @@
+use std::fmt;
+
+use super::PITCH_CLASSES;
+
+/// Krumhansl-Kessler probe-tone profiles, tonic first.
+const MAJOR_PROFILE: [f32; 12] = [
+    6.35, 2.23, 3.48, 2.33, 4.38, 4.09, 2.52, 5.19, 2.39, 3.66, 2.29,
+    2.88,
+];
+const MINOR_PROFILE: [f32; 12] = [
+    6.33, 2.68, 3.52, 5.38, 2.60, 3.53, 2.54, 4.75, 3.98, 2.69, 3.34,
+    3.17,
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Mode {
+    Major,
+    Minor,
+}
+
+/// Musical key: `tonic` is a pitch class with C = 0.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Key {
+    pub tonic: usize,
+    pub mode: Mode,
+}
+
+impl fmt::Display for Key {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = PITCH_CLASSES.get(self.tonic).unwrap_or(&"?");
+        match self.mode {
+            Mode::Major => write!(f, "{name} major"),
+            Mode::Minor => write!(f, "{name} minor"),
+        }
+    }
+}
+
+/// Best-matching key and its profile correlation in `-1..=1`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct KeyEstimate {
+    pub key: Key,
+    pub correlation: f32,
+}
+
+/// Correlates `chroma` with all 24 rotated key profiles. Returns
+/// `None` for a flat or empty chroma vector.
+#[must_use]
+pub fn estimate_key(chroma: &[f32; 12]) -> Option<KeyEstimate> {
+    let mut best: Option<KeyEstimate> = None;
+
+    for (mode, profile) in
+        [(Mode::Major, &MAJOR_PROFILE), (Mode::Minor, &MINOR_PROFILE)]
+    {
+        for tonic in 0..12 {
+            let r = correlate(chroma, profile, tonic)?;
+            if best.map_or(true, |b| r > b.correlation) {
+                best = Some(KeyEstimate {
+                    key: Key { tonic, mode },
+                    correlation: r,
+                });
+            }
+        }
+    }
+
+    best
+}
+
+/// Pearson correlation of `chroma` against `profile` rotated so its
+/// tonic lands on `tonic`.
+#[allow(clippy::arithmetic_side_effects)]
+fn correlate(
+    chroma: &[f32; 12],
+    profile: &[f32; 12],
+    tonic: usize,
+) -> Option<f32> {
+    let mean_x = chroma.iter().sum::<f32>() / 12.0;
+    let mean_y = profile.iter().sum::<f32>() / 12.0;
+
+    let mut sxy = 0.0f32;
+    let mut sxx = 0.0f32;
+    let mut syy = 0.0f32;
+    for (i, &x) in chroma.iter().enumerate() {
+        let y = profile.get((i + 12 - tonic) % 12).copied()? - mean_y;
+        let x = x - mean_x;
+        sxy = x.mul_add(y, sxy);
+        sxx = x.mul_add(x, sxx);
+        syy = y.mul_add(y, syy);
+    }
+
+    let den = (sxx * syy).sqrt();
+    (den > 1e-12).then(|| sxy / den)
+}
@@ -0,0 +1,166 @@
+mod key;
+mod pitch;
+
+pub use key::{Key, KeyEstimate, Mode, estimate_key};
+pub use pitch::{
+    A4_HZ, CHROMA_FMAX_HZ, CHROMA_FMIN_HZ, TonalPeak, fold_chroma,
+    midi_note, tonal_peaks, tuning_offset, tuning_vector,
+};
+
+use crate::dsp::ema_tc;
+
+pub const PITCH_CLASSES: [&str; 12] = [
+    "C", "C#", "D", "D#", "E", "F", "F#", "G", "G#", "A", "A#", "B",
+];
+
+/// Pitch class of a note name such as `"F#"` or `"Bb"`, C = 0.
+#[must_use]
+#[allow(clippy::arithmetic_side_effects)]
+pub fn parse_pitch_class(name: &str) -> Option<usize> {
+    let name = name.trim();
+    let mut chars = name.chars();
+    let letter = chars.next()?.to_ascii_uppercase();
+    let natural = match letter {
+        'C' => 0,
+        'D' => 2,
+        'E' => 4,
+        'F' => 5,
+        'G' => 7,
+        'A' => 9,
+        'B' => 11,
+        _ => return None,
+    };
+
+    let shift = match chars.as_str() {
+        "" => 0,
+        "#" => 1,
+        "b" => 11,
+        _ => return None,
+    };
+    Some((natural + shift) % 12)
+}
+
+/// MIDI number of a note name with octave such as `"A0"`, `"C#4"` or
+/// `"Bb-1"`, with C4 = 60.
+#[must_use]
+#[allow(clippy::arithmetic_side_effects)]
+pub fn parse_note(name: &str) -> Option<i32> {
+    let name = name.trim();
+    let split = name
+        .char_indices()
+        .skip(1)
+        .find(|&(_, c)| c == '-' || c.is_ascii_digit())
+        .map(|(i, _)| i)?;
+    let (pitch, octave) = (name.get(..split)?, name.get(split..)?);
+
+    let pc = i32::try_from(parse_pitch_class(pitch)?).ok()?;
+    let octave = octave.parse::<i32>().ok()?;
+    // Cb and B# belong to the neighbouring octave
+    let wrap = match pitch.get(1..) {
+        Some("b") if pc == 11 => -1,
+        Some("#") if pc == 0 => 1,
+        _ => 0,
+    };
+    Some((octave + 1 + wrap) * 12 + pc)
+}
+
+/// Smoothing of the displayed chroma.
+pub const CHROMA_TAU_S: f32 = 0.15;
+/// Memory of the chroma the key is estimated from.
+pub const KEY_TAU_S: f32 = 8.0;
+/// Memory of the tuning estimate.
+pub const TUNING_TAU_S: f32 = 4.0;
+/// Decay of the reference level the display is normalised to.
+const LEVEL_TAU_S: f32 = 2.0;
+
+/// Tracks pitch-class energy, tuning and key over time.
+///
+/// Feed it one power spectrum per frame. `chroma` is smoothed for
+/// display and normalised to `0..=1`; key and tuning use much longer
+/// memories so they stay put through a passage.
+pub struct Chromagram {
+    peaks: Vec<TonalPeak>,
+    smooth: [f32; 12],
+    chroma: [f32; 12],
+    key_chroma: [f32; 12],
+    level: f32,
+    tuning: (f32, f32),
+}
+
+impl Default for Chromagram {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Chromagram {
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            peaks: Vec::new(),
+            smooth: [0.0; 12],
+            chroma: [0.0; 12],
+            key_chroma: [0.0; 12],
+            level: 0.0,
+            tuning: (0.0, 0.0),
+        }
+    }
+
+    pub fn process(
+        &mut self,
+        spec_pow: &[f32],
+        bin_hz: f32,
+        dt: f32,
+    ) {
+        tonal_peaks(spec_pow, bin_hz, &mut self.peaks);
+
+        let (s, c) = tuning_vector(&self.peaks);
+        self.tuning = (
+            ema_tc(self.tuning.0, s, TUNING_TAU_S, dt),
+            ema_tc(self.tuning.1, c, TUNING_TAU_S, dt),
+        );
+
+        let frame = fold_chroma(&self.peaks, self.tuning_cents());
+        for ((smooth, long), &v) in self
+            .smooth
+            .iter_mut()
+            .zip(self.key_chroma.iter_mut())
+            .zip(&frame)
+        {
+            *smooth = ema_tc(*smooth, v, CHROMA_TAU_S, dt);
+            *long = ema_tc(*long, v, KEY_TAU_S, dt);
+        }
+
+        let max = self.smooth.iter().copied().fold(0.0, f32::max);
+        self.level =
+            max.max(ema_tc(self.level, 0.0, LEVEL_TAU_S, dt));
+        for (d, &v) in self.chroma.iter_mut().zip(&self.smooth) {
+            *d = if self.level > 1e-12 {
+                v / self.level
+            } else {
+                0.0
+            };
+        }
+    }
+
+    /// Smoothed chroma, C first, scaled to `0..=1`.
+    #[must_use]
+    pub const fn chroma(&self) -> &[f32; 12] {
+        &self.chroma
+    }
+
+    /// Estimated tuning offset from A4 = 440 Hz, in cents.
+    #[must_use]
+    pub fn tuning_cents(&self) -> f32 {
+        pitch::vector_cents(self.tuning).unwrap_or(0.0)
+    }
+
+    #[must_use]
+    pub fn key(&self) -> Option<KeyEstimate> {
+        estimate_key(&self.key_chroma)
+    }
+
+    pub fn reset(&mut self) {
+        *self = Self::new();
+    }
+}
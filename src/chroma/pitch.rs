@@ -0,0 +1,124 @@
+use std::f32::consts::TAU;
+
+/// Reference pitch of A4.
+pub const A4_HZ: f32 = 440.0;
+
+/// Lowest and highest frequencies folded into the chromagram.
+pub const CHROMA_FMIN_HZ: f32 = 60.0;
+pub const CHROMA_FMAX_HZ: f32 = 5_000.0;
+
+/// Peaks more than this far below the loudest one are ignored.
+const PEAK_RANGE: f32 = 1e-5;
+
+/// Spectral peak refined by parabolic interpolation of log power.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TonalPeak {
+    pub hz: f32,
+    pub power: f32,
+}
+
+/// Fractional MIDI note number of `hz`, with A4 = 69.
+#[must_use]
+pub fn midi_note(hz: f32) -> f32 {
+    12.0f32.mul_add((hz.max(1e-3) / A4_HZ).log2(), 69.0)
+}
+
+/// Local maxima of `spec_pow` between [`CHROMA_FMIN_HZ`] and
+/// [`CHROMA_FMAX_HZ`], written into `out`.
+#[allow(
+    clippy::cast_precision_loss,
+    clippy::cast_possible_truncation,
+    clippy::cast_sign_loss
+)]
+pub fn tonal_peaks(
+    spec_pow: &[f32],
+    bin_hz: f32,
+    out: &mut Vec<TonalPeak>,
+) {
+    out.clear();
+    if bin_hz <= 0.0 {
+        return;
+    }
+
+    let lo = ((CHROMA_FMIN_HZ / bin_hz).floor() as usize).max(1);
+    let hi = ((CHROMA_FMAX_HZ / bin_hz).ceil() as usize)
+        .min(spec_pow.len().saturating_sub(1));
+    let max = spec_pow
+        .get(lo..hi)
+        .map_or(0.0, |s| s.iter().copied().fold(0.0, f32::max));
+    if max <= 1e-20 {
+        return;
+    }
+    let floor = max * PEAK_RANGE;
+
+    for i in lo..hi {
+        let (Some(&a), Some(&b), Some(&c)) = (
+            spec_pow.get(i.wrapping_sub(1)),
+            spec_pow.get(i),
+            spec_pow.get(i.saturating_add(1)),
+        ) else {
+            continue;
+        };
+        if b <= floor || b <= a || b < c {
+            continue;
+        }
+
+        let (la, lb, lc) =
+            ((a + 1e-20).ln(), (b + 1e-20).ln(), (c + 1e-20).ln());
+        let den = la - 2.0f32.mul_add(lb, -lc);
+        let delta = if den.abs() > 1e-12 {
+            (0.5 * (la - lc) / den).clamp(-0.5, 0.5)
+        } else {
+            0.0
+        };
+
+        out.push(TonalPeak {
+            hz: (i as f32 + delta) * bin_hz,
+            power: b,
+        });
+    }
+}
+
+/// Deviation of the peaks from equal temperament in cents, in
+/// `-50..50`, or `None` without peaks.
+///
+/// Each peak votes with its magnitude on a circular mean, so
+/// deviations either side of a semitone boundary average sensibly.
+#[must_use]
+pub fn tuning_offset(peaks: &[TonalPeak]) -> Option<f32> {
+    vector_cents(tuning_vector(peaks))
+}
+
+pub(super) fn vector_cents((sin, cos): (f32, f32)) -> Option<f32> {
+    (sin.hypot(cos) > 1e-12).then(|| sin.atan2(cos) / TAU * 100.0)
+}
+
+/// Unnormalised circular-mean vector behind [`tuning_offset`].
+#[must_use]
+pub fn tuning_vector(peaks: &[TonalPeak]) -> (f32, f32) {
+    peaks.iter().fold((0.0, 0.0), |(s, c), p| {
+        let dev = midi_note(p.hz) - midi_note(p.hz).round();
+        let (ds, dc) = (dev * TAU).sin_cos();
+        let mag = p.power.sqrt();
+        (ds.mul_add(mag, s), dc.mul_add(mag, c))
+    })
+}
+
+/// Folds peak magnitudes into 12 pitch classes, C first, after
+/// shifting them by `tuning_cents`.
+#[must_use]
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+pub fn fold_chroma(
+    peaks: &[TonalPeak],
+    tuning_cents: f32,
+) -> [f32; 12] {
+    let mut chroma = [0.0f32; 12];
+    for p in peaks {
+        let note = (midi_note(p.hz) - tuning_cents / 100.0).round();
+        let pc = note.rem_euclid(12.0) as usize;
+        if let Some(c) = chroma.get_mut(pc) {
+            *c += p.power.sqrt();
+        }
+    }
+    chroma
+}
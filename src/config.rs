@@ -1,3 +1,12 @@
+use crate::{
+    analyzer::{DynamicsParams, PhysicsModel, ScalingMode},
+    chroma::{parse_note, parse_pitch_class},
+    dsp::PreprocessParams,
+    filterbank::{FilterbankMode, PIANO_HIGH_MIDI, PIANO_LOW_MIDI},
+    partials::Interpolation,
+    spl::{ExposureStandard, FrequencyWeighting, TimeWeighting},
+    tuner::Temperament,
+};
 use anyhow::{Context, Result};
 use serde::Deserialize;
 use std::{fs, path::Path};
@@ -15,20 +24,238 @@ impl RgbColor {
         g: 255,
         b: 255,
     };
+
+    /// Default color of system audio when it is drawn apart from the
+    /// microphone.
+    pub const ORANGE: Self = Self {
+        r: 255,
+        g: 159,
+        b: 67,
+    };
+
+    /// Default color of the long-term average spectrum line.
+    pub const CYAN: Self = Self {
+        r: 79,
+        g: 195,
+        b: 247,
+    };
+
+    /// Default color of the snapshot line.
+    pub const MAGENTA: Self = Self {
+        r: 255,
+        g: 106,
+        b: 213,
+    };
+
+    #[must_use]
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    pub fn lerp(self, to: Self, t: f32) -> Self {
+        let t = t.clamp(0.0, 1.0);
+        let mix = |a: u8, b: u8| {
+            (f32::from(b) - f32::from(a))
+                .mul_add(t, f32::from(a))
+                .round() as u8
+        };
+
+        Self {
+            r: mix(self.r, to.r),
+            g: mix(self.g, to.g),
+            b: mix(self.b, to.b),
+        }
+    }
+}
+
+/// Samples per FFT window.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FftSize {
+    /// Picked from `fmin`, the sample rate and `frame_ms`.
+    Auto,
+    Fixed(usize),
+}
+
+impl FftSize {
+    pub const MIN: usize = 512;
+    pub const MAX: usize = 16_384;
+    /// Bins an automatic size puts in the octave above `fmin`.
+    pub const AUTO_BINS_PER_OCTAVE: f32 = 4.0;
+    /// Longest window an automatic size allows, in frames.
+    pub const AUTO_MAX_FRAMES: f32 = 24.0;
+
+    /// The size to use at `sample_rate`.
+    ///
+    /// `Auto` takes the smallest power of two that gives the lowest
+    /// octave [`Self::AUTO_BINS_PER_OCTAVE`] bins, but no longer than
+    /// [`Self::AUTO_MAX_FRAMES`] frames, so bass is resolved without
+    /// the window lagging far behind the display.
+    #[must_use]
+    #[allow(
+        clippy::cast_precision_loss,
+        clippy::cast_possible_truncation,
+        clippy::cast_sign_loss
+    )]
+    pub fn resolve(
+        self,
+        fmin: f32,
+        sample_rate: f32,
+        frame_ms: u64,
+    ) -> usize {
+        let size = match self {
+            Self::Fixed(size) => size,
+            Self::Auto => {
+                let needed = sample_rate * Self::AUTO_BINS_PER_OCTAVE
+                    / fmin.max(1.0);
+                let budget = sample_rate * frame_ms as f32 / 1000.0
+                    * Self::AUTO_MAX_FRAMES;
+                needed.log2().ceil().min(budget.log2().floor()).exp2()
+                    as usize
+            }
+        };
+        size.clamp(Self::MIN, Self::MAX)
+    }
+}
+
+/// `fft_size` as written: a number of samples or `"auto"`.
+#[derive(Debug, Deserialize, Clone)]
+#[serde(untagged)]
+enum FileFftSize {
+    Samples(usize),
+    Named(String),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AnalysisMode {
+    Fft,
+    MultiRes,
+}
+
+/// What the main area of the screen shows.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum View {
+    Spectrum,
+    Chroma,
+    Tuner,
+    Stereo,
+    Distortion,
+}
+
+impl View {
+    /// The view after this one when cycling with `v`.
+    #[must_use]
+    pub const fn next(self) -> Self {
+        match self {
+            Self::Spectrum => Self::Chroma,
+            Self::Chroma => Self::Tuner,
+            Self::Tuner => Self::Stereo,
+            Self::Stereo => Self::Distortion,
+            Self::Distortion => Self::Spectrum,
+        }
+    }
+}
+
+/// How the spectrum shows the two sources when both are captured.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BothDisplay {
+    /// One spectrum of the averaged sources.
+    Mix,
+    /// Both spectra over the same bars, in two colors.
+    Overlay,
+    /// Microphone in the top half, system audio in the bottom half.
+    Split,
+}
+
+impl BothDisplay {
+    /// The display after this one when cycling with `s`.
+    #[must_use]
+    pub const fn next(self) -> Self {
+        match self {
+            Self::Mix => Self::Overlay,
+            Self::Overlay => Self::Split,
+            Self::Split => Self::Mix,
+        }
+    }
+}
+
+/// What opens the noise gate.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum GateMode {
+    /// Broadband power above the gate level.
+    Power,
+    /// Power above the gate level that also sounds like speech.
+    Voice,
+    /// A gate per spectrum band above that band's noise floor.
+    Bands,
 }
 
 #[derive(Debug, Clone, PartialEq)]
+#[allow(clippy::struct_excessive_bools)]
 pub struct Config {
     pub fmin: f32,
     pub fmax: f32,
     pub frame_ms: u64,
-    pub fft_size: usize,
+    pub fft_size: FftSize,
+    pub analysis: AnalysisMode,
+    pub filterbank: FilterbankMode,
+    pub piano_low: i32,
+    pub piano_high: i32,
+    pub piano_labels: bool,
+    pub view: View,
     pub tau_spec: f32,
+    pub attack_tau: f32,
+    pub tau_tilt: f32,
+    pub preprocess: PreprocessParams,
+    pub scaling: ScalingMode,
+    pub db_floor: f32,
+    pub db_ceiling: f32,
     pub gate_db: f32,
+    pub gate_mode: GateMode,
+    pub noise_subtraction: f32,
+    pub calibrate_on_start: bool,
+    pub calibrate_s: f32,
+    pub physics: PhysicsModel,
     pub flow_k: f32,
     pub spr_k: f32,
     pub spr_zeta: f32,
+    pub gravity: f32,
+    pub ema_tau: f32,
+    pub monstercat: f32,
+    pub dynamics: DynamicsParams,
     pub color: RgbColor,
+    pub both_display: BothDisplay,
+    pub system_color: RgbColor,
+    pub peak_caps: bool,
+    pub peak_hold: f32,
+    pub peak_gravity: f32,
+    pub peak_color: Option<RgbColor>,
+    pub beat_pulse: bool,
+    pub loudness_meter: bool,
+    pub peak_readout: bool,
+    pub peak_count: usize,
+    pub peak_interpolation: Interpolation,
+    pub peak_reassign: bool,
+    pub feedback_detect: bool,
+    pub feedback_persist_s: f32,
+    pub feedback_hook: Option<String>,
+    pub ltas: bool,
+    pub ltas_window_s: f32,
+    pub ltas_color: RgbColor,
+    pub snapshot_color: RgbColor,
+    pub snapshot_path: Option<String>,
+    pub thd_generator: bool,
+    pub thd_tone_hz: f32,
+    pub thd_tone_db: f32,
+    pub spl_meter: bool,
+    pub spl_weighting: FrequencyWeighting,
+    pub spl_time: TimeWeighting,
+    pub spl_offset_db: Option<f32>,
+    pub spl_reference_db: f32,
+    pub spl_standard: ExposureStandard,
+    pub a4_hz: f32,
+    pub temperament: Temperament,
+    pub temperament_root: usize,
 }
 
 impl Config {
@@ -38,13 +265,66 @@ impl Config {
             fmin: 30.0,
             fmax: 16_000.0,
             frame_ms: 16,
-            fft_size: 2048,
+            fft_size: FftSize::Fixed(2048),
+            analysis: AnalysisMode::Fft,
+            filterbank: FilterbankMode::Mel,
+            piano_low: PIANO_LOW_MIDI,
+            piano_high: PIANO_HIGH_MIDI,
+            piano_labels: true,
+            view: View::Spectrum,
             tau_spec: 0.06,
+            attack_tau: 0.0,
+            tau_tilt: 0.0,
+            preprocess: PreprocessParams::DEFAULT,
+            scaling: ScalingMode::Auto,
+            db_floor: -90.0,
+            db_ceiling: -20.0,
             gate_db: -65.0,
+            gate_mode: GateMode::Power,
+            noise_subtraction: 1.0,
+            calibrate_on_start: false,
+            calibrate_s: 3.0,
+            physics: PhysicsModel::Spring,
             flow_k: 0.18,
             spr_k: 60.0,
             spr_zeta: 1.0,
+            gravity: 4.0,
+            ema_tau: 0.08,
+            monstercat: 1.5,
+            dynamics: DynamicsParams::DEFAULT,
             color: RgbColor::WHITE,
+            both_display: BothDisplay::Mix,
+            system_color: RgbColor::ORANGE,
+            peak_caps: false,
+            peak_hold: 0.5,
+            peak_gravity: 2.0,
+            peak_color: None,
+            beat_pulse: false,
+            loudness_meter: false,
+            peak_readout: false,
+            peak_count: 3,
+            peak_interpolation: Interpolation::Gaussian,
+            peak_reassign: false,
+            feedback_detect: false,
+            feedback_persist_s: 0.3,
+            feedback_hook: None,
+            ltas: false,
+            ltas_window_s: 0.0,
+            ltas_color: RgbColor::CYAN,
+            snapshot_color: RgbColor::MAGENTA,
+            snapshot_path: None,
+            thd_generator: false,
+            thd_tone_hz: 1000.0,
+            thd_tone_db: -6.0,
+            spl_meter: false,
+            spl_weighting: FrequencyWeighting::A,
+            spl_time: TimeWeighting::Fast,
+            spl_offset_db: None,
+            spl_reference_db: 94.0,
+            spl_standard: ExposureStandard::Niosh,
+            a4_hz: 440.0,
+            temperament: Temperament::Equal,
+            temperament_root: 0,
         }
     }
 
@@ -70,15 +350,65 @@ impl Config {
         if let Some(v) = fc.frame_ms {
             self.frame_ms = v;
         }
-        if let Some(v) = fc.fft_size {
-            self.fft_size = v;
+        if let Some(v) = &fc.fft_size {
+            self.fft_size = parse_fft_size(v)?;
+        }
+        if let Some(v) = fc.analysis {
+            self.analysis = v;
+        }
+        if let Some(v) = fc.filterbank {
+            self.filterbank = v;
+        }
+        if let Some(v) = fc.piano_low.as_deref() {
+            self.piano_low =
+                parse_note(v).ok_or_else(|| invalid_note(v))?;
+        }
+        if let Some(v) = fc.piano_high.as_deref() {
+            self.piano_high =
+                parse_note(v).ok_or_else(|| invalid_note(v))?;
+        }
+        if let Some(v) = fc.view {
+            self.view = v;
         }
         if let Some(v) = fc.tau_spec {
             self.tau_spec = v;
         }
+        if let Some(v) = fc.attack_tau {
+            self.attack_tau = v;
+        }
+        if let Some(v) = fc.tau_tilt {
+            self.tau_tilt = v;
+        }
+        if let Some(fp) = &fc.preprocess {
+            apply_preprocess(&mut self.preprocess, fp);
+        }
+        if let Some(v) = fc.scaling {
+            self.scaling = v;
+        }
+        if let Some(v) = fc.db_floor {
+            self.db_floor = v;
+        }
+        if let Some(v) = fc.db_ceiling {
+            self.db_ceiling = v;
+        }
         if let Some(v) = fc.gate_db {
             self.gate_db = v;
         }
+        if let Some(v) = fc.gate_mode {
+            self.gate_mode = v;
+        }
+        if let Some(v) = fc.noise_subtraction {
+            self.noise_subtraction = v;
+        }
+        if let Some(v) = fc.calibrate_on_start {
+            self.calibrate_on_start = v;
+        }
+        if let Some(v) = fc.calibrate_s {
+            self.calibrate_s = v;
+        }
+        if let Some(v) = fc.physics {
+            self.physics = v;
+        }
         if let Some(v) = fc.flow_k {
             self.flow_k = v;
         }
@@ -88,9 +418,155 @@ impl Config {
         if let Some(v) = fc.spr_zeta {
             self.spr_zeta = v;
         }
+        if let Some(v) = fc.gravity {
+            self.gravity = v;
+        }
+        if let Some(v) = fc.ema_tau {
+            self.ema_tau = v;
+        }
+        if let Some(v) = fc.monstercat {
+            self.monstercat = v;
+        }
+        if let Some(fd) = &fc.dynamics {
+            apply_dynamics(&mut self.dynamics, fd);
+        }
+        self.apply_feedback(fc);
+        self.apply_distortion(fc);
+        self.apply_spl(fc);
+        self.apply_reference(fc)?;
+        self.apply_display(fc)
+    }
+
+    fn apply_feedback(&mut self, fc: &FileConfig) {
+        if let Some(v) = fc.feedback_detect {
+            self.feedback_detect = v;
+        }
+        if let Some(v) = fc.feedback_persist_s {
+            self.feedback_persist_s = v;
+        }
+        if let Some(v) = &fc.feedback_hook {
+            self.feedback_hook =
+                Some(v.clone()).filter(|cmd| !cmd.trim().is_empty());
+        }
+    }
+
+    /// Keys for the distortion view's test tone.
+    const fn apply_distortion(&mut self, fc: &FileConfig) {
+        if let Some(v) = fc.thd_generator {
+            self.thd_generator = v;
+        }
+        if let Some(v) = fc.thd_tone_hz {
+            self.thd_tone_hz = v;
+        }
+        if let Some(v) = fc.thd_tone_db {
+            self.thd_tone_db = v;
+        }
+    }
+
+    /// Keys for the sound level meter.
+    const fn apply_spl(&mut self, fc: &FileConfig) {
+        if let Some(v) = fc.spl_meter {
+            self.spl_meter = v;
+        }
+        if let Some(v) = fc.spl_weighting {
+            self.spl_weighting = v;
+        }
+        if let Some(v) = fc.spl_time {
+            self.spl_time = v;
+        }
+        if let Some(v) = fc.spl_offset_db {
+            self.spl_offset_db = Some(v);
+        }
+        if let Some(v) = fc.spl_reference_db {
+            self.spl_reference_db = v;
+        }
+        if let Some(v) = fc.spl_standard {
+            self.spl_standard = v;
+        }
+    }
+
+    /// Keys for the long-term average and the snapshot.
+    fn apply_reference(&mut self, fc: &FileConfig) -> Result<()> {
+        if let Some(v) = fc.ltas {
+            self.ltas = v;
+        }
+        if let Some(v) = fc.ltas_window_s {
+            self.ltas_window_s = v;
+        }
+        if let Some(v) = fc.ltas_color.as_deref() {
+            self.ltas_color = parse_hex_color(v)?;
+        }
+        if let Some(v) = fc.snapshot_color.as_deref() {
+            self.snapshot_color = parse_hex_color(v)?;
+        }
+        if let Some(v) = &fc.snapshot_path {
+            self.snapshot_path = Some(v.clone())
+                .filter(|path| !path.trim().is_empty());
+        }
+        Ok(())
+    }
+
+    /// Averaging window of the long-term average, `None` for the
+    /// whole session.
+    #[must_use]
+    pub fn ltas_window(&self) -> Option<f32> {
+        (self.ltas_window_s > 0.0).then_some(self.ltas_window_s)
+    }
+
+    /// Keys that only change what is drawn.
+    fn apply_display(&mut self, fc: &FileConfig) -> Result<()> {
         if let Some(v) = fc.color.as_deref() {
             self.color = parse_hex_color(v)?;
         }
+        if let Some(v) = fc.both_display {
+            self.both_display = v;
+        }
+        if let Some(v) = fc.system_color.as_deref() {
+            self.system_color = parse_hex_color(v)?;
+        }
+        if let Some(v) = fc.peak_caps {
+            self.peak_caps = v;
+        }
+        if let Some(v) = fc.peak_hold {
+            self.peak_hold = v;
+        }
+        if let Some(v) = fc.peak_gravity {
+            self.peak_gravity = v;
+        }
+        if let Some(v) = fc.peak_color.as_deref() {
+            self.peak_color = Some(parse_hex_color(v)?);
+        }
+        if let Some(v) = fc.beat_pulse {
+            self.beat_pulse = v;
+        }
+        if let Some(v) = fc.loudness_meter {
+            self.loudness_meter = v;
+        }
+        if let Some(v) = fc.peak_readout {
+            self.peak_readout = v;
+        }
+        if let Some(v) = fc.peak_count {
+            self.peak_count = v;
+        }
+        if let Some(v) = fc.peak_interpolation {
+            self.peak_interpolation = v;
+        }
+        if let Some(v) = fc.peak_reassign {
+            self.peak_reassign = v;
+        }
+        if let Some(v) = fc.piano_labels {
+            self.piano_labels = v;
+        }
+        if let Some(v) = fc.a4_hz {
+            self.a4_hz = v;
+        }
+        if let Some(v) = fc.temperament {
+            self.temperament = v;
+        }
+        if let Some(v) = fc.temperament_root.as_deref() {
+            self.temperament_root = parse_pitch_class(v)
+                .ok_or_else(|| invalid_pitch_class(v))?;
+        }
 
         Ok(())
     }
@@ -104,15 +580,60 @@ impl Config {
             self.fmax = 16_000.0;
         }
 
+        self.piano_low = self.piano_low.clamp(0, 127);
+        self.piano_high = self.piano_high.clamp(0, 127);
+        if self.piano_low >= self.piano_high {
+            self.piano_low = PIANO_LOW_MIDI;
+            self.piano_high = PIANO_HIGH_MIDI;
+        }
+
         self.frame_ms = self.frame_ms.clamp(8, 50);
-        self.fft_size = self.fft_size.clamp(512, 4096);
+        if let FftSize::Fixed(size) = &mut self.fft_size {
+            *size = (*size).clamp(FftSize::MIN, FftSize::MAX);
+        }
 
         self.tau_spec = self.tau_spec.clamp(0.01, 0.20);
+        self.attack_tau = self.attack_tau.clamp(0.0, 0.20);
+        self.tau_tilt = self.tau_tilt.clamp(-1.0, 1.0);
+
+        sanitize_preprocess(&mut self.preprocess);
+
+        self.db_floor = self.db_floor.clamp(-140.0, -20.0);
+        self.db_ceiling = self.db_ceiling.clamp(-60.0, 0.0);
+
+        if self.db_ceiling - self.db_floor < 12.0 {
+            self.db_floor = -90.0;
+            self.db_ceiling = -20.0;
+        }
+
         self.gate_db = self.gate_db.clamp(-80.0, -30.0);
+        self.noise_subtraction =
+            self.noise_subtraction.clamp(0.0, 4.0);
+        self.calibrate_s = self.calibrate_s.clamp(1.0, 30.0);
 
         self.flow_k = self.flow_k.clamp(0.0, 1.0);
         self.spr_k = self.spr_k.clamp(10.0, 200.0);
         self.spr_zeta = self.spr_zeta.clamp(0.1, 2.0);
+        self.gravity = self.gravity.clamp(0.5, 50.0);
+        self.ema_tau = self.ema_tau.clamp(0.01, 1.0);
+        self.monstercat = self.monstercat.clamp(1.0, 5.0);
+
+        self.peak_hold = self.peak_hold.clamp(0.0, 5.0);
+        self.peak_gravity = self.peak_gravity.clamp(0.1, 50.0);
+        self.feedback_persist_s =
+            self.feedback_persist_s.clamp(0.05, 5.0);
+        self.ltas_window_s = self.ltas_window_s.clamp(0.0, 3600.0);
+        self.peak_count = self.peak_count.clamp(1, 8);
+        self.thd_tone_hz = self.thd_tone_hz.clamp(20.0, 20_000.0);
+        self.thd_tone_db = self.thd_tone_db.clamp(-60.0, 0.0);
+        self.spl_offset_db =
+            self.spl_offset_db.map(|v| v.clamp(0.0, 200.0));
+        self.spl_reference_db =
+            self.spl_reference_db.clamp(60.0, 140.0);
+
+        sanitize_dynamics(&mut self.dynamics);
+
+        self.a4_hz = self.a4_hz.clamp(400.0, 480.0);
     }
 }
 
@@ -121,13 +642,187 @@ struct FileConfig {
     pub fmin: Option<f32>,
     pub fmax: Option<f32>,
     pub frame_ms: Option<u64>,
-    pub fft_size: Option<usize>,
+    pub fft_size: Option<FileFftSize>,
+    pub analysis: Option<AnalysisMode>,
+    pub filterbank: Option<FilterbankMode>,
+    pub piano_low: Option<String>,
+    pub piano_high: Option<String>,
+    pub piano_labels: Option<bool>,
+    pub view: Option<View>,
     pub tau_spec: Option<f32>,
+    pub attack_tau: Option<f32>,
+    pub tau_tilt: Option<f32>,
+    pub preprocess: Option<FilePreprocess>,
+    pub scaling: Option<ScalingMode>,
+    pub db_floor: Option<f32>,
+    pub db_ceiling: Option<f32>,
     pub gate_db: Option<f32>,
+    pub gate_mode: Option<GateMode>,
+    pub noise_subtraction: Option<f32>,
+    pub calibrate_on_start: Option<bool>,
+    pub calibrate_s: Option<f32>,
+    pub physics: Option<PhysicsModel>,
     pub flow_k: Option<f32>,
     pub spr_k: Option<f32>,
     pub spr_zeta: Option<f32>,
+    pub gravity: Option<f32>,
+    pub ema_tau: Option<f32>,
+    pub monstercat: Option<f32>,
+    pub dynamics: Option<FileDynamics>,
     pub color: Option<String>,
+    pub both_display: Option<BothDisplay>,
+    pub system_color: Option<String>,
+    pub peak_caps: Option<bool>,
+    pub peak_hold: Option<f32>,
+    pub peak_gravity: Option<f32>,
+    pub peak_color: Option<String>,
+    pub beat_pulse: Option<bool>,
+    pub loudness_meter: Option<bool>,
+    pub peak_readout: Option<bool>,
+    pub peak_count: Option<usize>,
+    pub peak_interpolation: Option<Interpolation>,
+    pub peak_reassign: Option<bool>,
+    pub feedback_detect: Option<bool>,
+    pub feedback_persist_s: Option<f32>,
+    pub feedback_hook: Option<String>,
+    pub ltas: Option<bool>,
+    pub ltas_window_s: Option<f32>,
+    pub ltas_color: Option<String>,
+    pub snapshot_color: Option<String>,
+    pub snapshot_path: Option<String>,
+    pub thd_generator: Option<bool>,
+    pub thd_tone_hz: Option<f32>,
+    pub thd_tone_db: Option<f32>,
+    pub spl_meter: Option<bool>,
+    pub spl_weighting: Option<FrequencyWeighting>,
+    pub spl_time: Option<TimeWeighting>,
+    pub spl_offset_db: Option<f32>,
+    pub spl_reference_db: Option<f32>,
+    pub spl_standard: Option<ExposureStandard>,
+    pub a4_hz: Option<f32>,
+    pub temperament: Option<Temperament>,
+    pub temperament_root: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Default, Clone)]
+struct FileDynamics {
+    pub eq_tau_s: Option<f32>,
+    pub percentile_low: Option<f32>,
+    pub percentile_high: Option<f32>,
+    pub range_tau_low_s: Option<f32>,
+    pub range_tau_high_s: Option<f32>,
+    pub hybrid_tau_s: Option<f32>,
+    pub margin_low_db: Option<f32>,
+    pub margin_high_db: Option<f32>,
+    pub min_range_db: Option<f32>,
+    pub shape_exp: Option<f32>,
+    pub ease_exp: Option<f32>,
+    pub gate_attack_s: Option<f32>,
+    pub gate_release_s: Option<f32>,
+    pub gate_confirm_s: Option<f32>,
+    pub silence_tau_s: Option<f32>,
+    pub band_floor_rise_s: Option<f32>,
+    pub band_floor_fall_s: Option<f32>,
+    pub band_gate_margin_db: Option<f32>,
+}
+
+#[derive(Debug, Deserialize, Default, Clone)]
+struct FilePreprocess {
+    pub dc_block: Option<bool>,
+    pub highpass_hz: Option<f32>,
+    pub lowpass_hz: Option<f32>,
+    pub filter_q: Option<f32>,
+    pub pre_emphasis: Option<f32>,
+}
+
+fn apply_preprocess(p: &mut PreprocessParams, fp: &FilePreprocess) {
+    if let Some(v) = fp.dc_block {
+        p.dc_block = v;
+    }
+    let fields = [
+        (&mut p.highpass_hz, fp.highpass_hz),
+        (&mut p.lowpass_hz, fp.lowpass_hz),
+        (&mut p.filter_q, fp.filter_q),
+        (&mut p.pre_emphasis, fp.pre_emphasis),
+    ];
+
+    for (dst, src) in fields {
+        if let Some(v) = src {
+            *dst = v;
+        }
+    }
+}
+
+fn sanitize_preprocess(p: &mut PreprocessParams) {
+    p.highpass_hz = if p.highpass_hz > 0.0 {
+        p.highpass_hz.clamp(10.0, 1000.0)
+    } else {
+        0.0
+    };
+    p.lowpass_hz = if p.lowpass_hz > 0.0 {
+        p.lowpass_hz.clamp(1000.0, 24_000.0)
+    } else {
+        0.0
+    };
+    p.filter_q = p.filter_q.clamp(0.3, 4.0);
+    p.pre_emphasis = p.pre_emphasis.clamp(0.0, 0.99);
+}
+
+fn apply_dynamics(d: &mut DynamicsParams, fd: &FileDynamics) {
+    let fields = [
+        (&mut d.eq_tau_s, fd.eq_tau_s),
+        (&mut d.percentile_low, fd.percentile_low),
+        (&mut d.percentile_high, fd.percentile_high),
+        (&mut d.range_tau_low_s, fd.range_tau_low_s),
+        (&mut d.range_tau_high_s, fd.range_tau_high_s),
+        (&mut d.hybrid_tau_s, fd.hybrid_tau_s),
+        (&mut d.margin_low_db, fd.margin_low_db),
+        (&mut d.margin_high_db, fd.margin_high_db),
+        (&mut d.min_range_db, fd.min_range_db),
+        (&mut d.shape_exp, fd.shape_exp),
+        (&mut d.ease_exp, fd.ease_exp),
+        (&mut d.gate_attack_s, fd.gate_attack_s),
+        (&mut d.gate_release_s, fd.gate_release_s),
+        (&mut d.gate_confirm_s, fd.gate_confirm_s),
+        (&mut d.silence_tau_s, fd.silence_tau_s),
+        (&mut d.band_floor_rise_s, fd.band_floor_rise_s),
+        (&mut d.band_floor_fall_s, fd.band_floor_fall_s),
+        (&mut d.band_gate_margin_db, fd.band_gate_margin_db),
+    ];
+
+    for (dst, src) in fields {
+        if let Some(v) = src {
+            *dst = v;
+        }
+    }
+}
+
+fn sanitize_dynamics(d: &mut DynamicsParams) {
+    d.eq_tau_s = d.eq_tau_s.clamp(0.5, 60.0);
+
+    d.percentile_low = d.percentile_low.clamp(0.0, 0.5);
+    d.percentile_high = d.percentile_high.clamp(0.5, 1.0);
+
+    d.range_tau_low_s = d.range_tau_low_s.clamp(0.05, 10.0);
+    d.range_tau_high_s = d.range_tau_high_s.clamp(0.05, 10.0);
+    d.hybrid_tau_s = d.hybrid_tau_s.clamp(1.0, 120.0);
+
+    d.margin_low_db = d.margin_low_db.clamp(-24.0, 0.0);
+    d.margin_high_db = d.margin_high_db.clamp(0.0, 24.0);
+    d.min_range_db = d.min_range_db.clamp(1.0, 60.0);
+
+    d.shape_exp = d.shape_exp.clamp(0.2, 3.0);
+    d.ease_exp = d.ease_exp.clamp(0.2, 4.0);
+
+    d.gate_attack_s = d.gate_attack_s.clamp(0.001, 0.5);
+    d.gate_release_s = d.gate_release_s.clamp(0.01, 2.0);
+    d.gate_confirm_s = d.gate_confirm_s.clamp(0.0, 2.0);
+
+    d.silence_tau_s = d.silence_tau_s.clamp(0.02, 2.0);
+
+    d.band_floor_rise_s = d.band_floor_rise_s.clamp(0.5, 60.0);
+    d.band_floor_fall_s = d.band_floor_fall_s.clamp(0.01, 2.0);
+    d.band_gate_margin_db = d.band_gate_margin_db.clamp(1.0, 30.0);
 }
 
 fn load_file_config() -> Result<Option<FileConfig>> {
@@ -171,10 +866,34 @@ fn parse_hex_color(value: &str) -> Result<RgbColor> {
     Ok(RgbColor { r, g, b })
 }
 
+fn parse_fft_size(value: &FileFftSize) -> Result<FftSize> {
+    match value {
+        FileFftSize::Samples(size) => Ok(FftSize::Fixed(*size)),
+        FileFftSize::Named(name) if name.trim() == "auto" => {
+            Ok(FftSize::Auto)
+        }
+        FileFftSize::Named(name) => Err(anyhow::anyhow!(
+            "invalid fft_size `{name}`: expected a number of samples or `auto`"
+        )),
+    }
+}
+
 fn invalid_color(value: &str) -> anyhow::Error {
     anyhow::anyhow!("invalid color `{value}`: expected `#RRGGBB`")
 }
 
+fn invalid_pitch_class(value: &str) -> anyhow::Error {
+    anyhow::anyhow!(
+        "invalid note name `{value}`: expected `C`, `F#`, `Bb` or similar"
+    )
+}
+
+fn invalid_note(value: &str) -> anyhow::Error {
+    anyhow::anyhow!(
+        "invalid note `{value}`: expected `A0`, `C#4`, `Bb5` or similar"
+    )
+}
+
 #[allow(clippy::arithmetic_side_effects)]
 const fn parse_hex_channel(high: u8, low: u8) -> Option<u8> {
     let Some(high) = parse_hex_digit(high) else {
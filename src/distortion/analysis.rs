@@ -0,0 +1,75 @@
+use realfft::{RealFftPlanner, RealToComplex, num_complex::Complex};
+use std::sync::Arc;
+
+use crate::dsp::{blackman_harris, prepare_fft_input_inplace};
+
+/// Power spectrum for distortion measurement, windowed with
+/// [`blackman_harris`].
+///
+/// Bins are scaled so the bins under a sinusoid's main lobe add up to
+/// its mean power, and those under broadband noise to the noise
+/// power. Power can then be accounted for by summing bins.
+pub struct DistortionAnalyzer {
+    fft: Arc<dyn RealToComplex<f32>>,
+    window: Vec<f32>,
+    scale: f32,
+    buf: Vec<f32>,
+    out: Vec<Complex<f32>>,
+    spec: Vec<f32>,
+}
+
+impl DistortionAnalyzer {
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn new(fft_size: usize) -> Self {
+        let fft =
+            RealFftPlanner::<f32>::new().plan_fft_forward(fft_size);
+        let window = blackman_harris(fft_size);
+        let energy = window.iter().map(|w| w * w).sum::<f32>();
+        let scale = 2.0 / (fft_size as f32 * energy).max(1e-12);
+
+        Self {
+            buf: fft.make_input_vec(),
+            out: fft.make_output_vec(),
+            spec: vec![0.0; fft_size / 2],
+            window,
+            scale,
+            fft,
+        }
+    }
+
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.window.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.window.is_empty()
+    }
+
+    /// Transforms one frame of [`Self::len`] samples. Returns `false`
+    /// if the frame could not be transformed.
+    pub fn process(&mut self, samples: &[f32]) -> bool {
+        if samples.len() != self.window.len() {
+            return false;
+        }
+        prepare_fft_input_inplace(
+            samples,
+            &self.window,
+            &mut self.buf,
+        );
+        if self.fft.process(&mut self.buf, &mut self.out).is_err() {
+            return false;
+        }
+        for (p, c) in self.spec.iter_mut().zip(&self.out) {
+            *p = c.norm_sqr() * self.scale;
+        }
+        true
+    }
+
+    #[must_use]
+    pub fn spectrum(&self) -> &[f32] {
+        &self.spec
+    }
+}
@@ -0,0 +1,330 @@
+mod analysis;
+
+pub use analysis::DistortionAnalyzer;
+
+use crate::partials::{Interpolation, interpolate_peak};
+
+/// Bins either side of a peak counted as its main lobe. The
+/// Blackman-Harris main lobe is four bins wide each way.
+const LOBE_BINS: usize = 5;
+
+/// Mean power of a full-scale sine.
+const FULL_SCALE_POWER: f32 = 0.5;
+
+/// Share of the band the fundamental must carry to count as a test
+/// tone, so music or noise is not measured.
+const MIN_FUNDAMENTAL_SHARE: f32 = 0.5;
+
+/// How far from the hinted frequency the fundamental is looked for,
+/// as a fraction of it.
+const HINT_TOLERANCE: f32 = 0.02;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DistortionParams {
+    /// Measurement band; power outside it is ignored.
+    pub fmin: f32,
+    pub fmax: f32,
+    /// Highest harmonic counted towards THD.
+    pub max_harmonic: usize,
+    /// Time constant the meter averages readings over.
+    pub average_s: f32,
+}
+
+impl DistortionParams {
+    pub const DEFAULT: Self = Self {
+        fmin: 20.0,
+        fmax: 20_000.0,
+        max_harmonic: 10,
+        average_s: 0.5,
+    };
+}
+
+impl Default for DistortionParams {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+/// Powers of a test tone, its harmonics and everything else in the
+/// measurement band. Powers are mean square, a full-scale sine being
+/// 0.5.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DistortionReading {
+    pub fundamental_hz: f32,
+    pub fundamental: f32,
+    /// Powers of the second harmonic upwards, as far as the band
+    /// reaches.
+    pub harmonics: Vec<f32>,
+    pub total: f32,
+}
+
+impl DistortionReading {
+    #[must_use]
+    pub fn harmonic_power(&self) -> f32 {
+        self.harmonics.iter().sum()
+    }
+
+    /// Power that is neither the fundamental nor a harmonic.
+    #[must_use]
+    pub fn noise_power(&self) -> f32 {
+        (self.total - self.fundamental - self.harmonic_power())
+            .max(0.0)
+    }
+
+    #[must_use]
+    pub fn fundamental_dbfs(&self) -> f32 {
+        power_db(self.fundamental / FULL_SCALE_POWER)
+    }
+
+    /// Total harmonic distortion as an amplitude ratio.
+    #[must_use]
+    pub fn thd(&self) -> f32 {
+        (self.harmonic_power() / self.fundamental.max(1e-30)).sqrt()
+    }
+
+    /// Total harmonic distortion plus noise as an amplitude ratio.
+    #[must_use]
+    pub fn thd_n(&self) -> f32 {
+        ((self.total - self.fundamental).max(0.0)
+            / self.fundamental.max(1e-30))
+        .sqrt()
+    }
+
+    /// Signal to noise and distortion ratio in dB.
+    #[must_use]
+    pub fn sinad_db(&self) -> f32 {
+        power_db(
+            self.total / (self.total - self.fundamental).max(1e-30),
+        )
+    }
+
+    /// Level of harmonic `n`, counting the fundamental as 1, relative
+    /// to the fundamental in dB.
+    #[must_use]
+    pub fn harmonic_dbc(&self, n: usize) -> Option<f32> {
+        let p = self.harmonics.get(n.checked_sub(2)?)?;
+        Some(power_db(p / self.fundamental.max(1e-30)))
+    }
+}
+
+/// An amplitude ratio in dB.
+#[must_use]
+pub fn ratio_db(ratio: f32) -> f32 {
+    20.0 * ratio.max(1e-15).log10()
+}
+
+fn power_db(ratio: f32) -> f32 {
+    10.0 * ratio.max(1e-30).log10()
+}
+
+/// Measures the strongest tone of a [`DistortionAnalyzer`] spectrum,
+/// or the one near `hint_hz` when given. `None` without a clear test
+/// tone.
+#[allow(
+    clippy::cast_precision_loss,
+    clippy::cast_possible_truncation,
+    clippy::cast_sign_loss
+)]
+#[must_use]
+pub fn measure_distortion(
+    spec: &[f32],
+    bin_hz: f32,
+    hint_hz: Option<f32>,
+    params: &DistortionParams,
+) -> Option<DistortionReading> {
+    if bin_hz <= 0.0 || spec.len() <= 2 * LOBE_BINS {
+        return None;
+    }
+    let lo = ((params.fmin / bin_hz).ceil() as usize).max(1);
+    let hi = ((params.fmax / bin_hz).floor() as usize)
+        .min(spec.len().saturating_sub(1));
+    if lo >= hi {
+        return None;
+    }
+
+    let (search_lo, search_hi) = hint_hz.map_or((lo, hi), |hz| {
+        let reach = hz.mul_add(HINT_TOLERANCE, bin_hz);
+        (
+            (((hz - reach) / bin_hz).floor().max(0.0) as usize)
+                .max(lo),
+            (((hz + reach) / bin_hz).ceil() as usize).min(hi),
+        )
+    });
+    let k0 = peak_bin(spec, search_lo, search_hi)?;
+    let at = |k: usize| spec.get(k).copied().unwrap_or(0.0);
+    let (delta, _) = interpolate_peak(
+        (at(k0.wrapping_sub(1)), at(k0), at(k0.saturating_add(1))),
+        Interpolation::Gaussian,
+    );
+    let fundamental_hz = (k0 as f32 + delta) * bin_hz;
+
+    let mut used = vec![false; spec.len()];
+    let fundamental = take_lobe(spec, k0, &mut used);
+    let harmonics = (2..=params.max_harmonic)
+        .map_while(|n| {
+            let k =
+                (n as f32 * fundamental_hz / bin_hz).round() as usize;
+            (k <= hi.saturating_sub(LOBE_BINS)).then(|| {
+                let center = peak_bin(
+                    spec,
+                    k.saturating_sub(1),
+                    k.saturating_add(1),
+                )
+                .unwrap_or(k);
+                take_lobe(spec, center, &mut used)
+            })
+        })
+        .collect::<Vec<_>>();
+
+    let rest = (lo..=hi)
+        .filter(|&k| !used.get(k).copied().unwrap_or(true))
+        .map(at)
+        .sum::<f32>();
+    let total = rest + fundamental + harmonics.iter().sum::<f32>();
+    if fundamental <= 1e-20
+        || fundamental < total * MIN_FUNDAMENTAL_SHARE
+    {
+        return None;
+    }
+
+    Some(DistortionReading {
+        fundamental_hz,
+        fundamental,
+        harmonics,
+        total,
+    })
+}
+
+/// Loudest bin in `lo..=hi`.
+fn peak_bin(spec: &[f32], lo: usize, hi: usize) -> Option<usize> {
+    spec.get(lo..=hi)?
+        .iter()
+        .enumerate()
+        .max_by(|a, b| a.1.total_cmp(b.1))
+        .map(|(i, _)| i.saturating_add(lo))
+}
+
+/// Adds up the bins of the main lobe around `k` not yet counted,
+/// marking them as counted.
+fn take_lobe(spec: &[f32], k: usize, used: &mut [bool]) -> f32 {
+    let range =
+        k.saturating_sub(LOBE_BINS)..k.saturating_add(LOBE_BINS + 1);
+    spec.get(range.clone())
+        .into_iter()
+        .flatten()
+        .zip(used.get_mut(range).into_iter().flatten())
+        .filter(|(_, u)| !**u)
+        .map(|(&p, u)| {
+            *u = true;
+            p
+        })
+        .sum()
+}
+
+/// One frame for [`DistortionMeter::process`]: [`DistortionMeter::len`]
+/// samples covering `dt_s` seconds, and the expected tone when known.
+pub struct DistortionInput<'a> {
+    pub samples: &'a [f32],
+    pub sample_rate: f32,
+    pub hint_hz: Option<f32>,
+    pub dt_s: f32,
+}
+
+/// A [`DistortionAnalyzer`] with readings averaged over time.
+pub struct DistortionMeter {
+    analyzer: DistortionAnalyzer,
+    params: DistortionParams,
+    reading: Option<DistortionReading>,
+}
+
+impl DistortionMeter {
+    #[must_use]
+    pub fn new(fft_size: usize, params: DistortionParams) -> Self {
+        Self {
+            analyzer: DistortionAnalyzer::new(fft_size),
+            params,
+            reading: None,
+        }
+    }
+
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.analyzer.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.analyzer.is_empty()
+    }
+
+    #[must_use]
+    pub const fn params(&self) -> &DistortionParams {
+        &self.params
+    }
+
+    /// Measures one frame. Readings of the same tone are averaged; a
+    /// new tone starts over.
+    pub fn process(
+        &mut self,
+        input: &DistortionInput,
+    ) -> Option<&DistortionReading> {
+        if !self.analyzer.process(input.samples) {
+            return self.reading.as_ref();
+        }
+        #[allow(clippy::cast_precision_loss)]
+        let bin_hz = input.sample_rate / self.analyzer.len() as f32;
+        let Some(new) = measure_distortion(
+            self.analyzer.spectrum(),
+            bin_hz,
+            input.hint_hz,
+            &self.params,
+        ) else {
+            self.reading = None;
+            return None;
+        };
+
+        let alpha = 1.0
+            - (-input.dt_s / self.params.average_s.max(1e-3)).exp();
+        let reading = match self.reading.take() {
+            Some(old) if same_tone(&old, &new, bin_hz) => {
+                blend(old, &new, alpha)
+            }
+            _ => new,
+        };
+        self.reading = Some(reading);
+        self.reading.as_ref()
+    }
+
+    #[must_use]
+    pub const fn reading(&self) -> Option<&DistortionReading> {
+        self.reading.as_ref()
+    }
+
+    pub fn reset(&mut self) {
+        self.reading = None;
+    }
+}
+
+fn same_tone(
+    old: &DistortionReading,
+    new: &DistortionReading,
+    bin_hz: f32,
+) -> bool {
+    old.harmonics.len() == new.harmonics.len()
+        && (old.fundamental_hz - new.fundamental_hz).abs() <= bin_hz
+}
+
+fn blend(
+    mut old: DistortionReading,
+    new: &DistortionReading,
+    alpha: f32,
+) -> DistortionReading {
+    let mix = |a: f32, b: f32| (b - a).mul_add(alpha, a);
+    old.fundamental_hz = mix(old.fundamental_hz, new.fundamental_hz);
+    old.fundamental = mix(old.fundamental, new.fundamental);
+    old.total = mix(old.total, new.total);
+    for (a, &b) in old.harmonics.iter_mut().zip(&new.harmonics) {
+        *a = mix(*a, b);
+    }
+    old
+}
@@ -0,0 +1,96 @@
+use std::f64::consts::PI;
+
+/// Second-order IIR section in transposed direct form II, with `a0`
+/// normalised to one.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Biquad {
+    b0: f64,
+    b1: f64,
+    b2: f64,
+    a1: f64,
+    a2: f64,
+    z1: f64,
+    z2: f64,
+}
+
+impl Biquad {
+    #[must_use]
+    pub const fn from_coeffs(b: [f64; 3], a1: f64, a2: f64) -> Self {
+        Self {
+            b0: b[0],
+            b1: b[1],
+            b2: b[2],
+            a1,
+            a2,
+            z1: 0.0,
+            z2: 0.0,
+        }
+    }
+
+    /// RBJ cookbook low-pass.
+    #[must_use]
+    pub fn low_pass(sample_rate: f32, hz: f32, q: f32) -> Self {
+        let (cos_w, alpha) = prewarp(sample_rate, hz, q);
+        let a0 = 1.0 + alpha;
+        let b1 = (1.0 - cos_w) / a0;
+
+        Self::from_coeffs(
+            [b1 * 0.5, b1, b1 * 0.5],
+            -2.0 * cos_w / a0,
+            (1.0 - alpha) / a0,
+        )
+    }
+
+    /// RBJ cookbook high-pass.
+    #[must_use]
+    pub fn high_pass(sample_rate: f32, hz: f32, q: f32) -> Self {
+        let (cos_w, alpha) = prewarp(sample_rate, hz, q);
+        let a0 = 1.0 + alpha;
+        let b0 = (1.0 + cos_w) * 0.5 / a0;
+
+        Self::from_coeffs(
+            [b0, -2.0 * b0, b0],
+            -2.0 * cos_w / a0,
+            (1.0 - alpha) / a0,
+        )
+    }
+
+    #[inline]
+    pub fn process(&mut self, x: f64) -> f64 {
+        let y = self.b0.mul_add(x, self.z1);
+        self.z1 = self.b1.mul_add(x, -self.a1 * y) + self.z2;
+        self.z2 = self.b2.mul_add(x, -self.a2 * y);
+        y
+    }
+
+    pub const fn reset(&mut self) {
+        self.z1 = 0.0;
+        self.z2 = 0.0;
+    }
+
+    /// Magnitude response in dB at `hz`.
+    #[must_use]
+    pub fn response_db(&self, sample_rate: f32, hz: f32) -> f64 {
+        let w = 2.0 * PI * f64::from(hz) / f64::from(sample_rate);
+        let (s1, c1) = w.sin_cos();
+        let (s2, c2) = (2.0 * w).sin_cos();
+
+        let num_re =
+            self.b2.mul_add(c2, self.b1.mul_add(c1, self.b0));
+        let num_im = -self.b2.mul_add(s2, self.b1 * s1);
+        let den_re = self.a2.mul_add(c2, self.a1.mul_add(c1, 1.0));
+        let den_im = -self.a2.mul_add(s2, self.a1 * s1);
+
+        let num = num_re.hypot(num_im);
+        let den = den_re.hypot(den_im).max(1e-300);
+        20.0 * (num / den).max(1e-300).log10()
+    }
+}
+
+fn prewarp(sample_rate: f32, hz: f32, q: f32) -> (f64, f64) {
+    let fs = f64::from(sample_rate.max(1.0));
+    let f0 = f64::from(hz).clamp(1e-3, fs * 0.499);
+    let w = 2.0 * PI * f0 / fs;
+    let (sin_w, cos_w) = w.sin_cos();
+    (cos_w, sin_w / (2.0 * f64::from(q.max(1e-3))))
+}
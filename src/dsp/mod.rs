@@ -1,9 +1,13 @@
+mod biquad;
 mod ema;
 mod mel;
+mod preprocess;
 mod weighting;
 mod window;
 
+pub use biquad::Biquad;
 pub use ema::{ema_precomputed, ema_tc};
 pub use mel::{hz_to_mel, mel_to_hz};
+pub use preprocess::{DcBlocker, Preprocess, PreprocessParams};
 pub use weighting::a_weighting;
-pub use window::{hann, prepare_fft_input_inplace};
+pub use window::{blackman_harris, hann, prepare_fft_input_inplace};
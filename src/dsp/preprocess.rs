@@ -0,0 +1,121 @@
+use super::Biquad;
+
+const DC_CORNER_HZ: f32 = 10.0;
+
+/// Per-sample filters applied before analysis. A corner of `0.0`
+/// disables that filter, as does a `pre_emphasis` of `0.0`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PreprocessParams {
+    pub dc_block: bool,
+    pub highpass_hz: f32,
+    pub lowpass_hz: f32,
+    pub filter_q: f32,
+    pub pre_emphasis: f32,
+}
+
+impl PreprocessParams {
+    pub const DEFAULT: Self = Self {
+        dc_block: false,
+        highpass_hz: 0.0,
+        lowpass_hz: 0.0,
+        filter_q: std::f32::consts::FRAC_1_SQRT_2,
+        pre_emphasis: 0.0,
+    };
+}
+
+impl Default for PreprocessParams {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+/// One-pole DC blocker: `y[n] = x[n] - x[n-1] + r * y[n-1]`.
+#[derive(Debug, Clone, Copy)]
+pub struct DcBlocker {
+    r: f32,
+    x1: f32,
+    y1: f32,
+}
+
+impl DcBlocker {
+    #[must_use]
+    pub fn new(sample_rate: f32, corner_hz: f32) -> Self {
+        let w =
+            std::f32::consts::TAU * corner_hz / sample_rate.max(1.0);
+        Self {
+            r: (-w).exp(),
+            x1: 0.0,
+            y1: 0.0,
+        }
+    }
+
+    #[inline]
+    pub fn process(&mut self, x: f32) -> f32 {
+        let y = self.r.mul_add(self.y1, x - self.x1);
+        self.x1 = x;
+        self.y1 = y;
+        y
+    }
+}
+
+/// DC blocker, high-pass, low-pass and pre-emphasis, in that order.
+pub struct Preprocess {
+    params: PreprocessParams,
+    sample_rate: f32,
+    dc: Option<DcBlocker>,
+    highpass: Option<Biquad>,
+    lowpass: Option<Biquad>,
+    emphasis_prev: f32,
+}
+
+impl Preprocess {
+    #[must_use]
+    pub fn new(params: PreprocessParams, sample_rate: f32) -> Self {
+        let q = params.filter_q;
+        Self {
+            params,
+            sample_rate,
+            dc: params
+                .dc_block
+                .then(|| DcBlocker::new(sample_rate, DC_CORNER_HZ)),
+            highpass: (params.highpass_hz > 0.0).then(|| {
+                Biquad::high_pass(sample_rate, params.highpass_hz, q)
+            }),
+            lowpass: (params.lowpass_hz > 0.0).then(|| {
+                Biquad::low_pass(sample_rate, params.lowpass_hz, q)
+            }),
+            emphasis_prev: 0.0,
+        }
+    }
+
+    #[must_use]
+    pub const fn params(&self) -> &PreprocessParams {
+        &self.params
+    }
+
+    #[must_use]
+    pub const fn sample_rate(&self) -> f32 {
+        self.sample_rate
+    }
+
+    #[inline]
+    #[allow(clippy::cast_possible_truncation)]
+    pub fn process(&mut self, x: f32) -> f32 {
+        let mut y = x;
+        if let Some(dc) = &mut self.dc {
+            y = dc.process(y);
+        }
+        if let Some(hp) = &mut self.highpass {
+            y = hp.process(f64::from(y)) as f32;
+        }
+        if let Some(lp) = &mut self.lowpass {
+            y = lp.process(f64::from(y)) as f32;
+        }
+        if self.params.pre_emphasis > 0.0 {
+            let prev = self.emphasis_prev;
+            self.emphasis_prev = y;
+            y = (-self.params.pre_emphasis).mul_add(prev, y);
+        }
+        y
+    }
+}
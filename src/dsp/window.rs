@@ -23,3 +23,22 @@ pub fn prepare_fft_input_inplace(
         samples.iter().zip(window.iter()).map(|(&s, &w)| s * w),
     );
 }
+
+/// Four-term Blackman-Harris window. Its side lobes sit about 92 dB
+/// down, low enough to measure distortion next to a strong tone.
+#[must_use]
+#[allow(clippy::cast_precision_loss, clippy::arithmetic_side_effects)]
+pub fn blackman_harris(n: usize) -> Vec<f32> {
+    const A: [f32; 4] = [0.358_75, 0.488_29, 0.141_28, 0.011_68];
+    let den = (n.max(2) - 1) as f32;
+    (0..n)
+        .map(|i| {
+            let x = std::f32::consts::TAU * i as f32 / den;
+            let [a0, a1, a2, a3] = A;
+            a3.mul_add(
+                -(3.0 * x).cos(),
+                a2.mul_add((2.0 * x).cos(), a1.mul_add(-x.cos(), a0)),
+            )
+        })
+        .collect()
+}
@@ -0,0 +1,77 @@
+mod spectral;
+mod temporal;
+
+pub use spectral::{
+    spectral_centroid, spectral_flatness, spectral_rolloff,
+};
+pub use temporal::{crest_factor, rms, zero_crossing_rate};
+
+use crate::beat::spectral_flux;
+
+/// Fraction of spectral energy below the reported rolloff frequency.
+pub const DEFAULT_ROLLOFF: f32 = 0.85;
+
+/// Descriptive features of one analysis frame. Frequencies are in Hz;
+/// `flatness` is in `0..=1`, `crest` is the linear peak-to-RMS ratio
+/// and `zcr` is crossings per sample.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct SpectralFeatures {
+    pub centroid_hz: f32,
+    pub rolloff_hz: f32,
+    pub flatness: f32,
+    pub flux: f32,
+    pub crest: f32,
+    pub rms: f32,
+    pub zcr: f32,
+}
+
+/// Computes [`SpectralFeatures`] frame by frame, keeping the previous
+/// spectrum for flux.
+pub struct FeatureExtractor {
+    prev: Vec<f32>,
+    rolloff: f32,
+}
+
+impl Default for FeatureExtractor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl FeatureExtractor {
+    #[must_use]
+    pub const fn new() -> Self {
+        Self::with_rolloff(DEFAULT_ROLLOFF)
+    }
+
+    #[must_use]
+    pub const fn with_rolloff(rolloff: f32) -> Self {
+        Self {
+            prev: Vec::new(),
+            rolloff,
+        }
+    }
+
+    /// `spec_pow` is a one-sided power spectrum with bins `bin_hz`
+    /// apart, and `samples` the time-domain frame it came from.
+    pub fn process(
+        &mut self,
+        spec_pow: &[f32],
+        samples: &[f32],
+        bin_hz: f32,
+    ) -> SpectralFeatures {
+        SpectralFeatures {
+            centroid_hz: spectral_centroid(spec_pow, bin_hz),
+            rolloff_hz: spectral_rolloff(
+                spec_pow,
+                bin_hz,
+                self.rolloff,
+            ),
+            flatness: spectral_flatness(spec_pow),
+            flux: spectral_flux(&mut self.prev, spec_pow),
+            crest: crest_factor(samples),
+            rms: rms(samples),
+            zcr: zero_crossing_rate(samples),
+        }
+    }
+}
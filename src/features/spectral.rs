@@ -0,0 +1,69 @@
+/// Magnitude-weighted mean frequency.
+#[must_use]
+#[allow(clippy::cast_precision_loss)]
+pub fn spectral_centroid(spec_pow: &[f32], bin_hz: f32) -> f32 {
+    let mut weighted = 0.0f64;
+    let mut total = 0.0f64;
+    for (i, &p) in spec_pow.iter().enumerate() {
+        let mag = f64::from(p.max(0.0).sqrt());
+        weighted = (i as f64).mul_add(mag, weighted);
+        total += mag;
+    }
+
+    if total <= 0.0 {
+        return 0.0;
+    }
+    #[allow(clippy::cast_possible_truncation)]
+    let centroid_bin = (weighted / total) as f32;
+    centroid_bin * bin_hz
+}
+
+/// Lowest frequency below which `fraction` of the total power lies.
+#[must_use]
+#[allow(clippy::cast_precision_loss)]
+pub fn spectral_rolloff(
+    spec_pow: &[f32],
+    bin_hz: f32,
+    fraction: f32,
+) -> f32 {
+    let total: f64 =
+        spec_pow.iter().map(|&p| f64::from(p.max(0.0))).sum();
+    if total <= 0.0 {
+        return 0.0;
+    }
+
+    let target = total * f64::from(fraction.clamp(0.0, 1.0));
+    let mut acc = 0.0f64;
+    for (i, &p) in spec_pow.iter().enumerate() {
+        acc += f64::from(p.max(0.0));
+        if acc >= target {
+            return i as f32 * bin_hz;
+        }
+    }
+    spec_pow.len().saturating_sub(1) as f32 * bin_hz
+}
+
+/// Geometric over arithmetic mean of the power spectrum: near `1` for
+/// white noise, near `0` for a pure tone.
+#[must_use]
+#[allow(clippy::cast_precision_loss)]
+pub fn spectral_flatness(spec_pow: &[f32]) -> f32 {
+    if spec_pow.is_empty() {
+        return 0.0;
+    }
+
+    let n = spec_pow.len() as f64;
+    let mut log_sum = 0.0f64;
+    let mut sum = 0.0f64;
+    for &p in spec_pow {
+        let p = f64::from(p.max(0.0)) + 1e-20;
+        log_sum += p.ln();
+        sum += p;
+    }
+
+    let geo = (log_sum / n).exp();
+    let arith = sum / n;
+    #[allow(clippy::cast_possible_truncation)]
+    let flatness = (geo / arith) as f32;
+    flatness.clamp(0.0, 1.0)
+}
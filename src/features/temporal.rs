@@ -0,0 +1,38 @@
+#[must_use]
+#[allow(
+    clippy::cast_precision_loss,
+    clippy::cast_possible_truncation
+)]
+pub fn rms(samples: &[f32]) -> f32 {
+    if samples.is_empty() {
+        return 0.0;
+    }
+    let sum_sq: f64 =
+        samples.iter().map(|&x| f64::from(x) * f64::from(x)).sum();
+    (sum_sq / samples.len() as f64).sqrt() as f32
+}
+
+/// Peak amplitude over RMS. A sine reads `sqrt(2)`; silence reads `0`.
+#[must_use]
+pub fn crest_factor(samples: &[f32]) -> f32 {
+    let level = rms(samples);
+    if level <= 0.0 {
+        return 0.0;
+    }
+    let peak = samples.iter().fold(0.0f32, |m, &x| m.max(x.abs()));
+    peak / level
+}
+
+/// Sign changes per sample.
+#[must_use]
+#[allow(clippy::cast_precision_loss)]
+pub fn zero_crossing_rate(samples: &[f32]) -> f32 {
+    if samples.len() < 2 {
+        return 0.0;
+    }
+    let crossings = samples
+        .windows(2)
+        .filter(|w| matches!(w, [a, b] if (*a >= 0.0) != (*b >= 0.0)))
+        .count();
+    crossings as f32 / samples.len().saturating_sub(1) as f32
+}
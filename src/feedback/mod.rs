@@ -0,0 +1,174 @@
+mod narrow;
+
+pub use narrow::{NarrowPeak, narrow_peaks};
+
+/// Thresholds of the feedback detector.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FeedbackParams {
+    /// Range searched for peaks, in Hz.
+    pub fmin_hz: f32,
+    pub fmax_hz: f32,
+    /// Quietest peak considered, in dB of FFT power.
+    pub min_db: f32,
+    /// How far a peak must stand above its neighbourhood, in dB.
+    pub min_prominence_db: f32,
+    /// How long a peak must last before it is flagged.
+    pub persist_s: f32,
+    /// How much it must have grown over that time, in dB.
+    pub growth_db: f32,
+    /// How long a peak may vanish before it is dropped.
+    pub release_s: f32,
+}
+
+impl FeedbackParams {
+    pub const DEFAULT: Self = Self {
+        fmin_hz: 60.0,
+        fmax_hz: 16_000.0,
+        min_db: -70.0,
+        min_prominence_db: 20.0,
+        persist_s: 0.3,
+        growth_db: 3.0,
+        release_s: 0.15,
+    };
+}
+
+impl Default for FeedbackParams {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+/// A peak flagged as feedback.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Howl {
+    pub hz: f32,
+    pub db: f32,
+    /// How long the peak has been tracked.
+    pub age_s: f32,
+}
+
+/// Most peaks tracked at once; the least prominent are skipped.
+const MAX_TRACKS: usize = 16;
+/// How far a tracked peak may drift between frames, in bins.
+const DRIFT_BINS: usize = 2;
+
+#[derive(Debug, Clone, Copy)]
+struct Track {
+    peak: NarrowPeak,
+    start_db: f32,
+    age_s: f32,
+    missed_s: f32,
+    seen: bool,
+    howling: bool,
+}
+
+/// Flags acoustic feedback: narrow peaks that persist and grow.
+///
+/// Feed it one full-resolution power spectrum per frame. Each narrow
+/// peak is tracked across frames. Once it has lasted `persist_s` and
+/// risen `growth_db` above its first level, it is reported until it
+/// disappears, even if it stops growing at the top.
+pub struct FeedbackDetector {
+    params: FeedbackParams,
+    peaks: Vec<NarrowPeak>,
+    tracks: Vec<Track>,
+}
+
+impl FeedbackDetector {
+    #[must_use]
+    pub const fn new(params: FeedbackParams) -> Self {
+        Self {
+            params,
+            peaks: Vec::new(),
+            tracks: Vec::new(),
+        }
+    }
+
+    #[must_use]
+    pub const fn params(&self) -> &FeedbackParams {
+        &self.params
+    }
+
+    /// Tracks the peaks of one frame and returns the loudest howl.
+    pub fn process(
+        &mut self,
+        spec_pow: &[f32],
+        bin_hz: f32,
+        dt: f32,
+    ) -> Option<Howl> {
+        let p = self.params;
+        narrow_peaks(spec_pow, bin_hz, &p, &mut self.peaks);
+        self.peaks.sort_by(|a, b| {
+            b.prominence_db.total_cmp(&a.prominence_db)
+        });
+        self.peaks.truncate(MAX_TRACKS);
+
+        for t in &mut self.tracks {
+            t.seen = false;
+        }
+        for i in 0..self.peaks.len() {
+            if let Some(&peak) = self.peaks.get(i) {
+                self.follow(peak, dt);
+            }
+        }
+        self.tracks.retain_mut(|t| {
+            if !t.seen {
+                t.missed_s += dt;
+            }
+            t.missed_s <= p.release_s
+        });
+
+        self.howl()
+    }
+
+    /// The loudest peak currently flagged.
+    #[must_use]
+    pub fn howl(&self) -> Option<Howl> {
+        self.tracks
+            .iter()
+            .filter(|t| t.howling && t.seen)
+            .max_by(|a, b| a.peak.db.total_cmp(&b.peak.db))
+            .map(|t| Howl {
+                hz: t.peak.hz,
+                db: t.peak.db,
+                age_s: t.age_s,
+            })
+    }
+
+    pub fn reset(&mut self) {
+        self.tracks.clear();
+    }
+
+    /// Extends the nearest unclaimed track within reach of `peak`, or
+    /// starts a new one.
+    fn follow(&mut self, peak: NarrowPeak, dt: f32) {
+        let p = self.params;
+        let near = self
+            .tracks
+            .iter_mut()
+            .filter(|t| {
+                !t.seen && t.peak.bin.abs_diff(peak.bin) <= DRIFT_BINS
+            })
+            .min_by_key(|t| t.peak.bin.abs_diff(peak.bin));
+
+        let Some(t) = near else {
+            self.tracks.push(Track {
+                peak,
+                start_db: peak.db,
+                age_s: 0.0,
+                missed_s: 0.0,
+                seen: true,
+                howling: false,
+            });
+            return;
+        };
+
+        t.peak = peak;
+        t.age_s += dt;
+        t.missed_s = 0.0;
+        t.seen = true;
+        t.howling = t.howling
+            || (t.age_s >= p.persist_s
+                && peak.db - t.start_db >= p.growth_db);
+    }
+}
@@ -0,0 +1,102 @@
+use super::FeedbackParams;
+
+/// Distance in bins from a peak to its neighbourhood, past the main
+/// lobe of a Hann window.
+const GUARD_BINS: usize = 3;
+/// Bins on each side averaged into the neighbourhood.
+const SPAN_BINS: usize = 6;
+
+/// A spectral peak that stands out from the bins around it.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct NarrowPeak {
+    pub bin: usize,
+    /// Frequency refined by parabolic interpolation of log power.
+    pub hz: f32,
+    pub db: f32,
+    /// Level above the mean power of the surrounding bins, in dB.
+    pub prominence_db: f32,
+}
+
+/// Local maxima of `spec_pow` between `fmin_hz` and `fmax_hz` at
+/// least `min_db` loud and `min_prominence_db` above their
+/// neighbourhood, written into `out`.
+#[allow(
+    clippy::cast_precision_loss,
+    clippy::cast_possible_truncation,
+    clippy::cast_sign_loss
+)]
+pub fn narrow_peaks(
+    spec_pow: &[f32],
+    bin_hz: f32,
+    params: &FeedbackParams,
+    out: &mut Vec<NarrowPeak>,
+) {
+    let FeedbackParams {
+        fmin_hz: fmin,
+        fmax_hz: fmax,
+        min_db,
+        min_prominence_db,
+        ..
+    } = *params;
+    out.clear();
+    if bin_hz <= 0.0 {
+        return;
+    }
+
+    let lo = ((fmin / bin_hz).floor() as usize).max(GUARD_BINS + 1);
+    let hi = ((fmax / bin_hz).ceil() as usize)
+        .min(spec_pow.len().saturating_sub(GUARD_BINS + 1));
+    for i in lo..hi {
+        let at = |k: usize| spec_pow.get(k).copied().unwrap_or(0.0);
+        let (a, b, c) =
+            (at(i.wrapping_sub(1)), at(i), at(i.saturating_add(1)));
+        if b <= a || b < c {
+            continue;
+        }
+
+        let db = to_db(b);
+        if db < min_db {
+            continue;
+        }
+        let prominence_db = db - to_db(neighbourhood(spec_pow, i));
+        if prominence_db < min_prominence_db {
+            continue;
+        }
+
+        let (la, lb, lc) = (to_db(a), db, to_db(c));
+        let den = la - 2.0f32.mul_add(lb, -lc);
+        let delta = if den.abs() > 1e-6 {
+            (0.5 * (la - lc) / den).clamp(-0.5, 0.5)
+        } else {
+            0.0
+        };
+
+        out.push(NarrowPeak {
+            bin: i,
+            hz: (i as f32 + delta) * bin_hz,
+            db,
+            prominence_db,
+        });
+    }
+}
+
+/// Mean power of the bins around `i`, skipping the guard bins.
+#[allow(clippy::cast_precision_loss)]
+fn neighbourhood(spec_pow: &[f32], i: usize) -> f32 {
+    let below = i.saturating_sub(GUARD_BINS + SPAN_BINS - 1)
+        ..i.saturating_sub(GUARD_BINS - 1);
+    let above = i.saturating_add(GUARD_BINS)
+        ..i.saturating_add(GUARD_BINS + SPAN_BINS);
+    let (sum, n) = [below, above]
+        .into_iter()
+        .filter_map(|r| spec_pow.get(r))
+        .flatten()
+        .fold((0.0f32, 0usize), |(s, n), &p| {
+            (s + p, n.saturating_add(1))
+        });
+    if n == 0 { 0.0 } else { sum / n as f32 }
+}
+
+fn to_db(p: f32) -> f32 {
+    10.0 * (p.max(0.0) + 1e-20).log10()
+}
@@ -1,10 +1,26 @@
 mod filters;
 mod mel_bins;
+mod notes;
+
+use serde::Deserialize;
 
 use crate::dsp::hz_to_mel;
 
 pub use filters::create_filters;
 pub use mel_bins::calculate_bin_points;
+pub use notes::{
+    NoteFilterbankParams, PIANO_HIGH_MIDI, PIANO_LOW_MIDI,
+    build_note_filterbank, note_hz,
+};
+
+/// How bands are spread over frequency: evenly on the mel scale across
+/// the terminal width, or one per semitone over a fixed note range.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FilterbankMode {
+    Mel,
+    Piano,
+}
 
 #[derive(Clone)]
 pub struct Tri {
@@ -12,7 +28,7 @@ pub struct Tri {
     pub center_hz: f32,
 }
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub struct FilterbankParams {
     pub sr: f32,
     pub fft_size: usize,
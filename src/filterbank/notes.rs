@@ -0,0 +1,92 @@
+use super::Tri;
+
+/// MIDI numbers of the lowest and highest keys of an 88-key piano.
+pub const PIANO_LOW_MIDI: i32 = 21;
+pub const PIANO_HIGH_MIDI: i32 = 108;
+
+#[derive(Debug, Clone, Copy)]
+pub struct NoteFilterbankParams {
+    pub sr: f32,
+    pub fft_size: usize,
+    /// Inclusive MIDI note range, one band per semitone.
+    pub low_midi: i32,
+    pub high_midi: i32,
+    pub a4_hz: f32,
+}
+
+impl NoteFilterbankParams {
+    #[must_use]
+    #[allow(clippy::cast_sign_loss, clippy::arithmetic_side_effects)]
+    pub const fn bands(&self) -> usize {
+        if self.high_midi < self.low_midi {
+            0
+        } else {
+            (self.high_midi - self.low_midi + 1) as usize
+        }
+    }
+}
+
+/// Frequency of MIDI note `midi` with A4 at `a4_hz`.
+#[must_use]
+#[allow(clippy::cast_precision_loss, clippy::arithmetic_side_effects)]
+pub fn note_hz(midi: i32, a4_hz: f32) -> f32 {
+    a4_hz * ((midi - 69) as f32 / 12.0).exp2()
+}
+
+/// One band per equal-tempered semitone, centered exactly on each
+/// note.
+///
+/// Each band is a triangle on a semitone scale reaching zero at the
+/// neighbouring notes, so adjacent bands cross at the quarter tone.
+/// Where a semitone is narrower than an FFT bin, the band instead
+/// interpolates between the two bins around its center.
+#[must_use]
+#[allow(
+    clippy::cast_precision_loss,
+    clippy::cast_possible_truncation,
+    clippy::cast_sign_loss,
+    clippy::arithmetic_side_effects
+)]
+pub fn build_note_filterbank(
+    params: NoteFilterbankParams,
+) -> Vec<Tri> {
+    let half = params.fft_size / 2;
+    let hz_per_bin = params.sr / params.fft_size.max(1) as f32;
+    let last = half.saturating_sub(1);
+
+    (params.low_midi..=params.high_midi)
+        .map(|midi| {
+            let center_hz = note_hz(midi, params.a4_hz);
+            let lo_hz = note_hz(midi - 1, params.a4_hz);
+            let hi_hz = note_hz(midi + 1, params.a4_hz);
+
+            let first = ((lo_hz / hz_per_bin).ceil() as usize).max(1);
+            let end =
+                ((hi_hz / hz_per_bin).floor() as usize).min(last);
+            let mut taps: Vec<(usize, f32)> = (first..=end)
+                .filter_map(|i| {
+                    let semis = 12.0
+                        * (i as f32 * hz_per_bin / center_hz).log2();
+                    let w = 1.0 - semis.abs();
+                    (w > 0.0).then_some((i, w))
+                })
+                .collect();
+
+            if taps.len() < 2 {
+                let pos =
+                    (center_hz / hz_per_bin).clamp(1.0, last as f32);
+                let below = (pos.floor() as usize).min(last);
+                let above = (below + 1).min(last);
+                let frac = pos - below as f32;
+                taps = vec![(below, 1.0 - frac), (above, frac)];
+            }
+
+            let inv_sumw = 1.0
+                / taps.iter().map(|(_, w)| *w).sum::<f32>().max(1e-6);
+            for t in &mut taps {
+                t.1 *= inv_sumw;
+            }
+            Tri { taps, center_hz }
+        })
+        .collect()
+}
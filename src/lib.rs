@@ -1,21 +1,63 @@
 pub mod analyzer;
 pub mod audio;
+pub mod beat;
 pub mod buffer;
+pub mod chroma;
 pub mod config;
+pub mod distortion;
 pub mod dsp;
+pub mod features;
+pub mod feedback;
 pub mod filterbank;
+pub mod loudness;
+pub mod ltas;
+pub mod multires;
+pub mod noise;
+pub mod partials;
 pub mod render;
+pub mod spl;
+pub mod stereo;
+pub mod tuner;
 pub mod utils;
+pub mod vad;
 
 pub use analyzer::SpectrumAnalyzer;
 pub use audio::{
     AudioController, AudioMode, best_config_for, build_stream,
     pick_input_device,
 };
+pub use beat::{BeatEvent, BeatTracker};
 pub use buffer::SharedBuf;
+pub use chroma::{Chromagram, Key, KeyEstimate, estimate_key};
+pub use distortion::{
+    DistortionInput, DistortionMeter, DistortionParams,
+    DistortionReading,
+};
 pub use dsp::{
     a_weighting, ema_tc, hann, hz_to_mel, mel_to_hz,
     prepare_fft_input_inplace,
 };
+pub use features::{FeatureExtractor, SpectralFeatures};
+pub use feedback::{FeedbackDetector, FeedbackParams, Howl};
 pub use filterbank::{FilterbankParams, Tri, build_filterbank};
-pub use render::{Layout, draw_blocks_vertical, layout_for};
+pub use loudness::{ChannelLayout, LoudnessMeter, LoudnessReading};
+pub use ltas::{Ltas, Snapshot, SnapshotBand};
+pub use multires::{
+    MultiResParams, ResolutionBand, fold_power, plan_resolutions,
+};
+pub use noise::{NoiseCalibrator, NoiseProfile};
+pub use partials::{
+    Interpolation, Partial, PartialParams, Reassigner,
+    strongest_partials,
+};
+pub use render::{
+    Layout, PeakCaps, draw_blocks_vertical, draw_labels,
+    layout_centered, layout_for,
+};
+pub use spl::{
+    ExposureStandard, FrequencyWeighting, SplMeter, SplParams,
+    SplReading, TimeWeighting,
+};
+pub use stereo::{StereoMeter, StereoSpectra};
+pub use tuner::{Temperament, Tuner, Tuning};
+pub use vad::{VadFeatures, VadInput, VadParams, VoiceDetector};
@@ -0,0 +1,170 @@
+pub const ABSOLUTE_GATE_LUFS: f32 = -70.0;
+const RELATIVE_GATE_LU: f32 = -10.0;
+const RANGE_GATE_LU: f32 = -20.0;
+const RANGE_LOW: f32 = 0.10;
+const RANGE_HIGH: f32 = 0.95;
+/// Width of a histogram bin in LU.
+const BIN_LU: f32 = 0.1;
+/// Histogram bins from the absolute gate up to +5 LUFS. Louder
+/// blocks share the top one.
+const BINS: usize = 750;
+
+/// Loudness of a channel-weighted mean square, `-inf` for silence.
+#[must_use]
+#[allow(clippy::cast_possible_truncation)]
+pub fn energy_to_lufs(energy: f64) -> f32 {
+    if energy <= 0.0 {
+        return f32::NEG_INFINITY;
+    }
+    10.0f64.mul_add(energy.log10(), -0.691) as f32
+}
+
+#[must_use]
+pub fn lufs_to_energy(lufs: f32) -> f64 {
+    10f64.powf((f64::from(lufs) + 0.691) / 10.0)
+}
+
+/// Integrated loudness of 400 ms block energies with the absolute
+/// and relative gates of BS.1770-4.
+#[must_use]
+pub fn gated_loudness(blocks: &[f64]) -> f32 {
+    LoudnessHistogram::from_blocks(blocks).integrated()
+}
+
+/// Loudness range (EBU Tech 3342) of 3 s block energies.
+#[must_use]
+pub fn loudness_range(blocks: &[f64]) -> f32 {
+    LoudnessHistogram::from_blocks(blocks).range()
+}
+
+/// Block energies counted in 0.1 LU bins from the absolute gate up
+/// to +5 LUFS, as libebur128 does.
+///
+/// Each bin keeps the sum of its energies as well as the count, so
+/// gated means are exact and only the gate edges and the range
+/// percentiles are quantized. Memory and the cost of a reading stay
+/// the same however long the meter runs.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LoudnessHistogram {
+    counts: Vec<u64>,
+    energy: Vec<f64>,
+}
+
+impl Default for LoudnessHistogram {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl LoudnessHistogram {
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            counts: vec![0; BINS],
+            energy: vec![0.0; BINS],
+        }
+    }
+
+    fn from_blocks(blocks: &[f64]) -> Self {
+        let mut hist = Self::new();
+        for &e in blocks {
+            hist.add(e);
+        }
+        hist
+    }
+
+    /// Counts one block; blocks at or below the absolute gate are
+    /// dropped.
+    #[allow(clippy::arithmetic_side_effects)]
+    pub fn add(&mut self, energy: f64) {
+        let lufs = energy_to_lufs(energy);
+        if lufs.is_nan() || lufs <= ABSOLUTE_GATE_LUFS {
+            return;
+        }
+        let i = bin_index(lufs);
+        if let (Some(n), Some(e)) =
+            (self.counts.get_mut(i), self.energy.get_mut(i))
+        {
+            *n += 1;
+            *e += energy;
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.counts.fill(0);
+        self.energy.fill(0.0);
+    }
+
+    /// Integrated loudness with the absolute and relative gates of
+    /// BS.1770-4, `-inf` until a block passes the absolute gate.
+    #[must_use]
+    pub fn integrated(&self) -> f32 {
+        let Some(mean) = self.mean_from(0) else {
+            return f32::NEG_INFINITY;
+        };
+        let gate = energy_to_lufs(mean) + RELATIVE_GATE_LU;
+        self.mean_from(bin_index(gate))
+            .map_or(f32::NEG_INFINITY, energy_to_lufs)
+    }
+
+    /// Loudness range per EBU Tech 3342: the spread between the 10th
+    /// and 95th percentiles of the blocks above the relative gate.
+    #[must_use]
+    #[allow(
+        clippy::cast_precision_loss,
+        clippy::cast_possible_truncation,
+        clippy::cast_sign_loss,
+        clippy::arithmetic_side_effects
+    )]
+    pub fn range(&self) -> f32 {
+        let Some(mean) = self.mean_from(0) else {
+            return 0.0;
+        };
+        let start = bin_index(energy_to_lufs(mean) + RANGE_GATE_LU);
+        let counts = self.counts.get(start..).unwrap_or_default();
+        let total = counts.iter().sum::<u64>();
+        if total == 0 {
+            return 0.0;
+        }
+
+        let last = (total - 1) as f32;
+        let lo =
+            percentile(counts, (last * RANGE_LOW).round() as u64);
+        let hi =
+            percentile(counts, (last * RANGE_HIGH).round() as u64);
+        hi.saturating_sub(lo) as f32 * BIN_LU
+    }
+
+    /// Mean energy of the blocks in bin `start` and above.
+    #[allow(clippy::cast_precision_loss)]
+    fn mean_from(&self, start: usize) -> Option<f64> {
+        let count = self.counts.get(start..)?.iter().sum::<u64>();
+        let sum = self.energy.get(start..)?.iter().sum::<f64>();
+        (count > 0).then(|| sum / count as f64)
+    }
+}
+
+/// Bin holding `lufs`, clamped to the histogram.
+#[allow(
+    clippy::cast_possible_truncation,
+    clippy::cast_sign_loss,
+    clippy::arithmetic_side_effects
+)]
+fn bin_index(lufs: f32) -> usize {
+    let i = ((lufs - ABSOLUTE_GATE_LUFS) / BIN_LU).floor().max(0.0);
+    (i as usize).min(BINS - 1)
+}
+
+/// Index, relative to the start of `counts`, of the bin holding
+/// the block of rank `rank`.
+#[allow(clippy::arithmetic_side_effects)]
+fn percentile(counts: &[u64], rank: u64) -> usize {
+    let mut seen = 0u64;
+    for (i, &n) in counts.iter().enumerate() {
+        seen += n;
+        if seen > rank {
+            return i;
+        }
+    }
+    counts.len().saturating_sub(1)
+}
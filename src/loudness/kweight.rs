@@ -0,0 +1,64 @@
+use crate::dsp::Biquad;
+
+/// The two-stage K-weighting pre-filter of ITU-R BS.1770: a high
+/// shelf modelling the head, followed by the RLB high-pass.
+pub struct KWeighting {
+    shelf: Biquad,
+    highpass: Biquad,
+}
+
+impl KWeighting {
+    #[must_use]
+    pub fn new(sample_rate: f32) -> Self {
+        let fs = f64::from(sample_rate.max(1.0));
+
+        Self {
+            shelf: shelf(fs),
+            highpass: highpass(fs),
+        }
+    }
+
+    #[inline]
+    pub fn process(&mut self, x: f32) -> f64 {
+        self.highpass.process(self.shelf.process(f64::from(x)))
+    }
+}
+
+// Coefficients re-derived for any rate from the 48 kHz reference
+// filter, following the analog prototypes used by libebur128.
+fn shelf(fs: f64) -> Biquad {
+    const F0: f64 = 1_681.974_450_955_533;
+    const GAIN_DB: f64 = 3.999_843_853_973_347;
+    const Q: f64 = 0.707_175_236_955_419_6;
+
+    let k = (std::f64::consts::PI * F0 / fs).tan();
+    let (k2, kq) = (k * k, k / Q);
+    let vh = 10f64.powf(GAIN_DB / 20.0);
+    let vb = vh.powf(0.499_666_774_154_541_6);
+    let a0 = 1.0 + kq + k2;
+
+    Biquad::from_coeffs(
+        [
+            vb.mul_add(kq, vh + k2) / a0,
+            2.0 * (k2 - vh) / a0,
+            vb.mul_add(-kq, vh + k2) / a0,
+        ],
+        2.0 * (k2 - 1.0) / a0,
+        (1.0 - kq + k2) / a0,
+    )
+}
+
+fn highpass(fs: f64) -> Biquad {
+    const F0: f64 = 38.135_470_876_024_44;
+    const Q: f64 = 0.500_327_037_323_877_3;
+
+    let k = (std::f64::consts::PI * F0 / fs).tan();
+    let (k2, kq) = (k * k, k / Q);
+    let a0 = 1.0 + kq + k2;
+
+    Biquad::from_coeffs(
+        [1.0, -2.0, 1.0],
+        2.0 * (k2 - 1.0) / a0,
+        (1.0 - kq + k2) / a0,
+    )
+}
@@ -0,0 +1,158 @@
+mod gating;
+mod kweight;
+mod true_peak;
+
+pub use gating::{
+    ABSOLUTE_GATE_LUFS, LoudnessHistogram, energy_to_lufs,
+    gated_loudness, loudness_range, lufs_to_energy,
+};
+pub use kweight::KWeighting;
+pub use true_peak::TruePeak;
+
+use std::collections::VecDeque;
+
+const SUBBLOCK_S: f32 = 0.1;
+const MOMENTARY_SUBBLOCKS: usize = 4;
+const SHORT_TERM_SUBBLOCKS: usize = 30;
+
+/// How the mono sample stream maps onto BS.1770 channels.
+///
+/// Lookas downmixes capture to mono, so `DualMono` treats the stream
+/// as identical left and right channels, which reads a stereo
+/// programme the way a stereo meter would.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChannelLayout {
+    Mono,
+    DualMono,
+}
+
+impl ChannelLayout {
+    const fn gain(self) -> f64 {
+        match self {
+            Self::Mono => 1.0,
+            Self::DualMono => 2.0,
+        }
+    }
+}
+
+/// Loudness values in LUFS/LU/dBTP. Levels are `-inf` until enough
+/// signal has been measured.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LoudnessReading {
+    pub momentary: f32,
+    pub short_term: f32,
+    pub integrated: f32,
+    pub range: f32,
+    pub true_peak: f32,
+}
+
+impl LoudnessReading {
+    const EMPTY: Self = Self {
+        momentary: f32::NEG_INFINITY,
+        short_term: f32::NEG_INFINITY,
+        integrated: f32::NEG_INFINITY,
+        range: 0.0,
+        true_peak: f32::NEG_INFINITY,
+    };
+}
+
+pub struct LoudnessMeter {
+    sample_rate: f32,
+    layout: ChannelLayout,
+    filter: KWeighting,
+    true_peak: TruePeak,
+    subblock_len: usize,
+    acc: f64,
+    acc_len: usize,
+    recent: VecDeque<f64>,
+    blocks: LoudnessHistogram,
+    short_blocks: LoudnessHistogram,
+    reading: LoudnessReading,
+}
+
+impl LoudnessMeter {
+    #[must_use]
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    pub fn new(sample_rate: f32, layout: ChannelLayout) -> Self {
+        Self {
+            sample_rate,
+            layout,
+            filter: KWeighting::new(sample_rate),
+            true_peak: TruePeak::new(),
+            subblock_len: (sample_rate * SUBBLOCK_S).round().max(1.0)
+                as usize,
+            acc: 0.0,
+            acc_len: 0,
+            recent: VecDeque::with_capacity(SHORT_TERM_SUBBLOCKS),
+            blocks: LoudnessHistogram::new(),
+            short_blocks: LoudnessHistogram::new(),
+            reading: LoudnessReading::EMPTY,
+        }
+    }
+
+    #[must_use]
+    pub const fn sample_rate(&self) -> f32 {
+        self.sample_rate
+    }
+
+    #[must_use]
+    pub const fn reading(&self) -> LoudnessReading {
+        self.reading
+    }
+
+    pub fn reset(&mut self) {
+        *self = Self::new(self.sample_rate, self.layout);
+    }
+
+    #[allow(clippy::arithmetic_side_effects)]
+    pub fn process(&mut self, samples: &[f32]) {
+        for &x in samples {
+            let y = self.filter.process(x);
+            self.true_peak.process(x);
+            self.acc = y.mul_add(y, self.acc);
+            self.acc_len += 1;
+
+            if self.acc_len >= self.subblock_len {
+                self.finish_subblock();
+            }
+        }
+
+        self.reading.true_peak =
+            20.0 * self.true_peak.peak().max(1e-12).log10();
+    }
+
+    #[allow(clippy::cast_precision_loss)]
+    fn finish_subblock(&mut self) {
+        let energy = self.layout.gain() * self.acc
+            / self.acc_len.max(1) as f64;
+        self.acc = 0.0;
+        self.acc_len = 0;
+
+        if self.recent.len() == SHORT_TERM_SUBBLOCKS {
+            self.recent.pop_front();
+        }
+        self.recent.push_back(energy);
+
+        if let Some(block) = self.window_mean(MOMENTARY_SUBBLOCKS) {
+            self.blocks.add(block);
+            self.reading.momentary = energy_to_lufs(block);
+            self.reading.integrated = self.blocks.integrated();
+        }
+
+        if let Some(block) = self.window_mean(SHORT_TERM_SUBBLOCKS) {
+            self.short_blocks.add(block);
+            self.reading.short_term = energy_to_lufs(block);
+            self.reading.range = self.short_blocks.range();
+        }
+    }
+
+    #[allow(clippy::cast_precision_loss)]
+    fn window_mean(&self, n: usize) -> Option<f64> {
+        if self.recent.len() < n {
+            return None;
+        }
+
+        let sum = self.recent.iter().rev().take(n).sum::<f64>();
+        Some(sum / n as f64)
+    }
+}
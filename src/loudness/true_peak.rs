@@ -0,0 +1,99 @@
+const OVERSAMPLE: usize = 4;
+const TAPS_PER_PHASE: usize = 12;
+
+/// Inter-sample peak estimate from 4x polyphase oversampling, as
+/// described in ITU-R BS.1770-4 Annex 2.
+pub struct TruePeak {
+    phases: [[f32; TAPS_PER_PHASE]; OVERSAMPLE],
+    history: [f32; 2 * TAPS_PER_PHASE],
+    pos: usize,
+    peak: f32,
+}
+
+impl Default for TruePeak {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TruePeak {
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            phases: design_phases(),
+            history: [0.0; 2 * TAPS_PER_PHASE],
+            pos: 0,
+            peak: 0.0,
+        }
+    }
+
+    #[allow(clippy::arithmetic_side_effects)]
+    pub fn process(&mut self, x: f32) {
+        // Each sample is written twice so the newest TAPS_PER_PHASE
+        // samples are always one contiguous, oldest-first slice.
+        self.pos = (self.pos + 1) % TAPS_PER_PHASE;
+        for idx in [self.pos, self.pos + TAPS_PER_PHASE] {
+            if let Some(slot) = self.history.get_mut(idx) {
+                *slot = x;
+            }
+        }
+        let Some(window) = self
+            .history
+            .get(self.pos + 1..=self.pos + TAPS_PER_PHASE)
+        else {
+            return;
+        };
+
+        let mut peak = self.peak.max(x.abs());
+        for phase in &self.phases {
+            let acc = phase
+                .iter()
+                .rev()
+                .zip(window)
+                .fold(0.0f32, |acc, (&h, &s)| h.mul_add(s, acc));
+            peak = peak.max(acc.abs());
+        }
+        self.peak = peak;
+    }
+
+    /// Highest linear peak seen so far.
+    #[must_use]
+    pub const fn peak(&self) -> f32 {
+        self.peak
+    }
+}
+
+#[allow(clippy::cast_precision_loss, clippy::arithmetic_side_effects)]
+fn design_phases() -> [[f32; TAPS_PER_PHASE]; OVERSAMPLE] {
+    use std::f32::consts::PI;
+
+    let len = OVERSAMPLE * TAPS_PER_PHASE;
+    let center = (len - 1) as f32 / 2.0;
+    let mut phases = [[0.0f32; TAPS_PER_PHASE]; OVERSAMPLE];
+
+    for (p, phase) in phases.iter_mut().enumerate() {
+        for (k, tap) in phase.iter_mut().enumerate() {
+            let n = (p + OVERSAMPLE * k) as f32;
+            let x = (n - center) / OVERSAMPLE as f32;
+            let sinc = if x.abs() < 1e-6 {
+                1.0
+            } else {
+                (PI * x).sin() / (PI * x)
+            };
+            let window = 0.5f32.mul_add(
+                -(2.0 * PI * n / (len - 1) as f32).cos(),
+                0.5,
+            );
+            *tap = sinc * window;
+        }
+
+        let sum = phase.iter().sum::<f32>();
+        if sum.abs() > f32::EPSILON {
+            for tap in phase.iter_mut() {
+                *tap /= sum;
+            }
+        }
+    }
+
+    phases
+}
@@ -0,0 +1,143 @@
+mod snapshot;
+
+pub use snapshot::{Snapshot, SnapshotBand};
+
+use crate::{analyzer::band_level_db, filterbank::Tri};
+
+/// Long-term average spectrum: per-bin power averaged over a sliding
+/// window, or over everything fed in since the last reset.
+///
+/// Averaging happens on FFT bins rather than bands, so band levels
+/// can be taken with whatever filterbank is current.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Ltas {
+    window_s: Option<f32>,
+    power: Vec<f32>,
+    elapsed_s: f32,
+}
+
+impl Ltas {
+    /// `window_s` of `None` averages the whole session.
+    #[must_use]
+    pub const fn new(window_s: Option<f32>) -> Self {
+        Self {
+            window_s,
+            power: Vec::new(),
+            elapsed_s: 0.0,
+        }
+    }
+
+    #[must_use]
+    pub const fn window_s(&self) -> Option<f32> {
+        self.window_s
+    }
+
+    /// Changes the window, keeping what has been averaged so far.
+    pub const fn set_window(&mut self, window_s: Option<f32>) {
+        self.window_s = window_s;
+    }
+
+    /// Adds one power spectrum covering `dt_s` seconds. A spectrum of
+    /// another size restarts the average.
+    pub fn process(&mut self, spec_pow: &[f32], dt_s: f32) {
+        if dt_s <= 0.0 {
+            return;
+        }
+        if self.power.len() != spec_pow.len() {
+            self.power.clear();
+            self.power.extend_from_slice(spec_pow);
+            self.elapsed_s = dt_s;
+            return;
+        }
+
+        self.elapsed_s += dt_s;
+        let mean = dt_s / self.elapsed_s;
+        let weight = self
+            .window_s
+            .map_or(mean, |tau| mean.max(1.0 - (-dt_s / tau).exp()));
+        for (p, &x) in self.power.iter_mut().zip(spec_pow) {
+            *p = (x - *p).mul_add(weight, *p);
+        }
+    }
+
+    /// Averaged power per FFT bin, empty before the first spectrum.
+    #[must_use]
+    pub fn power(&self) -> &[f32] {
+        &self.power
+    }
+
+    /// Seconds of audio in the average.
+    #[must_use]
+    pub const fn elapsed_s(&self) -> f32 {
+        self.elapsed_s
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.power.is_empty()
+    }
+
+    pub fn reset(&mut self) {
+        self.power.clear();
+        self.elapsed_s = 0.0;
+    }
+
+    /// Level of each band of the average in dBFS.
+    pub fn levels_db(&self, filters: &[Tri], out: &mut Vec<f32>) {
+        out.clear();
+        if self.power.is_empty() {
+            return;
+        }
+        out.extend(
+            filters.iter().map(|tri| band_level_db(tri, &self.power)),
+        );
+    }
+}
+
+/// Octave band centers the readout groups deltas into.
+const OCTAVE_CENTERS_HZ: [f32; 10] = [
+    31.5, 63.0, 125.0, 250.0, 500.0, 1000.0, 2000.0, 4000.0, 8000.0,
+    16_000.0,
+];
+
+/// Averages `live - reference` over the bands nearest each octave
+/// center, skipping bands without a reference. Writes
+/// `(center_hz, delta_db)` for every octave that has bands.
+pub fn octave_deltas(
+    centers_hz: &[f32],
+    live_db: &[f32],
+    reference_db: &[Option<f32>],
+    out: &mut Vec<(f32, f32)>,
+) {
+    out.clear();
+    let mut sums = [(0.0f32, 0u16); OCTAVE_CENTERS_HZ.len()];
+    let bands = centers_hz.iter().zip(live_db).zip(reference_db);
+    for ((&hz, &live), reference) in bands {
+        let (Some(reference), Some(slot)) = (
+            reference,
+            nearest_octave(hz).and_then(|i| sums.get_mut(i)),
+        ) else {
+            continue;
+        };
+        slot.0 += live - reference;
+        slot.1 = slot.1.saturating_add(1);
+    }
+
+    for (&hz, &(sum, count)) in OCTAVE_CENTERS_HZ.iter().zip(&sums) {
+        if count > 0 {
+            out.push((hz, sum / f32::from(count)));
+        }
+    }
+}
+
+fn nearest_octave(hz: f32) -> Option<usize> {
+    if !hz.is_finite() || hz <= 0.0 {
+        return None;
+    }
+    OCTAVE_CENTERS_HZ
+        .iter()
+        .map(|&c| (hz / c).log2().abs())
+        .enumerate()
+        .min_by(|a, b| a.1.total_cmp(&b.1))
+        .map(|(i, _)| i)
+}
@@ -0,0 +1,135 @@
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use crate::{analyzer::band_level_db, filterbank::Tri};
+
+/// How far past its outermost bands a snapshot is still read, in
+/// octaves.
+const EDGE_OCTAVES: f32 = 1.0 / 3.0;
+
+/// A frozen spectrum to compare the live one against.
+///
+/// Levels are stored per band center rather than per bar, so a
+/// snapshot can be read back with any filterbank, FFT size or
+/// terminal width.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Snapshot {
+    pub bands: Vec<SnapshotBand>,
+}
+
+/// Level of one band in dBFS at its center frequency.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct SnapshotBand {
+    pub hz: f32,
+    pub db: f32,
+}
+
+impl Snapshot {
+    /// Takes the level of every band of a power spectrum.
+    #[must_use]
+    pub fn from_spectrum(filters: &[Tri], spec_pow: &[f32]) -> Self {
+        Self::from_bands(filters.iter().map(|tri| SnapshotBand {
+            hz: tri.center_hz,
+            db: band_level_db(tri, spec_pow),
+        }))
+    }
+
+    /// Builds a snapshot from bands in any order, dropping any that
+    /// are not finite or sit at or below 0 Hz.
+    pub fn from_bands<I: IntoIterator<Item = SnapshotBand>>(
+        bands: I,
+    ) -> Self {
+        let mut bands: Vec<SnapshotBand> = bands
+            .into_iter()
+            .filter(|b| {
+                b.hz.is_finite() && b.hz > 0.0 && b.db.is_finite()
+            })
+            .collect();
+        bands.sort_by(|a, b| a.hz.total_cmp(&b.hz));
+        Self { bands }
+    }
+
+    /// Level at `hz`, interpolated over log frequency. `None` well
+    /// outside the bands the snapshot covers.
+    #[must_use]
+    pub fn level_at(&self, hz: f32) -> Option<f32> {
+        let first = self.bands.first()?;
+        let last = self.bands.last()?;
+        if !hz.is_finite() || hz <= 0.0 {
+            return None;
+        }
+        if hz <= first.hz {
+            return ((first.hz / hz).log2() <= EDGE_OCTAVES)
+                .then_some(first.db);
+        }
+        if hz >= last.hz {
+            return ((hz / last.hz).log2() <= EDGE_OCTAVES)
+                .then_some(last.db);
+        }
+
+        let upper = self.bands.partition_point(|b| b.hz < hz);
+        let hi = self.bands.get(upper)?;
+        let lo = self.bands.get(upper.checked_sub(1)?)?;
+        let span = (hi.hz / lo.hz).log2();
+        if span <= 0.0 {
+            return Some(hi.db);
+        }
+        let t = (hz / lo.hz).log2() / span;
+        Some((hi.db - lo.db).mul_add(t, lo.db))
+    }
+
+    pub fn path() -> Result<PathBuf> {
+        Ok(dirs::config_dir()
+            .context("failed to resolve config directory")?
+            .join("lookas-snapshot.toml"))
+    }
+
+    /// Reads a snapshot saved as JSON when the path ends in `.json`,
+    /// and as TOML otherwise.
+    pub fn load_from(path: &Path) -> Result<Self> {
+        let contents =
+            fs::read_to_string(path).with_context(|| {
+                format!("failed to read snapshot: {}", path.display())
+            })?;
+
+        let snapshot: Self = if is_json(path) {
+            serde_json::from_str(&contents).with_context(|| {
+                format!("invalid snapshot in {}", path.display())
+            })?
+        } else {
+            toml::from_str(&contents).with_context(|| {
+                format!("invalid snapshot in {}", path.display())
+            })?
+        };
+        Ok(Self::from_bands(snapshot.bands))
+    }
+
+    pub fn save_to(&self, path: &Path) -> Result<()> {
+        let contents = if is_json(path) {
+            serde_json::to_string_pretty(self)
+                .context("failed to serialise snapshot")?
+        } else {
+            toml::to_string(self)
+                .context("failed to serialise snapshot")?
+        };
+
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir).with_context(|| {
+                format!("failed to create {}", dir.display())
+            })?;
+        }
+
+        fs::write(path, contents).with_context(|| {
+            format!("failed to write snapshot: {}", path.display())
+        })
+    }
+}
+
+fn is_json(path: &Path) -> bool {
+    path.extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("json"))
+}
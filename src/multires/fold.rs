@@ -0,0 +1,28 @@
+use super::ResolutionBand;
+
+/// Spreads the power of a shorter window over the bins of the output
+/// grid it covers. Each source bin is divided by the number of output
+/// bins it maps onto, so total power in the band is preserved.
+#[allow(clippy::cast_precision_loss, clippy::arithmetic_side_effects)]
+pub fn fold_power(
+    dst: &mut [f32],
+    src: &[f32],
+    band: &ResolutionBand,
+    dst_fft_size: usize,
+) {
+    let ratio = (dst_fft_size / band.fft_size.max(1)).max(1);
+    let scale = 1.0 / ratio as f32;
+    let half_ratio = ratio / 2;
+
+    let end = band.end_bin.min(dst.len());
+    let Some(slots) = dst.get_mut(band.start_bin.min(end)..end)
+    else {
+        return;
+    };
+
+    for (k, slot) in (band.start_bin..).zip(slots.iter_mut()) {
+        let m = (k + half_ratio) / ratio;
+        let pow = src.get(m).or_else(|| src.last()).copied();
+        *slot = pow.unwrap_or(0.0) * scale;
+    }
+}
@@ -0,0 +1,5 @@
+mod fold;
+mod plan;
+
+pub use fold::fold_power;
+pub use plan::{MultiResParams, ResolutionBand, plan_resolutions};
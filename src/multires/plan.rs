@@ -0,0 +1,60 @@
+/// Smallest window any resolution level is allowed to shrink to.
+pub const MIN_LEVEL_FFT_SIZE: usize = 256;
+
+/// A shorter window takes over once its bin spacing is at most
+/// 1/16 of the frequency it covers (roughly one semitone).
+const CROSSOVER_BINS: usize = 16;
+
+#[derive(Debug, Clone, Copy)]
+pub struct MultiResParams {
+    pub fft_size: usize,
+    pub levels: usize,
+}
+
+/// A slice of the output spectrum grid (`fft_size / 2` bins) that
+/// is filled from one window length.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ResolutionBand {
+    pub fft_size: usize,
+    pub start_bin: usize,
+    pub end_bin: usize,
+}
+
+#[must_use]
+#[allow(clippy::arithmetic_side_effects)]
+pub fn plan_resolutions(
+    params: MultiResParams,
+) -> Vec<ResolutionBand> {
+    let half = params.fft_size / 2;
+    let mut bands = Vec::with_capacity(params.levels);
+    let mut start_bin = 0;
+
+    for level in 0..params.levels.max(1) {
+        let size = params.fft_size >> level;
+        let next = size / 2;
+        let last = level + 1 >= params.levels
+            || next < MIN_LEVEL_FFT_SIZE
+            || size < MIN_LEVEL_FFT_SIZE;
+
+        let end_bin = if last {
+            half
+        } else {
+            (CROSSOVER_BINS << (level + 1)).clamp(start_bin, half)
+        };
+
+        if end_bin > start_bin {
+            bands.push(ResolutionBand {
+                fft_size: size,
+                start_bin,
+                end_bin,
+            });
+        }
+
+        start_bin = end_bin;
+        if last {
+            break;
+        }
+    }
+
+    bands
+}
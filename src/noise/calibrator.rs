@@ -0,0 +1,81 @@
+use super::NoiseProfile;
+
+/// Averages per-bin power and broadband power over a fixed window.
+pub struct NoiseCalibrator {
+    duration_s: f32,
+    elapsed_s: f32,
+    sum: Vec<f32>,
+    power_sum: f32,
+    frames: u32,
+}
+
+impl NoiseCalibrator {
+    #[must_use]
+    pub fn new(duration_s: f32) -> Self {
+        Self {
+            duration_s: duration_s.max(0.0),
+            elapsed_s: 0.0,
+            sum: Vec::new(),
+            power_sum: 0.0,
+            frames: 0,
+        }
+    }
+
+    /// Adds one frame and returns `true` once the window is full. A
+    /// frame of a different length starts the window over.
+    pub fn push(
+        &mut self,
+        spec_pow: &[f32],
+        power: f32,
+        dt_s: f32,
+    ) -> bool {
+        if self.sum.len() != spec_pow.len() {
+            self.sum = vec![0.0; spec_pow.len()];
+            self.power_sum = 0.0;
+            self.frames = 0;
+            self.elapsed_s = 0.0;
+        }
+
+        for (acc, &p) in self.sum.iter_mut().zip(spec_pow) {
+            *acc += p;
+        }
+        self.power_sum += power;
+        self.frames = self.frames.saturating_add(1);
+        self.elapsed_s += dt_s;
+
+        self.is_done()
+    }
+
+    #[must_use]
+    pub fn is_done(&self) -> bool {
+        self.frames > 0 && self.elapsed_s >= self.duration_s
+    }
+
+    #[must_use]
+    pub fn remaining_s(&self) -> f32 {
+        (self.duration_s - self.elapsed_s).max(0.0)
+    }
+
+    /// Returns the averaged profile, or `None` if nothing was pushed.
+    #[must_use]
+    pub fn finish(
+        &self,
+        sample_rate: f32,
+        fft_size: usize,
+    ) -> Option<NoiseProfile> {
+        if self.frames == 0 {
+            return None;
+        }
+
+        #[allow(clippy::cast_precision_loss)]
+        let inv = 1.0 / self.frames as f32;
+        let power = self.power_sum * inv;
+
+        Some(NoiseProfile {
+            sample_rate,
+            fft_size,
+            floor_db: 10.0 * power.max(1e-12).log10(),
+            bins: self.sum.iter().map(|&s| s * inv).collect(),
+        })
+    }
+}
@@ -0,0 +1,18 @@
+mod calibrator;
+mod profile;
+
+pub use calibrator::NoiseCalibrator;
+pub use profile::NoiseProfile;
+
+/// How far above the measured noise floor the gate opens.
+pub const GATE_MARGIN_DB: f32 = 6.0;
+
+/// Fraction of the original band power kept after subtraction, so a
+/// band sitting exactly on the noise floor never reads as silence.
+pub const SPECTRAL_FLOOR: f32 = 0.01;
+
+/// Power-domain spectral subtraction with a spectral floor.
+#[must_use]
+pub fn subtract_noise(power: f32, noise: f32) -> f32 {
+    (power - noise).max(power * SPECTRAL_FLOOR)
+}
@@ -0,0 +1,115 @@
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::{fs, path::Path, path::PathBuf};
+
+use super::GATE_MARGIN_DB;
+
+/// Averaged noise power spectrum measured during calibration.
+///
+/// `bins` holds one power value per FFT bin, normalised the same way
+/// as the analyzer's spectrum, and `floor_db` is the broadband power
+/// the gate compares against.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NoiseProfile {
+    pub sample_rate: f32,
+    pub fft_size: usize,
+    pub floor_db: f32,
+    pub bins: Vec<f32>,
+}
+
+impl NoiseProfile {
+    /// Gate threshold derived from the measured floor.
+    #[must_use]
+    pub fn gate_db(&self) -> f32 {
+        (self.floor_db + GATE_MARGIN_DB).clamp(-90.0, -20.0)
+    }
+
+    /// Maps the profile onto another FFT grid by interpolating over
+    /// frequency. Per-bin power of broadband noise scales inversely
+    /// with the FFT size, so values are rescaled to match.
+    #[must_use]
+    #[allow(
+        clippy::cast_precision_loss,
+        clippy::cast_possible_truncation,
+        clippy::cast_sign_loss
+    )]
+    pub fn bins_for(
+        &self,
+        sample_rate: f32,
+        fft_size: usize,
+    ) -> Vec<f32> {
+        let half = fft_size / 2;
+        if self.bins.is_empty() || self.fft_size == 0 || fft_size == 0
+        {
+            return vec![0.0; half];
+        }
+
+        let src_hz = self.sample_rate / self.fft_size as f32;
+        let dst_hz = sample_rate / fft_size as f32;
+        let gain = self.fft_size as f32 / fft_size as f32;
+        let last = self.bins.len().saturating_sub(1);
+
+        (0..half)
+            .map(|i| {
+                let pos = i as f32 * dst_hz / src_hz;
+                let lo = (pos.floor() as usize).min(last);
+                let hi = lo.saturating_add(1).min(last);
+                let t = (pos - lo as f32).clamp(0.0, 1.0);
+                let a = self.bins.get(lo).copied().unwrap_or(0.0);
+                let b = self.bins.get(hi).copied().unwrap_or(a);
+                (b - a).mul_add(t, a) * gain
+            })
+            .collect()
+    }
+
+    pub fn path() -> Result<PathBuf> {
+        Ok(dirs::config_dir()
+            .context("failed to resolve config directory")?
+            .join("lookas-noise.toml"))
+    }
+
+    /// Loads the persisted profile, if one has been saved.
+    pub fn load() -> Result<Option<Self>> {
+        let path = Self::path()?;
+        if path.exists() {
+            return Ok(Some(Self::load_from(&path)?));
+        }
+        Ok(None)
+    }
+
+    pub fn save(&self) -> Result<()> {
+        self.save_to(&Self::path()?)
+    }
+
+    pub fn load_from(path: &Path) -> Result<Self> {
+        let contents =
+            fs::read_to_string(path).with_context(|| {
+                format!(
+                    "failed to read noise profile: {}",
+                    path.display()
+                )
+            })?;
+
+        toml::from_str::<Self>(&contents).with_context(|| {
+            format!("invalid noise profile in {}", path.display())
+        })
+    }
+
+    pub fn save_to(&self, path: &Path) -> Result<()> {
+        let contents = toml::to_string(self)
+            .context("failed to serialise noise profile")?;
+
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir).with_context(|| {
+                format!("failed to create {}", dir.display())
+            })?;
+        }
+
+        fs::write(path, contents).with_context(|| {
+            format!(
+                "failed to write noise profile: {}",
+                path.display()
+            )
+        })
+    }
+}
@@ -0,0 +1,118 @@
+mod reassign;
+
+pub use reassign::Reassigner;
+
+use serde::Deserialize;
+
+/// Power of a full-scale sine's peak bin after Hann windowing and
+/// `1 / N²` normalisation, so levels read in dBFS.
+const FULL_SCALE_POWER: f32 = 1.0 / 16.0;
+
+/// How a spectral maximum is refined between bins.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Interpolation {
+    /// Parabola through the magnitudes of the three bins.
+    Quadratic,
+    /// Parabola through their log power, exact for a Gaussian peak
+    /// and close for a Hann main lobe.
+    Gaussian,
+}
+
+/// One of the strongest partials of a spectrum.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Partial {
+    pub bin: usize,
+    pub hz: f32,
+    /// Interpolated peak level in dBFS, a full-scale sine reading
+    /// about 0 dB.
+    pub db: f32,
+}
+
+/// Bin width, refinement and number of partials to look for.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PartialParams {
+    pub bin_hz: f32,
+    pub interpolation: Interpolation,
+    pub count: usize,
+}
+
+/// Offset of the true maximum from the middle of three bins, in
+/// `-0.5..=0.5` bins, and the power interpolated there.
+#[must_use]
+pub fn interpolate_peak(
+    (a, b, c): (f32, f32, f32),
+    interpolation: Interpolation,
+) -> (f32, f32) {
+    let fit = |p: f32| match interpolation {
+        Interpolation::Quadratic => p.max(0.0).sqrt(),
+        Interpolation::Gaussian => (p.max(0.0) + 1e-30).ln(),
+    };
+    let (fa, fb, fc) = (fit(a), fit(b), fit(c));
+    let den = fa - 2.0f32.mul_add(fb, -fc);
+    if den.abs() <= 1e-12 {
+        return (0.0, b);
+    }
+
+    let delta = (0.5 * (fa - fc) / den).clamp(-0.5, 0.5);
+    let peak = (0.25 * (fa - fc)).mul_add(-delta, fb);
+    let power = match interpolation {
+        Interpolation::Quadratic => peak * peak,
+        Interpolation::Gaussian => peak.exp(),
+    };
+    (delta, power.max(b))
+}
+
+/// The `count` strongest local maxima of `spec_pow`, loudest first,
+/// refined with `interpolation`, written into `out`.
+#[allow(clippy::cast_precision_loss)]
+pub fn strongest_partials(
+    spec_pow: &[f32],
+    params: &PartialParams,
+    out: &mut Vec<Partial>,
+) {
+    let PartialParams {
+        bin_hz,
+        interpolation,
+        count,
+    } = *params;
+    out.clear();
+    if bin_hz <= 0.0 || count == 0 {
+        return;
+    }
+
+    for (i, w) in spec_pow.windows(3).enumerate() {
+        let (Some(&a), Some(&b), Some(&c)) =
+            (w.first(), w.get(1), w.get(2))
+        else {
+            continue;
+        };
+        if b <= 1e-20 || b <= a || b < c {
+            continue;
+        }
+
+        let bin = i.saturating_add(1);
+        let (delta, power) =
+            interpolate_peak((a, b, c), interpolation);
+        let partial = Partial {
+            bin,
+            hz: (bin as f32 + delta) * bin_hz,
+            db: 10.0 * (power / FULL_SCALE_POWER).log10(),
+        };
+        insert_strongest(out, partial, count);
+    }
+}
+
+/// Keeps `out` sorted loudest first and at most `count` long.
+fn insert_strongest(
+    out: &mut Vec<Partial>,
+    partial: Partial,
+    count: usize,
+) {
+    let at = out.partition_point(|p| p.db >= partial.db);
+    if at >= count {
+        return;
+    }
+    out.insert(at, partial);
+    out.truncate(count);
+}
@@ -0,0 +1,93 @@
+use realfft::{RealFftPlanner, RealToComplex, num_complex::Complex};
+use std::{f32::consts::PI, sync::Arc};
+
+use crate::dsp::{hann, prepare_fft_input_inplace};
+
+/// Frequency reassignment: a second FFT with the derivative of the
+/// Hann window tells how far a bin's energy sits from its center.
+///
+/// For a stable sinusoid this recovers the frequency well inside a
+/// bin, without relying on the shape of the main lobe.
+pub struct Reassigner {
+    fft: Arc<dyn RealToComplex<f32>>,
+    window: Vec<f32>,
+    dwindow: Vec<f32>,
+    buf: Vec<f32>,
+    spec_h: Vec<Complex<f32>>,
+    spec_dh: Vec<Complex<f32>>,
+}
+
+impl Reassigner {
+    #[must_use]
+    #[allow(
+        clippy::cast_precision_loss,
+        clippy::arithmetic_side_effects
+    )]
+    pub fn new(fft_size: usize) -> Self {
+        let fft =
+            RealFftPlanner::<f32>::new().plan_fft_forward(fft_size);
+        let den = fft_size.max(2).saturating_sub(1) as f32;
+        let dwindow = (0..fft_size)
+            .map(|i| (PI / den) * (2.0 * PI * i as f32 / den).sin())
+            .collect();
+
+        Self {
+            buf: fft.make_input_vec(),
+            spec_h: fft.make_output_vec(),
+            spec_dh: fft.make_output_vec(),
+            window: hann(fft_size),
+            dwindow,
+            fft,
+        }
+    }
+
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.window.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.window.is_empty()
+    }
+
+    /// Transforms one frame of [`Self::len`] samples. Returns `false`
+    /// if the frame could not be transformed.
+    pub fn process(&mut self, samples: &[f32]) -> bool {
+        if samples.len() != self.window.len() {
+            return false;
+        }
+        prepare_fft_input_inplace(
+            samples,
+            &self.window,
+            &mut self.buf,
+        );
+        if self.fft.process(&mut self.buf, &mut self.spec_h).is_err()
+        {
+            return false;
+        }
+        prepare_fft_input_inplace(
+            samples,
+            &self.dwindow,
+            &mut self.buf,
+        );
+        self.fft.process(&mut self.buf, &mut self.spec_dh).is_ok()
+    }
+
+    /// Reassigned frequency of the energy in `bin`, or `None` for an
+    /// empty bin or one more than a bin away from its estimate.
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn frequency(&self, bin: usize, bin_hz: f32) -> Option<f32> {
+        let h = self.spec_h.get(bin)?;
+        let dh = self.spec_dh.get(bin)?;
+        let norm = h.norm_sqr();
+        if norm <= 1e-30 {
+            return None;
+        }
+
+        let cross = dh.im.mul_add(h.re, -(dh.re * h.im));
+        let shift = cross / norm * self.len() as f32 / (2.0 * PI);
+        (shift.abs() <= 1.0).then_some((bin as f32 - shift) * bin_hz)
+    }
+}
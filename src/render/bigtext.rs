@@ -0,0 +1,69 @@
+use std::io::Write;
+
+/// Rows in every big-text glyph.
+pub const BIG_TEXT_ROWS: usize = 5;
+
+/// Columns per glyph bit, and blank columns after each glyph.
+const BIT_W: usize = 2;
+const GLYPH_BITS: usize = 3;
+const GLYPH_GAP: usize = 2;
+
+const FULL: &str = "\u{2588}";
+
+/// 3x5 bitmaps, most significant of the low three bits on the left.
+const fn glyph(c: char) -> [u8; BIG_TEXT_ROWS] {
+    match c.to_ascii_uppercase() {
+        'A' => [0b010, 0b101, 0b111, 0b101, 0b101],
+        'B' => [0b110, 0b101, 0b110, 0b101, 0b110],
+        'C' => [0b011, 0b100, 0b100, 0b100, 0b011],
+        'D' => [0b110, 0b101, 0b101, 0b101, 0b110],
+        'E' => [0b111, 0b100, 0b110, 0b100, 0b111],
+        'F' => [0b111, 0b100, 0b110, 0b100, 0b100],
+        'G' => [0b011, 0b100, 0b101, 0b101, 0b011],
+        '#' => [0b101, 0b111, 0b101, 0b111, 0b101],
+        '-' => [0b000, 0b000, 0b111, 0b000, 0b000],
+        '.' => [0b000, 0b000, 0b000, 0b000, 0b010],
+        '%' => [0b101, 0b001, 0b010, 0b100, 0b101],
+        '0' => [0b111, 0b101, 0b101, 0b101, 0b111],
+        '1' => [0b010, 0b110, 0b010, 0b010, 0b111],
+        '2' => [0b111, 0b001, 0b111, 0b100, 0b111],
+        '3' => [0b111, 0b001, 0b011, 0b001, 0b111],
+        '4' => [0b101, 0b101, 0b111, 0b001, 0b001],
+        '5' => [0b111, 0b100, 0b111, 0b001, 0b111],
+        '6' => [0b111, 0b100, 0b111, 0b101, 0b111],
+        '7' => [0b111, 0b001, 0b001, 0b010, 0b010],
+        '8' => [0b111, 0b101, 0b111, 0b101, 0b111],
+        '9' => [0b111, 0b101, 0b111, 0b001, 0b111],
+        _ => [0; BIG_TEXT_ROWS],
+    }
+}
+
+/// Columns taken by `text` in big letters.
+#[must_use]
+#[allow(clippy::arithmetic_side_effects)]
+pub fn big_text_width(text: &str) -> usize {
+    let n = text.chars().count();
+    (n * (GLYPH_BITS * BIT_W + GLYPH_GAP)).saturating_sub(GLYPH_GAP)
+}
+
+/// Writes row `row` of `text` in big block letters. Characters
+/// without a glyph are left blank.
+pub fn draw_big_text_row<W: Write>(
+    out: &mut W,
+    text: &str,
+    row: usize,
+) -> std::io::Result<()> {
+    for (i, c) in text.chars().enumerate() {
+        if i > 0 {
+            write!(out, "{:GLYPH_GAP$}", "")?;
+        }
+        let bits = glyph(c).get(row).copied().unwrap_or(0);
+        for b in (0..GLYPH_BITS).rev() {
+            let cell = if bits >> b & 1 == 1 { FULL } else { " " };
+            for _ in 0..BIT_W {
+                out.write_all(cell.as_bytes())?;
+            }
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,113 @@
+use std::io::Write;
+
+/// Dots per braille cell across and down.
+pub const BRAILLE_DOTS_W: usize = 2;
+pub const BRAILLE_DOTS_H: usize = 4;
+
+/// Bit of each dot in a braille cell, by row and then column.
+const DOT_BITS: [[u8; BRAILLE_DOTS_W]; BRAILLE_DOTS_H] =
+    [[0x01, 0x08], [0x02, 0x10], [0x04, 0x20], [0x40, 0x80]];
+
+/// A grid of braille cells, each holding 2×4 dots, for plotting
+/// points at a finer resolution than the terminal cells.
+#[derive(Debug, Clone, Default)]
+pub struct BrailleCanvas {
+    cols: usize,
+    rows: usize,
+    cells: Vec<u8>,
+}
+
+impl BrailleCanvas {
+    #[must_use]
+    #[allow(clippy::arithmetic_side_effects)]
+    pub fn new(cols: usize, rows: usize) -> Self {
+        Self {
+            cols,
+            rows,
+            cells: vec![0; cols * rows],
+        }
+    }
+
+    #[must_use]
+    pub const fn cols(&self) -> usize {
+        self.cols
+    }
+
+    #[must_use]
+    pub const fn rows(&self) -> usize {
+        self.rows
+    }
+
+    /// Resizes and clears the canvas.
+    pub fn resize(&mut self, cols: usize, rows: usize) {
+        *self = Self::new(cols, rows);
+    }
+
+    pub fn clear(&mut self) {
+        self.cells.fill(0);
+    }
+
+    /// Sets the dot at `x`, `y` in `-1..=1`, with `y` pointing up.
+    /// Points outside the canvas are dropped.
+    #[allow(
+        clippy::arithmetic_side_effects,
+        clippy::cast_precision_loss,
+        clippy::cast_possible_truncation,
+        clippy::cast_sign_loss
+    )]
+    pub fn plot(&mut self, x: f32, y: f32) {
+        if !(-1.0..=1.0).contains(&x) || !(-1.0..=1.0).contains(&y) {
+            return;
+        }
+
+        let dots_w = self.cols * BRAILLE_DOTS_W;
+        let dots_h = self.rows * BRAILLE_DOTS_H;
+        if dots_w == 0 || dots_h == 0 {
+            return;
+        }
+        let dx = (x.mul_add(0.5, 0.5) * (dots_w - 1) as f32).round()
+            as usize;
+        let dy = ((-y).mul_add(0.5, 0.5) * (dots_h - 1) as f32)
+            .round() as usize;
+
+        let bit = DOT_BITS
+            .get(dy % BRAILLE_DOTS_H)
+            .and_then(|row| row.get(dx % BRAILLE_DOTS_W))
+            .copied()
+            .unwrap_or(0);
+        let cell =
+            (dy / BRAILLE_DOTS_H) * self.cols + dx / BRAILLE_DOTS_W;
+        if let Some(c) = self.cells.get_mut(cell) {
+            *c |= bit;
+        }
+    }
+
+    /// Character of the cell at `col`, `row`, blank braille when no
+    /// dot is set.
+    #[must_use]
+    #[allow(clippy::arithmetic_side_effects)]
+    pub fn cell(&self, col: usize, row: usize) -> char {
+        let bits = if col < self.cols {
+            self.cells.get(row * self.cols + col).copied()
+        } else {
+            None
+        };
+        char::from_u32(0x2800 | u32::from(bits.unwrap_or(0)))
+            .unwrap_or(' ')
+    }
+
+    /// Writes one row of cells.
+    pub fn draw_row<W: Write>(
+        &self,
+        out: &mut W,
+        row: usize,
+    ) -> std::io::Result<()> {
+        let mut buf = [0u8; 4];
+        for col in 0..self.cols {
+            out.write_all(
+                self.cell(col, row).encode_utf8(&mut buf).as_bytes(),
+            )?;
+        }
+        Ok(())
+    }
+}
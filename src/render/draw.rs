@@ -1,8 +1,18 @@
 use std::io::Write;
 
-use super::{BAR_W, GAP_W, Layout};
+use crate::config::RgbColor;
 
-const VBLOCKS_ENCODED: [[u8; 3]; 9] = [
+use super::Layout;
+
+/// Peak cap heights drawn above the bars in their own color.
+/// `bar_color` is restored after each run of caps.
+pub struct PeakCaps<'a> {
+    pub heights: &'a [f32],
+    pub color: RgbColor,
+    pub bar_color: RgbColor,
+}
+
+pub(super) const VBLOCKS_ENCODED: [[u8; 3]; 9] = [
     [b' ', 0, 0],       // ' '  U+0020  1 byte
     [0xE2, 0x96, 0x81], // '▁'  U+2581
     [0xE2, 0x96, 0x82], // '▂'  U+2582
@@ -14,19 +24,42 @@ const VBLOCKS_ENCODED: [[u8; 3]; 9] = [
     [0xE2, 0x96, 0x88], // '█'  U+2588
 ];
 
-const VBLOCKS_LEN: [usize; 9] = [1, 3, 3, 3, 3, 3, 3, 3, 3];
+pub(super) const VBLOCKS_LEN: [usize; 9] =
+    [1, 3, 3, 3, 3, 3, 3, 3, 3];
 
-const FULL_BLOCK: usize = 8;
+pub(super) const FULL_BLOCK: usize = 8;
+
+const CAP_ENCODED: [u8; 3] = [0xE2, 0x96, 0x94]; // '▔'  U+2594
+
+/// Row holding the top of a cap, or `None` if the cap rests on the
+/// floor.
+#[inline]
+#[allow(
+    clippy::cast_precision_loss,
+    clippy::cast_possible_truncation,
+    clippy::cast_sign_loss
+)]
+fn cap_row(height: f32, rows: usize) -> Option<usize> {
+    let top = (height.clamp(0.0, 1.0) * rows as f32).ceil() as usize;
+    top.checked_sub(1)
+}
+
+pub(super) fn write_color<W: Write>(
+    out: &mut W,
+    c: RgbColor,
+) -> std::io::Result<()> {
+    write!(out, "\x1b[38;2;{};{};{}m", c.r, c.g, c.b)
+}
 
 #[inline]
 #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
-fn v_partial_idx(frac: f32) -> usize {
+pub(super) fn v_partial_idx(frac: f32) -> usize {
     let f = frac.clamp(0.0, 0.9999);
     f.mul_add(8.0, 0.5).floor() as usize
 }
 
 #[inline]
-fn write_spaces<W: Write>(
+pub(super) fn write_spaces<W: Write>(
     out: &mut W,
     mut n: usize,
 ) -> std::io::Result<()> {
@@ -43,6 +76,26 @@ fn write_spaces<W: Write>(
     Ok(())
 }
 
+/// Glyph of a bar `full` rows and `frac` of a row tall on `row`,
+/// counted from the bottom.
+#[must_use]
+pub fn bar_glyph(full: usize, frac: f32, row: usize) -> &'static str {
+    let glyphs: &'static [[u8; 3]; 9] = &VBLOCKS_ENCODED;
+    let idx = if row < full {
+        FULL_BLOCK
+    } else if row == full && frac > 0.0 {
+        v_partial_idx(frac)
+    } else {
+        0
+    };
+    let len = VBLOCKS_LEN.get(idx).copied().unwrap_or(1);
+    glyphs
+        .get(idx)
+        .and_then(|enc| enc.get(..len))
+        .and_then(|bytes| std::str::from_utf8(bytes).ok())
+        .unwrap_or(" ")
+}
+
 #[inline]
 #[allow(
     clippy::too_many_arguments,
@@ -60,6 +113,7 @@ pub fn draw_blocks_vertical<W: Write>(
     lay: &Layout,
     fulls: &mut [usize],
     fracs: &mut [f32],
+    caps: Option<&PeakCaps<'_>>,
 ) -> std::io::Result<()> {
     let rows = h.saturating_sub(lay.top_pad) as usize;
     let cols = w
@@ -69,7 +123,7 @@ pub fn draw_blocks_vertical<W: Write>(
         return Ok(());
     }
 
-    let per = BAR_W + GAP_W;
+    let per = lay.slot_w();
     let n = bars
         .len()
         .min(cols.checked_div(per).map_or(1, |v| v.max(1)))
@@ -105,15 +159,32 @@ pub fn draw_blocks_vertical<W: Write>(
                 0
             };
 
+            let cap = caps.filter(|c| {
+                idx == 0
+                    && cap_row(
+                        c.heights.get(i).copied().unwrap_or(0.0),
+                        rows,
+                    ) == Some(row)
+            });
+            if let Some(c) = cap {
+                write_color(out, c.color)?;
+                for _ in 0..lay.bar_w {
+                    out.write_all(&CAP_ENCODED)?;
+                }
+                write_color(out, c.bar_color)?;
+                write_spaces(out, lay.gap_w)?;
+                continue;
+            }
+
             let enc = VBLOCKS_ENCODED
                 .get(idx)
                 .unwrap_or(&VBLOCKS_ENCODED[0]);
             let len = VBLOCKS_LEN.get(idx).copied().unwrap_or(1);
             let bytes = enc.get(..len).unwrap_or(enc.as_slice());
-            for _ in 0..BAR_W {
+            for _ in 0..lay.bar_w {
                 out.write_all(bytes)?;
             }
-            write_spaces(out, GAP_W)?;
+            write_spaces(out, lay.gap_w)?;
         }
 
         let used = n * per;
@@ -0,0 +1,91 @@
+use std::io::Write;
+
+/// Deviation at either end of the gauge, in cents.
+pub const GAUGE_RANGE_CENTS: f32 = 50.0;
+
+/// Scale marks every this many cents, and how many on each side.
+const TICK_CENTS: f32 = 10.0;
+const TICKS: i8 = 5;
+
+/// Column of `cents` on a gauge `width` columns wide.
+#[must_use]
+#[allow(
+    clippy::cast_precision_loss,
+    clippy::cast_possible_truncation,
+    clippy::cast_sign_loss
+)]
+pub fn gauge_col(cents: f32, width: usize) -> usize {
+    let last = width.saturating_sub(1) as f32;
+    let t = (cents / GAUGE_RANGE_CENTS)
+        .clamp(-1.0, 1.0)
+        .mul_add(0.5, 0.5);
+    (t * last).round() as usize
+}
+
+/// Writes the gauge scale: a line with ticks every ten cents and a
+/// heavy mark in the middle.
+pub fn draw_gauge_scale<W: Write>(
+    out: &mut W,
+    width: usize,
+) -> std::io::Result<()> {
+    let centre = gauge_col(0.0, width);
+    let is_tick = |col: usize| {
+        (-TICKS..=TICKS).any(|k| {
+            gauge_col(f32::from(k) * TICK_CENTS, width) == col
+        })
+    };
+
+    for col in 0..width {
+        let cell = if col == centre {
+            "\u{253F}"
+        } else if is_tick(col) {
+            "\u{253C}"
+        } else {
+            "\u{2500}"
+        };
+        out.write_all(cell.as_bytes())?;
+    }
+    Ok(())
+}
+
+/// Writes the needle row, blank when there is no reading.
+pub fn draw_gauge_needle<W: Write>(
+    out: &mut W,
+    cents: Option<f32>,
+    width: usize,
+) -> std::io::Result<()> {
+    let needle = cents.map(|c| gauge_col(c, width));
+    for col in 0..width {
+        let cell = if needle == Some(col) { "\u{25B2}" } else { " " };
+        out.write_all(cell.as_bytes())?;
+    }
+    Ok(())
+}
+
+/// Writes `-50`, `0` and `+50` under the ends and middle of the
+/// scale.
+pub fn draw_gauge_labels<W: Write>(
+    out: &mut W,
+    width: usize,
+) -> std::io::Result<()> {
+    draw_gauge_end_labels(out, "-50", "+50", width)
+}
+
+/// Writes `left` and `right` under the ends of the scale and `0` in
+/// the middle, for gauges not reading in cents.
+#[allow(clippy::arithmetic_side_effects)]
+pub fn draw_gauge_end_labels<W: Write>(
+    out: &mut W,
+    left: &str,
+    right: &str,
+    width: usize,
+) -> std::io::Result<()> {
+    let centre = gauge_col(0.0, width);
+    if centre < left.len() + 1 || width < centre + 2 + right.len() {
+        return write!(out, "{:width$}", "");
+    }
+
+    let mid = centre - left.len();
+    let tail = width - centre - 1 - right.len();
+    write!(out, "{left}{:mid$}0{:tail$}{right}", "", "")
+}
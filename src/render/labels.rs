@@ -0,0 +1,59 @@
+use std::io::Write;
+
+use super::Layout;
+
+/// Writes one text row with `labels` left-aligned under the bars of
+/// `lay`. Labels wider than a bar slot are cut, and the rest of the
+/// row is blanked.
+#[allow(clippy::arithmetic_side_effects)]
+pub fn draw_labels<W: Write, S: AsRef<str>>(
+    out: &mut W,
+    labels: &[S],
+    w: u16,
+    lay: &Layout,
+) -> std::io::Result<()> {
+    let per = lay.slot_w();
+    let cols = usize::from(w);
+    let mut used = usize::from(lay.left_pad).min(cols);
+    write!(out, "{:used$}", "")?;
+
+    for label in labels.iter().take(lay.bars) {
+        let label = label.as_ref();
+        if used + per > cols {
+            break;
+        }
+        let cut = label
+            .char_indices()
+            .nth(per)
+            .map_or(label.len(), |(i, _)| i);
+        let shown = label.get(..cut).unwrap_or("");
+        write!(out, "{shown:<per$}")?;
+        used += per;
+    }
+
+    let rest = cols - used;
+    write!(out, "{:rest$}", "")
+}
+
+/// Labels for a row of piano keys: black keys get a half block, every
+/// C its octave number, and other white keys stay blank. With slots
+/// two columns or wider the Cs read `C4` rather than `4`.
+#[must_use]
+#[allow(clippy::arithmetic_side_effects)]
+pub fn keyboard_labels(
+    low_midi: i32,
+    high_midi: i32,
+    slot_w: usize,
+) -> Vec<String> {
+    (low_midi..=high_midi)
+        .map(|midi| {
+            let octave = midi.div_euclid(12) - 1;
+            match midi.rem_euclid(12) {
+                1 | 3 | 6 | 8 | 10 => String::from("\u{2580}"),
+                0 if slot_w >= 2 => format!("C{octave}"),
+                0 => octave.to_string(),
+                _ => String::new(),
+            }
+        })
+        .collect()
+}
@@ -1,12 +1,26 @@
 use super::{BAR_W, GAP_W};
 
+/// Widest bar a fixed layout will stretch to.
+const MAX_FIXED_BAR_W: usize = 8;
+
+#[derive(Debug, Clone, Copy)]
 pub struct Layout {
     pub bars: usize,
+    pub bar_w: usize,
+    pub gap_w: usize,
     pub left_pad: u16,
     pub right_pad: u16,
     pub top_pad: u16,
 }
 
+impl Layout {
+    /// Columns from the start of one bar to the next.
+    #[must_use]
+    pub const fn slot_w(&self) -> usize {
+        self.bar_w.saturating_add(self.gap_w)
+    }
+}
+
 #[inline]
 #[must_use]
 #[allow(
@@ -25,8 +39,59 @@ pub fn layout_for(w: u16, _h: u16, top_pad: u16) -> Layout {
 
     Layout {
         bars,
+        bar_w: BAR_W,
+        gap_w: GAP_W,
         left_pad,
         right_pad,
         top_pad,
     }
 }
+
+/// Layout for a fixed number of bars, centered horizontally. Bars
+/// widen to use the space and shrink to a single column without gaps
+/// when the terminal is narrow.
+#[inline]
+#[must_use]
+#[allow(
+    clippy::cast_possible_truncation,
+    clippy::arithmetic_side_effects
+)]
+pub const fn layout_centered(
+    w: u16,
+    bars: usize,
+    top_pad: u16,
+) -> Layout {
+    let per = match (w as usize).checked_div(bars) {
+        Some(per) => per,
+        None => 0,
+    };
+    let (bar_w, gap_w) = if per >= 2 {
+        let bar_w = per - 1;
+        let bar_w = if bar_w > MAX_FIXED_BAR_W {
+            MAX_FIXED_BAR_W
+        } else {
+            bar_w
+        };
+        (bar_w, 1)
+    } else {
+        (1, 0)
+    };
+
+    let needed = bars.saturating_mul(bar_w + gap_w);
+    let needed = if needed > u16::MAX as usize {
+        u16::MAX
+    } else {
+        needed as u16
+    };
+    let spare = w.saturating_sub(needed);
+    let left_pad = spare / 2;
+
+    Layout {
+        bars,
+        bar_w,
+        gap_w,
+        left_pad,
+        right_pad: spare - left_pad,
+        top_pad,
+    }
+}
@@ -1,8 +1,21 @@
+mod bigtext;
+mod braille;
 mod draw;
+mod gauge;
+mod labels;
 mod layout;
+mod overlay;
 
 pub(crate) const BAR_W: usize = 2;
 pub(crate) const GAP_W: usize = 1;
 
-pub use draw::draw_blocks_vertical;
-pub use layout::{Layout, layout_for};
+pub use bigtext::{BIG_TEXT_ROWS, big_text_width, draw_big_text_row};
+pub use braille::{BRAILLE_DOTS_H, BRAILLE_DOTS_W, BrailleCanvas};
+pub use draw::{PeakCaps, bar_glyph, draw_blocks_vertical};
+pub use gauge::{
+    GAUGE_RANGE_CENTS, draw_gauge_end_labels, draw_gauge_labels,
+    draw_gauge_needle, draw_gauge_scale, gauge_col,
+};
+pub use labels::{draw_labels, keyboard_labels};
+pub use layout::{Layout, layout_centered, layout_for};
+pub use overlay::{OverlayLayer, draw_blocks_overlay};
@@ -0,0 +1,112 @@
+use std::io::Write;
+
+use crate::config::RgbColor;
+
+use super::Layout;
+use super::draw::{
+    FULL_BLOCK, VBLOCKS_ENCODED, VBLOCKS_LEN, v_partial_idx,
+    write_color, write_spaces,
+};
+
+/// One spectrum of an overlay and the color it is drawn in.
+pub struct OverlayLayer<'a> {
+    pub bars: &'a [f32],
+    pub color: RgbColor,
+}
+
+/// Block index of a bar `height` rows tall on `row`, counted from the
+/// bottom.
+#[allow(
+    clippy::cast_precision_loss,
+    clippy::cast_possible_truncation,
+    clippy::cast_sign_loss
+)]
+fn block_idx(height: f32, row: usize) -> usize {
+    let full = height.floor() as usize;
+    let frac = height - full as f32;
+    if row < full {
+        FULL_BLOCK
+    } else if row == full && frac > 0.0 {
+        v_partial_idx(frac)
+    } else {
+        0
+    }
+}
+
+/// Draws two spectra over the same bars. Cells both bars cover take
+/// a color halfway between the two; above the shorter bar, the taller
+/// one shows in its own color.
+#[allow(
+    clippy::too_many_arguments,
+    clippy::cast_precision_loss,
+    clippy::arithmetic_side_effects
+)]
+pub fn draw_blocks_overlay<W: Write>(
+    out: &mut W,
+    layers: [&OverlayLayer<'_>; 2],
+    w: u16,
+    h: u16,
+    lay: &Layout,
+) -> std::io::Result<()> {
+    let rows = h.saturating_sub(lay.top_pad) as usize;
+    let cols = w
+        .saturating_sub(lay.left_pad.saturating_add(lay.right_pad))
+        as usize;
+    if rows == 0 || cols == 0 {
+        return Ok(());
+    }
+
+    let [front, back] = layers;
+    let both = front.color.lerp(back.color, 0.5);
+    let per = lay.slot_w();
+    let n = front
+        .bars
+        .len()
+        .min(back.bars.len())
+        .min(cols.checked_div(per).map_or(1, |v| v.max(1)));
+    let height = |bars: &[f32], i: usize| {
+        bars.get(i).copied().unwrap_or(0.0).clamp(0.0, 1.0)
+            * rows as f32
+    };
+
+    let mut current = None;
+    for y in 0..rows {
+        let row = rows - 1 - y;
+        write_spaces(out, lay.left_pad as usize)?;
+
+        for i in 0..n {
+            let ia = block_idx(height(front.bars, i), row);
+            let ib = block_idx(height(back.bars, i), row);
+            let idx = ia.max(ib);
+            let color = if ia.min(ib) == FULL_BLOCK {
+                both
+            } else if ia >= ib {
+                front.color
+            } else {
+                back.color
+            };
+            if idx > 0 && current != Some(color) {
+                write_color(out, color)?;
+                current = Some(color);
+            }
+
+            let enc = VBLOCKS_ENCODED
+                .get(idx)
+                .unwrap_or(&VBLOCKS_ENCODED[0]);
+            let len = VBLOCKS_LEN.get(idx).copied().unwrap_or(1);
+            let bytes = enc.get(..len).unwrap_or(enc.as_slice());
+            for _ in 0..lay.bar_w {
+                out.write_all(bytes)?;
+            }
+            write_spaces(out, lay.gap_w)?;
+        }
+
+        write_spaces(out, cols.saturating_sub(n * per))?;
+        write_spaces(out, lay.right_pad as usize)?;
+        if y + 1 < rows {
+            out.write_all(b"\r\n")?;
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,340 @@
+mod weighting;
+
+pub use weighting::{FrequencyWeighting, WeightingFilter};
+
+use serde::Deserialize;
+
+/// Mean square of a full-scale sine, the 0 dBFS reference.
+const FULL_SCALE_MS: f64 = 0.5;
+/// Length of the blocks noise dose is accumulated over.
+const DOSE_BLOCK_S: f32 = 0.125;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TimeWeighting {
+    Fast,
+    Slow,
+    Impulse,
+}
+
+impl TimeWeighting {
+    /// Time constants while the level rises and falls, in seconds.
+    #[must_use]
+    pub const fn tau_s(self) -> (f32, f32) {
+        match self {
+            Self::Fast => (0.125, 0.125),
+            Self::Slow => (1.0, 1.0),
+            Self::Impulse => (0.035, 1.5),
+        }
+    }
+
+    #[must_use]
+    pub const fn letter(self) -> char {
+        match self {
+            Self::Fast => 'F',
+            Self::Slow => 'S',
+            Self::Impulse => 'I',
+        }
+    }
+
+    /// The weighting after this one when cycling.
+    #[must_use]
+    pub const fn next(self) -> Self {
+        match self {
+            Self::Fast => Self::Slow,
+            Self::Slow => Self::Impulse,
+            Self::Impulse => Self::Fast,
+        }
+    }
+}
+
+/// Occupational noise limits the exposure dose is measured against.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ExposureStandard {
+    /// NIOSH REL: 85 dBA for 8 hours, 3 dB exchange rate.
+    Niosh,
+    /// OSHA PEL: 90 dBA for 8 hours, 5 dB exchange rate.
+    Osha,
+}
+
+impl ExposureStandard {
+    #[must_use]
+    pub const fn params(self) -> DoseParams {
+        match self {
+            Self::Niosh => DoseParams {
+                criterion_db: 85.0,
+                exchange_db: 3.0,
+                threshold_db: 80.0,
+                criterion_hours: 8.0,
+            },
+            Self::Osha => DoseParams {
+                criterion_db: 90.0,
+                exchange_db: 5.0,
+                threshold_db: 80.0,
+                criterion_hours: 8.0,
+            },
+        }
+    }
+
+    #[must_use]
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Niosh => "NIOSH",
+            Self::Osha => "OSHA",
+        }
+    }
+}
+
+/// A noise dose rule: `criterion_db` for `criterion_hours` is 100 %,
+/// every `exchange_db` more halves the allowed time, and levels below
+/// `threshold_db` do not count.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DoseParams {
+    pub criterion_db: f32,
+    pub exchange_db: f32,
+    pub threshold_db: f32,
+    pub criterion_hours: f32,
+}
+
+impl DoseParams {
+    /// Share of the daily allowance, in percent, used up by `dt_s`
+    /// seconds at `level_db` SPL.
+    #[must_use]
+    pub fn dose_percent(&self, level_db: f32, dt_s: f32) -> f32 {
+        if level_db < self.threshold_db {
+            return 0.0;
+        }
+        let allowed_s = self.criterion_hours * 3600.0
+            / ((level_db - self.criterion_db)
+                / self.exchange_db.max(0.1))
+            .exp2();
+        100.0 * dt_s / allowed_s.max(1e-6)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SplParams {
+    pub frequency: FrequencyWeighting,
+    pub time: TimeWeighting,
+    /// Added to dBFS to get dB SPL; `None` while uncalibrated.
+    pub offset_db: Option<f32>,
+    pub standard: ExposureStandard,
+}
+
+impl SplParams {
+    pub const DEFAULT: Self = Self {
+        frequency: FrequencyWeighting::A,
+        time: TimeWeighting::Fast,
+        offset_db: None,
+        standard: ExposureStandard::Niosh,
+    };
+}
+
+/// Levels in dB SPL, or in dBFS while uncalibrated. Levels are `-inf`
+/// before any sound.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SplReading {
+    /// Current time-weighted level.
+    pub level: f32,
+    /// Equivalent continuous level over the session.
+    pub leq: f32,
+    /// Highest time-weighted level of the session.
+    pub lmax: f32,
+    /// Noise dose in percent, once calibrated.
+    pub dose: Option<f32>,
+    pub elapsed_s: f32,
+}
+
+/// A sound level meter: frequency and time weighting, Leq, Lmax and
+/// exposure dose over the session.
+pub struct SplMeter {
+    sample_rate: f32,
+    params: SplParams,
+    filter: WeightingFilter,
+    alpha: (f64, f64),
+    ms: f64,
+    max_ms: f64,
+    sum_sq: f64,
+    count: u64,
+    block_sq: f64,
+    block_len: usize,
+    block_count: usize,
+    dose: f32,
+    calibration: Option<Calibration>,
+}
+
+/// A reference level being measured to derive the offset.
+struct Calibration {
+    reference_db: f32,
+    remaining: usize,
+    sum_sq: f64,
+    count: u64,
+}
+
+impl SplMeter {
+    #[must_use]
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    pub fn new(sample_rate: f32, params: SplParams) -> Self {
+        let sr = sample_rate.max(1.0);
+
+        Self {
+            sample_rate,
+            params,
+            filter: WeightingFilter::new(params.frequency, sr),
+            alpha: time_alphas(params.time, sr),
+            ms: 0.0,
+            max_ms: 0.0,
+            sum_sq: 0.0,
+            count: 0,
+            block_sq: 0.0,
+            block_len: (DOSE_BLOCK_S * sr).round().max(1.0) as usize,
+            block_count: 0,
+            dose: 0.0,
+            calibration: None,
+        }
+    }
+
+    #[must_use]
+    pub const fn sample_rate(&self) -> f32 {
+        self.sample_rate
+    }
+
+    #[must_use]
+    pub const fn params(&self) -> &SplParams {
+        &self.params
+    }
+
+    /// Restarts Leq, Lmax and the dose.
+    pub fn reset(&mut self) {
+        self.max_ms = 0.0;
+        self.sum_sq = 0.0;
+        self.count = 0;
+        self.block_sq = 0.0;
+        self.block_count = 0;
+        self.dose = 0.0;
+    }
+
+    /// Switches the time weighting. Lmax restarts, as it belongs to
+    /// the weighting; Leq and the dose carry on.
+    pub fn set_time_weighting(&mut self, time: TimeWeighting) {
+        self.params.time = time;
+        self.alpha = time_alphas(time, self.sample_rate);
+        self.max_ms = self.ms;
+    }
+
+    /// Measures the next `seconds` of sound as `reference_db` SPL, as
+    /// from an acoustic calibrator, then sets the offset from it and
+    /// restarts the session.
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    pub fn calibrate(&mut self, reference_db: f32, seconds: f32) {
+        self.calibration = Some(Calibration {
+            reference_db,
+            remaining: (seconds * self.sample_rate).round().max(1.0)
+                as usize,
+            sum_sq: 0.0,
+            count: 0,
+        });
+    }
+
+    /// Whether a calibration is being measured.
+    #[must_use]
+    pub const fn calibrating(&self) -> bool {
+        self.calibration.is_some()
+    }
+
+    #[allow(clippy::arithmetic_side_effects)]
+    pub fn process(&mut self, samples: &[f32]) {
+        for &x in samples {
+            let y = self.filter.process(x);
+            let sq = y * y;
+            let alpha = if sq > self.ms {
+                self.alpha.0
+            } else {
+                self.alpha.1
+            };
+            self.ms = (sq - self.ms).mul_add(alpha, self.ms);
+            self.max_ms = self.max_ms.max(self.ms);
+            self.sum_sq += sq;
+            self.count += 1;
+            self.block_sq += sq;
+            self.block_count += 1;
+            if self.block_count >= self.block_len {
+                self.finish_block();
+            }
+            if let Some(cal) = &mut self.calibration {
+                cal.sum_sq += sq;
+                cal.count += 1;
+                cal.remaining = cal.remaining.saturating_sub(1);
+            }
+        }
+        self.finish_calibration();
+    }
+
+    #[allow(clippy::cast_precision_loss)]
+    fn finish_block(&mut self) {
+        let ms = self.block_sq / self.block_count.max(1) as f64;
+        if let Some(offset) = self.params.offset_db {
+            #[allow(clippy::cast_possible_truncation)]
+            let seconds = self.block_count as f32 / self.sample_rate;
+            self.dose += self
+                .params
+                .standard
+                .params()
+                .dose_percent(ms_db(ms) + offset, seconds);
+        }
+        self.block_sq = 0.0;
+        self.block_count = 0;
+    }
+
+    #[allow(clippy::cast_precision_loss)]
+    fn finish_calibration(&mut self) {
+        if !matches!(
+            self.calibration,
+            Some(Calibration { remaining: 0, .. })
+        ) {
+            return;
+        }
+        let Some(cal) = self.calibration.take() else {
+            return;
+        };
+        let ms = cal.sum_sq / cal.count.max(1) as f64;
+        if ms > 0.0 {
+            self.params.offset_db =
+                Some(cal.reference_db - ms_db(ms));
+            self.reset();
+        }
+    }
+
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn reading(&self) -> SplReading {
+        let offset = self.params.offset_db.unwrap_or(0.0);
+        let leq = self.sum_sq / self.count.max(1) as f64;
+        SplReading {
+            level: ms_db(self.ms) + offset,
+            leq: ms_db(leq) + offset,
+            lmax: ms_db(self.max_ms) + offset,
+            dose: self.params.offset_db.map(|_| self.dose),
+            elapsed_s: self.count as f32 / self.sample_rate.max(1.0),
+        }
+    }
+}
+
+/// Per-sample smoothing while the level rises and falls.
+fn time_alphas(time: TimeWeighting, sample_rate: f32) -> (f64, f64) {
+    let (rise, fall) = time.tau_s();
+    let alpha = |tau: f32| {
+        1.0 - (-1.0 / f64::from(tau * sample_rate.max(1.0))).exp()
+    };
+    (alpha(rise), alpha(fall))
+}
+
+/// A mean square in dB relative to a full-scale sine.
+#[allow(clippy::cast_possible_truncation)]
+fn ms_db(ms: f64) -> f32 {
+    if ms <= 0.0 {
+        return f32::NEG_INFINITY;
+    }
+    (10.0 * (ms / FULL_SCALE_MS).log10()) as f32
+}
@@ -0,0 +1,128 @@
+use std::f32::consts::FRAC_1_SQRT_2;
+
+use crate::filterbank::Tri;
+
+/// Power below which a channel pair counts as silent.
+const SILENCE: f32 = 1e-20;
+
+/// Mean products of a channel pair: `lr` is the cross term, `ll` and
+/// `rr` the power of each channel.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Moments {
+    pub lr: f32,
+    pub ll: f32,
+    pub rr: f32,
+}
+
+impl Moments {
+    /// Moments over the common length of `left` and `right`.
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn measure(left: &[f32], right: &[f32]) -> Self {
+        let n = left.len().min(right.len());
+        if n == 0 {
+            return Self::default();
+        }
+
+        let (mut lr, mut ll, mut rr) = (0.0f64, 0.0f64, 0.0f64);
+        for (&l, &r) in left.iter().zip(right) {
+            let (l, r) = (f64::from(l), f64::from(r));
+            lr = l.mul_add(r, lr);
+            ll = l.mul_add(l, ll);
+            rr = r.mul_add(r, rr);
+        }
+
+        let n = n as f64;
+        #[allow(clippy::cast_possible_truncation)]
+        Self {
+            lr: (lr / n) as f32,
+            ll: (ll / n) as f32,
+            rr: (rr / n) as f32,
+        }
+    }
+
+    /// Phase correlation in `-1..=1`: `+1` for mono, `0` for
+    /// unrelated channels and `-1` for one channel inverted.
+    #[must_use]
+    pub fn correlation(&self) -> f32 {
+        let norm = (self.ll * self.rr).sqrt();
+        if norm <= SILENCE {
+            return 0.0;
+        }
+        (self.lr / norm).clamp(-1.0, 1.0)
+    }
+
+    /// Balance in `-1..=1`, negative when the left channel is
+    /// louder.
+    #[must_use]
+    pub fn balance(&self) -> f32 {
+        power_balance(self.ll, self.rr)
+    }
+
+    /// Right over left power in dB, `0` when silent.
+    #[must_use]
+    pub fn balance_db(&self) -> f32 {
+        if self.ll + self.rr <= SILENCE {
+            return 0.0;
+        }
+        10.0 * ((self.rr + SILENCE) / (self.ll + SILENCE)).log10()
+    }
+}
+
+/// Phase correlation of two channels, see [`Moments::correlation`].
+#[must_use]
+pub fn correlation(left: &[f32], right: &[f32]) -> f32 {
+    Moments::measure(left, right).correlation()
+}
+
+/// Balance of two channels, see [`Moments::balance`].
+#[must_use]
+pub fn balance(left: &[f32], right: &[f32]) -> f32 {
+    Moments::measure(left, right).balance()
+}
+
+/// Balance of each band in `filters` from the power spectra of the
+/// two channels, one value per filter in `out`.
+pub fn band_balance(
+    filters: &[Tri],
+    left_pow: &[f32],
+    right_pow: &[f32],
+    out: &mut Vec<f32>,
+) {
+    out.clear();
+    out.extend(filters.iter().map(|tri| {
+        let (l, r) = band_power(tri, left_pow, right_pow);
+        power_balance(l, r)
+    }));
+}
+
+/// Power of both channels through one filter.
+#[must_use]
+pub fn band_power(
+    tri: &Tri,
+    left_pow: &[f32],
+    right_pow: &[f32],
+) -> (f32, f32) {
+    tri.taps.iter().fold((0.0, 0.0), |(l, r), &(i, w)| {
+        (
+            left_pow.get(i).copied().unwrap_or(0.0).mul_add(w, l),
+            right_pow.get(i).copied().unwrap_or(0.0).mul_add(w, r),
+        )
+    })
+}
+
+/// Where a sample pair lands on a goniometer: `x` is the side signal,
+/// with the left channel alone on the upper left diagonal, and `y` is
+/// the mid signal.
+#[must_use]
+pub fn goniometer_point(l: f32, r: f32) -> (f32, f32) {
+    ((r - l) * FRAC_1_SQRT_2, (l + r) * FRAC_1_SQRT_2)
+}
+
+fn power_balance(l: f32, r: f32) -> f32 {
+    let total = l + r;
+    if total <= SILENCE {
+        return 0.0;
+    }
+    ((r - l) / total).clamp(-1.0, 1.0)
+}
@@ -0,0 +1,119 @@
+mod field;
+
+pub use field::{
+    Moments, balance, band_balance, band_power, correlation,
+    goniometer_point,
+};
+
+use crate::{dsp::ema_tc, filterbank::Tri};
+
+/// Memory of the correlation and balance readings.
+pub const STEREO_TAU_S: f32 = 0.3;
+
+/// The power spectra of a channel pair and the bands to split them
+/// into.
+pub struct StereoSpectra<'a> {
+    pub filters: &'a [Tri],
+    pub left_pow: &'a [f32],
+    pub right_pow: &'a [f32],
+}
+
+/// Smoothed correlation and balance of a channel pair, overall and
+/// per band.
+///
+/// The channel moments are averaged before the ratios are taken, so
+/// quiet frames weigh less than loud ones.
+pub struct StereoMeter {
+    moments: Moments,
+    band_power: Vec<(f32, f32)>,
+    bands: Vec<f32>,
+}
+
+impl Default for StereoMeter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl StereoMeter {
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            moments: Moments {
+                lr: 0.0,
+                ll: 0.0,
+                rr: 0.0,
+            },
+            band_power: Vec::new(),
+            bands: Vec::new(),
+        }
+    }
+
+    pub fn process(&mut self, left: &[f32], right: &[f32], dt: f32) {
+        let m = Moments::measure(left, right);
+        let avg =
+            |prev: f32, x: f32| ema_tc(prev, x, STEREO_TAU_S, dt);
+        self.moments = Moments {
+            lr: avg(self.moments.lr, m.lr),
+            ll: avg(self.moments.ll, m.ll),
+            rr: avg(self.moments.rr, m.rr),
+        };
+    }
+
+    /// Updates the balance of each band in `spectra.filters` from the
+    /// power spectra of the two channels.
+    pub fn process_bands(
+        &mut self,
+        spectra: &StereoSpectra,
+        dt: f32,
+    ) {
+        let filters = spectra.filters;
+        if self.band_power.len() != filters.len() {
+            self.band_power = vec![(0.0, 0.0); filters.len()];
+        }
+
+        self.bands.clear();
+        for (acc, tri) in self.band_power.iter_mut().zip(filters) {
+            let (l, r) = field::band_power(
+                tri,
+                spectra.left_pow,
+                spectra.right_pow,
+            );
+            acc.0 = ema_tc(acc.0, l, STEREO_TAU_S, dt);
+            acc.1 = ema_tc(acc.1, r, STEREO_TAU_S, dt);
+            self.bands.push(
+                Moments {
+                    lr: 0.0,
+                    ll: acc.0,
+                    rr: acc.1,
+                }
+                .balance(),
+            );
+        }
+    }
+
+    #[must_use]
+    pub fn correlation(&self) -> f32 {
+        self.moments.correlation()
+    }
+
+    #[must_use]
+    pub fn balance(&self) -> f32 {
+        self.moments.balance()
+    }
+
+    #[must_use]
+    pub fn balance_db(&self) -> f32 {
+        self.moments.balance_db()
+    }
+
+    /// Balance of each band, lowest first.
+    #[must_use]
+    pub fn bands(&self) -> &[f32] {
+        &self.bands
+    }
+
+    pub fn reset(&mut self) {
+        *self = Self::new();
+    }
+}
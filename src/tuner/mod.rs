@@ -0,0 +1,55 @@
+mod temperament;
+mod yin;
+
+pub use temperament::{NoteReading, Temperament, Tuning};
+pub use yin::{PitchEstimate, Yin, YinParams};
+
+/// Pitch detector over a sliding window of recent samples.
+///
+/// Push audio as it arrives and call [`Tuner::estimate`] whenever a
+/// reading is wanted. The window covers two periods of the lowest
+/// detectable pitch.
+pub struct Tuner {
+    yin: Yin,
+    window: Vec<f32>,
+    sample_rate: f32,
+}
+
+impl Tuner {
+    #[must_use]
+    pub fn new(params: YinParams, sample_rate: f32) -> Self {
+        Self {
+            yin: Yin::new(params),
+            window: vec![0.0; params.window_len(sample_rate)],
+            sample_rate,
+        }
+    }
+
+    #[must_use]
+    pub const fn sample_rate(&self) -> f32 {
+        self.sample_rate
+    }
+
+    pub fn push(&mut self, samples: &[f32]) {
+        let len = self.window.len();
+        let src = samples
+            .get(samples.len().saturating_sub(len)..)
+            .unwrap_or(samples);
+        let n = src.len();
+
+        self.window.copy_within(n.., 0);
+        if let Some(dst) =
+            self.window.get_mut(len.saturating_sub(n)..)
+        {
+            dst.copy_from_slice(src);
+        }
+    }
+
+    pub fn estimate(&mut self) -> Option<PitchEstimate> {
+        self.yin.detect(&self.window, self.sample_rate)
+    }
+
+    pub fn reset(&mut self) {
+        self.window.fill(0.0);
+    }
+}
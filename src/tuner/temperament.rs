@@ -0,0 +1,180 @@
+use serde::Deserialize;
+
+use crate::chroma::{A4_HZ, PITCH_CLASSES, midi_note};
+
+/// Pitch class of A.
+const A: usize = 9;
+
+const JUST_RATIOS: [f64; 12] = [
+    1.0,
+    16.0 / 15.0,
+    9.0 / 8.0,
+    6.0 / 5.0,
+    5.0 / 4.0,
+    4.0 / 3.0,
+    45.0 / 32.0,
+    3.0 / 2.0,
+    8.0 / 5.0,
+    5.0 / 3.0,
+    9.0 / 5.0,
+    15.0 / 8.0,
+];
+
+const PYTHAGOREAN_RATIOS: [f64; 12] = [
+    1.0,
+    256.0 / 243.0,
+    9.0 / 8.0,
+    32.0 / 27.0,
+    81.0 / 64.0,
+    4.0 / 3.0,
+    729.0 / 512.0,
+    3.0 / 2.0,
+    128.0 / 81.0,
+    27.0 / 16.0,
+    16.0 / 9.0,
+    243.0 / 128.0,
+];
+
+/// Werckmeister III, in cents from equal temperament.
+const WERCKMEISTER_CENTS: [f32; 12] = [
+    0.0, -9.775, -7.82, -5.865, -9.775, -1.955, -11.73, -3.91, -7.82,
+    -11.73, -3.91, -7.82,
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Temperament {
+    Equal,
+    Just,
+    Pythagorean,
+    Werckmeister,
+}
+
+impl Temperament {
+    /// Deviation from equal temperament of each interval above the
+    /// root, in cents.
+    #[must_use]
+    pub fn offsets(self) -> [f32; 12] {
+        match self {
+            Self::Equal => [0.0; 12],
+            Self::Just => ratio_offsets(&JUST_RATIOS),
+            Self::Pythagorean => ratio_offsets(&PYTHAGOREAN_RATIOS),
+            Self::Werckmeister => WERCKMEISTER_CENTS,
+        }
+    }
+}
+
+#[allow(
+    clippy::cast_precision_loss,
+    clippy::cast_possible_truncation
+)]
+fn ratio_offsets(ratios: &[f64; 12]) -> [f32; 12] {
+    let mut out = [0.0f32; 12];
+    for (i, (o, r)) in out.iter_mut().zip(ratios).enumerate() {
+        *o = 1200.0f64.mul_add(r.log2(), -100.0 * i as f64) as f32;
+    }
+    out
+}
+
+/// The note nearest to a measured frequency.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct NoteReading {
+    /// MIDI note number, A4 = 69.
+    pub midi: i32,
+    pub pitch_class: usize,
+    pub octave: i32,
+    /// Deviation from `target_hz`; positive is sharp.
+    pub cents: f32,
+    pub target_hz: f32,
+}
+
+impl NoteReading {
+    #[must_use]
+    pub fn name(&self) -> &'static str {
+        PITCH_CLASSES.get(self.pitch_class).copied().unwrap_or("?")
+    }
+}
+
+/// Reference pitch and temperament the tuner measures against.
+///
+/// Tempered offsets are taken relative to `root` and then shifted so
+/// that A always sounds at `a4_hz`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Tuning {
+    pub a4_hz: f32,
+    pub temperament: Temperament,
+    pub root: usize,
+}
+
+impl Tuning {
+    pub const DEFAULT: Self = Self {
+        a4_hz: A4_HZ,
+        temperament: Temperament::Equal,
+        root: 0,
+    };
+
+    /// Offset of `pitch_class` from equal temperament, in cents.
+    #[must_use]
+    #[allow(clippy::arithmetic_side_effects)]
+    pub fn offset_cents(&self, pitch_class: usize) -> f32 {
+        let offsets = self.temperament.offsets();
+        let at = |pc: usize| {
+            offsets
+                .get((pc % 12 + 12 - self.root % 12) % 12)
+                .copied()
+                .unwrap_or(0.0)
+        };
+        at(pitch_class) - at(A)
+    }
+
+    #[must_use]
+    #[allow(
+        clippy::cast_precision_loss,
+        clippy::cast_sign_loss,
+        clippy::arithmetic_side_effects
+    )]
+    pub fn target_hz(&self, midi: i32) -> f32 {
+        let pc = midi.rem_euclid(12) as usize;
+        let cents = 100.0f32
+            .mul_add((midi - 69) as f32, self.offset_cents(pc));
+        self.a4_hz * (cents / 1200.0).exp2()
+    }
+
+    /// Nearest tempered note to `hz`, or `None` for non-positive
+    /// input.
+    #[must_use]
+    #[allow(
+        clippy::cast_possible_truncation,
+        clippy::cast_sign_loss,
+        clippy::arithmetic_side_effects
+    )]
+    pub fn nearest(&self, hz: f32) -> Option<NoteReading> {
+        if !(hz > 0.0 && hz.is_finite()) {
+            return None;
+        }
+
+        let equal = 12.0f32
+            .mul_add(-(self.a4_hz / A4_HZ).log2(), midi_note(hz));
+        let centre = equal.round() as i32;
+
+        (centre - 1..=centre + 1)
+            .map(|midi| {
+                let target_hz = self.target_hz(midi);
+                let cents = 1200.0 * (hz / target_hz).log2();
+                NoteReading {
+                    midi,
+                    pitch_class: midi.rem_euclid(12) as usize,
+                    octave: midi.div_euclid(12) - 1,
+                    cents,
+                    target_hz,
+                }
+            })
+            .min_by(|a, b| a.cents.abs().total_cmp(&b.cents.abs()))
+    }
+}
+
+impl Default for Tuning {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
@@ -0,0 +1,173 @@
+/// Search range and voicing threshold for [`Yin`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct YinParams {
+    pub fmin_hz: f32,
+    pub fmax_hz: f32,
+    /// Largest normalised difference still accepted as periodic.
+    pub threshold: f32,
+}
+
+impl YinParams {
+    pub const DEFAULT: Self = Self {
+        fmin_hz: 30.0,
+        fmax_hz: 2_000.0,
+        threshold: 0.15,
+    };
+
+    /// Samples needed to cover two periods of `fmin_hz`.
+    #[must_use]
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    pub fn window_len(&self, sample_rate: f32) -> usize {
+        (2.0 * sample_rate / self.fmin_hz.max(1.0)).ceil() as usize
+    }
+}
+
+impl Default for YinParams {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+/// Fundamental frequency and how periodic the signal was, in `0..=1`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PitchEstimate {
+    pub hz: f32,
+    pub clarity: f32,
+}
+
+/// Signals quieter than this mean square are treated as unvoiced.
+const SILENCE_POWER: f32 = 1e-8;
+
+/// YIN fundamental-frequency estimator (de Cheveigné and Kawahara,
+/// 2002), keeping its difference buffer between calls.
+pub struct Yin {
+    params: YinParams,
+    diff: Vec<f32>,
+}
+
+impl Yin {
+    #[must_use]
+    pub const fn new(params: YinParams) -> Self {
+        Self {
+            params,
+            diff: Vec::new(),
+        }
+    }
+
+    #[must_use]
+    pub const fn params(&self) -> &YinParams {
+        &self.params
+    }
+
+    /// Estimates the pitch of `samples`, or `None` when nothing
+    /// periodic is found in the search range.
+    #[allow(
+        clippy::cast_precision_loss,
+        clippy::cast_possible_truncation,
+        clippy::cast_sign_loss
+    )]
+    pub fn detect(
+        &mut self,
+        samples: &[f32],
+        sample_rate: f32,
+    ) -> Option<PitchEstimate> {
+        let n = samples.len();
+        let tau_min = ((sample_rate / self.params.fmax_hz).floor()
+            as usize)
+            .max(2);
+        let tau_max = ((sample_rate / self.params.fmin_hz).ceil()
+            as usize)
+            .min(n / 2);
+        if tau_max <= tau_min.saturating_add(1) {
+            return None;
+        }
+
+        let power =
+            samples.iter().map(|&x| x * x).sum::<f32>() / n as f32;
+        if power < SILENCE_POWER {
+            return None;
+        }
+
+        self.difference(samples, tau_max);
+        let tau = self.pick(tau_min, tau_max)?;
+        let refined = self.refine(tau);
+        let clarity =
+            1.0 - self.diff.get(tau).copied().unwrap_or(1.0);
+
+        Some(PitchEstimate {
+            hz: sample_rate / refined,
+            clarity: clarity.clamp(0.0, 1.0),
+        })
+    }
+
+    /// Squared difference function, normalised by its cumulative
+    /// mean in place.
+    #[allow(clippy::cast_precision_loss)]
+    fn difference(&mut self, samples: &[f32], tau_max: usize) {
+        let w = samples.len().saturating_sub(tau_max);
+        self.diff.clear();
+        self.diff.resize(tau_max.saturating_add(1), 0.0);
+
+        let head = samples.get(..w).unwrap_or(&[]);
+        for (tau, d) in self.diff.iter_mut().enumerate().skip(1) {
+            let lagged = samples.get(tau..).unwrap_or(&[]);
+            *d = head
+                .iter()
+                .zip(lagged)
+                .map(|(&a, &b)| (a - b) * (a - b))
+                .sum();
+        }
+
+        let mut running = 0.0f32;
+        if let Some(d0) = self.diff.first_mut() {
+            *d0 = 1.0;
+        }
+        for (tau, d) in self.diff.iter_mut().enumerate().skip(1) {
+            running += *d;
+            *d = if running > 0.0 {
+                *d * tau as f32 / running
+            } else {
+                1.0
+            };
+        }
+    }
+
+    /// First dip below the threshold, followed down to its minimum.
+    fn pick(&self, tau_min: usize, tau_max: usize) -> Option<usize> {
+        let threshold = self.params.threshold;
+        let mut tau = (tau_min..tau_max).find(|&t| {
+            self.diff.get(t).is_some_and(|&d| d < threshold)
+        })?;
+
+        while let (Some(&here), Some(&next)) =
+            (self.diff.get(tau), self.diff.get(tau.saturating_add(1)))
+        {
+            if next >= here || tau.saturating_add(1) >= tau_max {
+                break;
+            }
+            tau = tau.saturating_add(1);
+        }
+        Some(tau)
+    }
+
+    /// Parabolic interpolation of the dip around `tau`.
+    #[allow(clippy::cast_precision_loss)]
+    fn refine(&self, tau: usize) -> f32 {
+        let at = |t: usize| self.diff.get(t).copied();
+        let (Some(a), Some(b), Some(c)) = (
+            at(tau.saturating_sub(1)),
+            at(tau),
+            at(tau.saturating_add(1)),
+        ) else {
+            return tau as f32;
+        };
+
+        let den = 2.0f32.mul_add(-b, a + c);
+        let delta = if den.abs() > 1e-12 {
+            (0.5 * (a - c) / den).clamp(-0.5, 0.5)
+        } else {
+            0.0
+        };
+        tau as f32 + delta
+    }
+}
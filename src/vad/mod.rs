@@ -0,0 +1,164 @@
+use crate::features::{spectral_flatness, zero_crossing_rate};
+
+/// Thresholds for telling speech from other sound.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct VadParams {
+    /// Band holding most of the energy of voiced speech.
+    pub speech_lo_hz: f32,
+    pub speech_hi_hz: f32,
+    /// Least share of the power that must fall in the speech band.
+    pub min_speech_ratio: f32,
+    /// Flatness of the speech band above which it counts as noise.
+    /// Voiced speech is a series of harmonics and far from flat.
+    pub max_flatness: f32,
+    /// Highest zero-crossing frequency, in Hz, of voiced speech.
+    pub max_zcr_hz: f32,
+    /// How long speech must last before the gate opens.
+    pub onset_s: f32,
+    /// How long the gate stays open after the last speech, so word
+    /// endings and unvoiced consonants are kept.
+    pub hangover_s: f32,
+}
+
+impl VadParams {
+    pub const DEFAULT: Self = Self {
+        speech_lo_hz: 300.0,
+        speech_hi_hz: 3400.0,
+        min_speech_ratio: 0.5,
+        max_flatness: 0.3,
+        max_zcr_hz: 2500.0,
+        onset_s: 0.05,
+        hangover_s: 0.3,
+    };
+}
+
+impl Default for VadParams {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+/// A power spectrum of `bin_hz` wide bins and the samples it was
+/// taken from.
+pub struct VadInput<'a> {
+    pub spec_pow: &'a [f32],
+    pub bin_hz: f32,
+    pub samples: &'a [f32],
+    pub sample_rate: f32,
+}
+
+/// What the detector looks at in one frame.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct VadFeatures {
+    /// Share of the power in the speech band, in `0..=1`.
+    pub speech_ratio: f32,
+    /// Spectral flatness of the speech band, in `0..=1`.
+    pub flatness: f32,
+    /// Half the zero crossings per second: the frequency of a sine
+    /// that would cross as often.
+    pub zcr_hz: f32,
+}
+
+impl VadFeatures {
+    /// Measures one frame of `input`.
+    #[must_use]
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    pub fn measure(input: &VadInput, params: &VadParams) -> Self {
+        let VadInput {
+            spec_pow,
+            bin_hz,
+            samples,
+            sample_rate,
+        } = *input;
+        let bin = |hz: f32| {
+            ((hz / bin_hz.max(1e-6)).round().max(1.0) as usize)
+                .min(spec_pow.len())
+        };
+        let (lo, hi) =
+            (bin(params.speech_lo_hz), bin(params.speech_hi_hz));
+        let speech = spec_pow.get(lo..hi).unwrap_or_default();
+        let total = spec_pow.iter().skip(1).sum::<f32>();
+
+        Self {
+            speech_ratio: if total > 0.0 {
+                (speech.iter().sum::<f32>() / total).min(1.0)
+            } else {
+                0.0
+            },
+            flatness: spectral_flatness(speech),
+            zcr_hz: zero_crossing_rate(samples) * sample_rate * 0.5,
+        }
+    }
+
+    /// Whether the frame looks like voiced speech.
+    #[must_use]
+    pub fn voice_like(&self, params: &VadParams) -> bool {
+        self.speech_ratio >= params.min_speech_ratio
+            && self.flatness <= params.max_flatness
+            && self.zcr_hz <= params.max_zcr_hz
+    }
+}
+
+/// Voice activity over time: opens once speech has lasted
+/// [`VadParams::onset_s`] and closes [`VadParams::hangover_s`] after
+/// it stops.
+#[derive(Debug, Clone, PartialEq)]
+pub struct VoiceDetector {
+    params: VadParams,
+    voiced_s: f32,
+    quiet_s: f32,
+    active: bool,
+}
+
+impl VoiceDetector {
+    #[must_use]
+    pub const fn new(params: VadParams) -> Self {
+        Self {
+            params,
+            voiced_s: 0.0,
+            quiet_s: 0.0,
+            active: false,
+        }
+    }
+
+    #[must_use]
+    pub const fn params(&self) -> &VadParams {
+        &self.params
+    }
+
+    /// Takes one frame lasting `dt_s`; `loud` says whether it is above
+    /// the level gate. Returns whether voice is active.
+    pub fn process(
+        &mut self,
+        features: &VadFeatures,
+        loud: bool,
+        dt_s: f32,
+    ) -> bool {
+        if loud && features.voice_like(&self.params) {
+            self.voiced_s += dt_s;
+            self.quiet_s = 0.0;
+            if self.voiced_s >= self.params.onset_s {
+                self.active = true;
+            }
+        } else {
+            self.quiet_s += dt_s;
+            if !self.active || self.quiet_s >= self.params.hangover_s
+            {
+                self.active = false;
+                self.voiced_s = 0.0;
+            }
+        }
+        self.active
+    }
+
+    #[must_use]
+    pub const fn active(&self) -> bool {
+        self.active
+    }
+
+    pub const fn reset(&mut self) {
+        self.voiced_s = 0.0;
+        self.quiet_s = 0.0;
+        self.active = false;
+    }
+}
@@ -1,4 +1,7 @@
-use lookas::analyzer::{FlowSpringParams, SpectrumAnalyzer};
+use lookas::analyzer::{
+    BandParams, DynamicsParams, FlowSpringParams, PhysicsModel,
+    ScalingMode, ScalingParams, SmoothingParams, SpectrumAnalyzer,
+};
 use lookas::filterbank::build_filterbank;
 
 // ---------------------------------------------------------------------------
@@ -12,6 +15,17 @@ fn make_analyzer(bins: usize) -> SpectrumAnalyzer {
 const DT: f32 = 1.0 / 60.0; // 60 fps frame time
 const TAU: f32 = 0.06; // default tau_spec from config
 
+const fn smoothing(attack_tau: f32, tilt: f32) -> SmoothingParams {
+    SmoothingParams {
+        attack_tau,
+        release_tau: TAU,
+        tilt,
+        bin_hz: 48_000.0 / 2048.0,
+    }
+}
+
+const INSTANT: SmoothingParams = smoothing(0.0, 0.0);
+
 // ---------------------------------------------------------------------------
 // update_spectrum -- asymmetric attack / release
 // ---------------------------------------------------------------------------
@@ -22,7 +36,7 @@ fn update_spectrum_instant_attack() {
     sa.spec_pow_smooth = vec![0.01, 0.01, 0.01, 0.01];
 
     let high_pow = vec![1.0f32, 1.0, 1.0, 1.0];
-    sa.update_spectrum(&high_pow, TAU, DT);
+    sa.update_spectrum(&high_pow, &INSTANT, DT);
 
     for (i, &v) in sa.spec_pow_smooth.iter().enumerate() {
         assert!(
@@ -38,7 +52,7 @@ fn update_spectrum_smooth_release() {
     sa.spec_pow_smooth = vec![1.0, 1.0, 1.0, 1.0];
 
     let low_pow = vec![0.0f32; 4];
-    sa.update_spectrum(&low_pow, TAU, DT);
+    sa.update_spectrum(&low_pow, &INSTANT, DT);
 
     for (i, &v) in sa.spec_pow_smooth.iter().enumerate() {
         assert!(
@@ -48,10 +62,72 @@ fn update_spectrum_smooth_release() {
     }
 }
 
+#[test]
+fn update_spectrum_attack_tau_smooths_rise() {
+    let mut sa = make_analyzer(4);
+    sa.spec_pow_smooth = vec![0.0; 4];
+
+    sa.update_spectrum(&[1.0; 4], &smoothing(0.02, 0.0), DT);
+
+    for (i, &v) in sa.spec_pow_smooth.iter().enumerate() {
+        assert!(
+            v > 0.0 && v < 1.0,
+            "bin {i}: attack should not snap, got {v}"
+        );
+    }
+}
+
+#[test]
+fn update_spectrum_tilt_scales_with_frequency() {
+    let bins = 1024;
+    let mut bass_heavy = make_analyzer(bins);
+    let mut treble_heavy = make_analyzer(bins);
+    bass_heavy.spec_pow_smooth.fill(1.0);
+    treble_heavy.spec_pow_smooth.fill(1.0);
+
+    let low = vec![0.0f32; bins];
+    bass_heavy.update_spectrum(&low, &smoothing(0.0, 1.0), DT);
+    treble_heavy.update_spectrum(&low, &smoothing(0.0, -1.0), DT);
+
+    let (lo, hi) = (2, 800);
+    let get = |sa: &SpectrumAnalyzer, i: usize| {
+        sa.spec_pow_smooth.get(i).copied().unwrap_or(0.0)
+    };
+    assert!(
+        get(&bass_heavy, lo) > get(&bass_heavy, hi),
+        "positive tilt should hold bass longer"
+    );
+    assert!(
+        get(&treble_heavy, hi) > get(&treble_heavy, lo),
+        "negative tilt should hold treble longer"
+    );
+}
+
 // ---------------------------------------------------------------------------
 // analyze_bands
 // ---------------------------------------------------------------------------
 
+const DYN: DynamicsParams = DynamicsParams::DEFAULT;
+
+const fn bands(
+    scaling: &ScalingParams,
+    dynamics: &DynamicsParams,
+) -> BandParams {
+    BandParams {
+        scaling: *scaling,
+        dynamics: *dynamics,
+    }
+}
+const AUTO: ScalingParams = scaling(ScalingMode::Auto);
+
+const fn scaling(mode: ScalingMode) -> ScalingParams {
+    ScalingParams {
+        mode,
+        floor_db: -90.0,
+        ceiling_db: -20.0,
+    }
+}
+
 fn make_analyzer_with_filters(
     sr: f32,
     fft_size: usize,
@@ -74,7 +150,7 @@ fn make_analyzer_with_filters(
 #[test]
 fn analyze_bands_updates_target_length() {
     let mut sa = make_analyzer_with_filters(44_100.0, 2048, 32);
-    sa.analyze_bands(DT, true);
+    sa.analyze_bands(&bands(&AUTO, &DYN), DT, true);
     assert_eq!(sa.bars_target.len(), 32);
 }
 
@@ -83,10 +159,10 @@ fn analyze_bands_gate_closed_sets_zeros() {
     let mut sa = make_analyzer_with_filters(44_100.0, 2048, 16);
     // pump some energy in
     sa.spec_pow_smooth.fill(1.0);
-    sa.analyze_bands(DT, true);
+    sa.analyze_bands(&bands(&AUTO, &DYN), DT, true);
 
     // now close the gate
-    sa.analyze_bands(DT, false);
+    sa.analyze_bands(&bands(&AUTO, &DYN), DT, false);
     for (i, &v) in sa.bars_target.iter().enumerate() {
         assert!(
             v.abs() < f32::EPSILON,
@@ -101,7 +177,7 @@ fn analyze_bands_outputs_in_unit_range() {
     sa.spec_pow_smooth.fill(0.01);
 
     for _ in 0..30 {
-        sa.analyze_bands(DT, true);
+        sa.analyze_bands(&bands(&AUTO, &DYN), DT, true);
         for (i, &v) in sa.bars_target.iter().enumerate() {
             assert!(
                 (0.0..=1.0).contains(&v),
@@ -111,15 +187,186 @@ fn analyze_bands_outputs_in_unit_range() {
     }
 }
 
+// ---------------------------------------------------------------------------
+// scaling modes
+// ---------------------------------------------------------------------------
+
+fn settled_mean_target(
+    params: &ScalingParams,
+    pow: f32,
+    frames: usize,
+) -> f32 {
+    let mut sa = make_analyzer_with_filters(44_100.0, 2048, 24);
+    sa.spec_pow_smooth.fill(pow);
+    for _ in 0..frames {
+        sa.analyze_bands(&bands(params, &DYN), DT, true);
+    }
+    #[allow(clippy::cast_precision_loss)]
+    let n = sa.bars_target.len() as f32;
+    sa.bars_target.iter().sum::<f32>() / n
+}
+
+#[test]
+fn auto_scaling_hides_absolute_level() {
+    // 10 s of steady input lets eq_ref and the percentiles settle
+    let quiet = settled_mean_target(&AUTO, 1e-10, 600);
+    let loud = settled_mean_target(&AUTO, 1e-4, 600);
+    assert!(
+        (quiet - loud).abs() < 0.05,
+        "auto scaling should normalise level away: {quiet} vs {loud}"
+    );
+}
+
+#[test]
+fn absolute_scaling_shows_level() {
+    let params = scaling(ScalingMode::Absolute);
+    let quiet = settled_mean_target(&params, 1e-10, 600);
+    let loud = settled_mean_target(&params, 1e-4, 600);
+    assert!(
+        loud > quiet + 0.3,
+        "louder input should give taller bars: {quiet} vs {loud}"
+    );
+}
+
+#[test]
+fn absolute_scaling_uses_fixed_bounds_and_skips_eq_ref() {
+    let params = scaling(ScalingMode::Absolute);
+    let mut sa = make_analyzer_with_filters(44_100.0, 2048, 16);
+    sa.spec_pow_smooth.fill(1e-3);
+    for _ in 0..60 {
+        sa.analyze_bands(&bands(&params, &DYN), DT, true);
+    }
+
+    assert!((sa.db_low - params.floor_db).abs() < f32::EPSILON);
+    assert!((sa.db_high - params.ceiling_db).abs() < f32::EPSILON);
+    for (i, &eq) in sa.eq_ref.iter().enumerate() {
+        assert!(
+            (eq - 1e-6).abs() < f32::EPSILON,
+            "band {i}: eq_ref should be untouched, got {eq}"
+        );
+    }
+}
+
+#[test]
+fn absolute_full_scale_clamps_to_top() {
+    let params = scaling(ScalingMode::Absolute);
+    let top = settled_mean_target(&params, 1.0, 1);
+    assert!(
+        (top - 1.0).abs() < 1e-6,
+        "input above the ceiling should fill the bar, got {top}"
+    );
+}
+
+#[test]
+fn hybrid_scaling_adapts_slowly() {
+    let params = scaling(ScalingMode::Hybrid);
+    let mut auto = make_analyzer_with_filters(44_100.0, 2048, 24);
+    let mut hybrid = make_analyzer_with_filters(44_100.0, 2048, 24);
+    auto.spec_pow_smooth.fill(1e-9);
+    hybrid.spec_pow_smooth.fill(1e-9);
+
+    let (start_auto, start_hybrid) = (auto.db_low, hybrid.db_low);
+    for _ in 0..30 {
+        auto.analyze_bands(&bands(&AUTO, &DYN), DT, true);
+        hybrid.analyze_bands(&bands(&params, &DYN), DT, true);
+    }
+
+    let moved_auto = (auto.db_low - start_auto).abs();
+    let moved_hybrid = (hybrid.db_low - start_hybrid).abs();
+    assert!(moved_hybrid > 0.0, "hybrid range should still adapt");
+    assert!(
+        moved_hybrid < 0.2 * moved_auto,
+        "hybrid should adapt much slower: auto {moved_auto}, hybrid {moved_hybrid}"
+    );
+}
+
+#[test]
+fn hybrid_range_stays_within_bounds() {
+    let params = scaling(ScalingMode::Hybrid);
+    let mut sa = make_analyzer_with_filters(44_100.0, 2048, 24);
+    sa.spec_pow_smooth.fill(1e-16);
+    for _ in 0..6000 {
+        sa.analyze_bands(&bands(&params, &DYN), DT, true);
+    }
+    assert!(sa.db_low >= params.floor_db);
+    assert!(sa.db_high <= params.ceiling_db);
+}
+
+#[test]
+fn dynamics_custom_percentiles_move_range() {
+    let wide = DynamicsParams {
+        percentile_low: 0.0,
+        percentile_high: 1.0,
+        ..DYN
+    };
+    let mut narrow_sa =
+        make_analyzer_with_filters(44_100.0, 2048, 24);
+    let mut wide_sa = make_analyzer_with_filters(44_100.0, 2048, 24);
+    for (i, (a, b)) in narrow_sa
+        .spec_pow_smooth
+        .iter_mut()
+        .zip(wide_sa.spec_pow_smooth.iter_mut())
+        .enumerate()
+    {
+        #[allow(clippy::cast_precision_loss)]
+        let v = 1e-8 * (1.0 + (i % 7) as f32);
+        *a = v;
+        *b = v;
+    }
+
+    for _ in 0..300 {
+        narrow_sa.analyze_bands(&bands(&AUTO, &DYN), DT, true);
+        wide_sa.analyze_bands(&bands(&AUTO, &wide), DT, true);
+    }
+
+    assert!(
+        wide_sa.db_high - wide_sa.db_low
+            > narrow_sa.db_high - narrow_sa.db_low,
+        "extreme percentiles should widen the tracked range"
+    );
+}
+
+#[test]
+fn dynamics_min_range_compresses_bars() {
+    let params = scaling(ScalingMode::Absolute);
+    let roomy = DynamicsParams {
+        min_range_db: 60.0,
+        ..DYN
+    };
+    let mut sa = make_analyzer_with_filters(44_100.0, 2048, 24);
+    let mut roomy_sa = make_analyzer_with_filters(44_100.0, 2048, 24);
+    let narrow = ScalingParams {
+        floor_db: -60.0,
+        ceiling_db: -50.0,
+        ..params
+    };
+    sa.spec_pow_smooth.fill(1e-7);
+    roomy_sa.spec_pow_smooth.fill(1e-7);
+    sa.analyze_bands(&bands(&narrow, &DYN), DT, true);
+    roomy_sa.analyze_bands(&bands(&narrow, &roomy), DT, true);
+
+    let sum = sa.bars_target.iter().sum::<f32>();
+    let roomy_sum = roomy_sa.bars_target.iter().sum::<f32>();
+    assert!(
+        roomy_sum < sum,
+        "a larger minimum range should shorten bars: {sum} vs {roomy_sum}"
+    );
+}
+
 // ---------------------------------------------------------------------------
 // apply_flow_and_spring
 // ---------------------------------------------------------------------------
 
 const fn default_params() -> FlowSpringParams {
     FlowSpringParams {
+        model: PhysicsModel::Spring,
         flow_k: 0.18,
         spr_k: 60.0,
         spr_zeta: 1.0,
+        gravity: 4.0,
+        ema_tau_s: 0.08,
+        monstercat: 1.5,
+        silence_tau_s: 0.22,
     }
 }
 
@@ -181,3 +428,181 @@ fn spring_critically_damped_no_overshoot() {
         "critically damped spring should not overshoot significantly: max_y = {max_y}"
     );
 }
+
+#[test]
+fn spring_silence_tau_sets_decay_speed() {
+    let fast = FlowSpringParams {
+        silence_tau_s: 0.05,
+        ..default_params()
+    };
+    let slow = FlowSpringParams {
+        silence_tau_s: 1.0,
+        ..default_params()
+    };
+    let mut fast_sa = make_analyzer_with_filters(44_100.0, 2048, 4);
+    let mut slow_sa = make_analyzer_with_filters(44_100.0, 2048, 4);
+    fast_sa.bars_y = vec![1.0; 4];
+    slow_sa.bars_y = vec![1.0; 4];
+
+    for _ in 0..10 {
+        fast_sa.apply_flow_and_spring(&fast, DT, false);
+        slow_sa.apply_flow_and_spring(&slow, DT, false);
+    }
+
+    for (f, s) in fast_sa.bars_y.iter().zip(&slow_sa.bars_y) {
+        assert!(f < s, "shorter silence tau should decay faster");
+    }
+}
+
+// ---------------------------------------------------------------------------
+// band gates
+// ---------------------------------------------------------------------------
+
+const QUIET: f32 = 1e-10;
+
+/// 30 s, long enough for every floor to settle from `min_db`.
+const SETTLE_FRAMES: usize = 1800;
+
+fn gated_analyzer(min_db: f32) -> SpectrumAnalyzer {
+    let mut sa = make_analyzer_with_filters(44_100.0, 2048, 16);
+    sa.set_band_gating(Some(min_db));
+    sa.spec_pow_smooth.fill(QUIET);
+    sa
+}
+
+/// Runs `frames` frames of the current spectrum with the single gate
+/// closed, which band gating ignores.
+fn run_gated(sa: &mut SpectrumAnalyzer, frames: usize) {
+    for _ in 0..frames {
+        sa.analyze_bands(&bands(&AUTO, &DYN), DT, false);
+    }
+}
+
+fn fill_bins(
+    sa: &mut SpectrumAnalyzer,
+    bins: std::ops::Range<usize>,
+    v: f32,
+) {
+    if let Some(s) = sa.spec_pow_smooth.get_mut(bins) {
+        s.fill(v);
+    }
+}
+
+fn open_bands(sa: &SpectrumAnalyzer) -> Vec<bool> {
+    sa.band_gates
+        .as_ref()
+        .map(|g| g.open().to_vec())
+        .unwrap_or_default()
+}
+
+#[test]
+fn band_gates_settle_on_steady_floor() {
+    let mut sa = gated_analyzer(-150.0);
+    run_gated(&mut sa, SETTLE_FRAMES);
+    assert!(open_bands(&sa).iter().all(|&open| !open));
+    assert!(sa.bars_target.iter().all(|&t| t.abs() < f32::EPSILON));
+}
+
+#[test]
+fn steady_sound_opens_when_gating_starts() {
+    let mut sa = gated_analyzer(-150.0);
+    run_gated(&mut sa, 2);
+    assert!(open_bands(&sa).iter().all(|&open| open));
+}
+
+#[test]
+fn steady_sound_opens_after_reset() {
+    let mut sa = gated_analyzer(-150.0);
+    run_gated(&mut sa, SETTLE_FRAMES);
+    if let Some(gates) = &mut sa.band_gates {
+        gates.reset();
+    }
+    run_gated(&mut sa, 2);
+    assert!(open_bands(&sa).iter().all(|&open| open));
+}
+
+#[test]
+fn quiet_treble_shows_while_bass_is_silent() {
+    let mut sa = gated_analyzer(-150.0);
+    run_gated(&mut sa, SETTLE_FRAMES);
+    fill_bins(&mut sa, 300..700, QUIET * 1000.0);
+    run_gated(&mut sa, 2);
+
+    let open = open_bands(&sa);
+    assert!(!open.first().copied().unwrap_or(true), "bass: {open:?}");
+    assert!(
+        open.iter().rev().take(3).all(|&o| o),
+        "treble: {open:?}"
+    );
+    let first = sa.bars_target.first().copied().unwrap_or(1.0);
+    assert!(first.abs() < f32::EPSILON);
+    assert!(sa.bars_target.iter().rev().take(3).all(|&t| t > 0.0));
+}
+
+#[test]
+fn hum_settles_into_its_band_floor() {
+    let mut sa = gated_analyzer(-150.0);
+    run_gated(&mut sa, SETTLE_FRAMES);
+    fill_bins(&mut sa, 2..6, QUIET * 1e4);
+    run_gated(&mut sa, 2);
+    let open = open_bands(&sa);
+    assert!(open.iter().any(|&o| o), "hum should open at first");
+    assert!(!open.last().copied().unwrap_or(true));
+
+    // 30 s of unchanging hum.
+    run_gated(&mut sa, 1800);
+    let open = open_bands(&sa);
+    assert!(open.iter().all(|&o| !o), "hum still open: {open:?}");
+}
+
+#[test]
+fn sustained_tone_stays_open_past_floor_rise() {
+    let mut sa = gated_analyzer(-150.0);
+    run_gated(&mut sa, SETTLE_FRAMES);
+    fill_bins(&mut sa, 300..700, QUIET * 30.0);
+
+    // Held for 7 s, well past the default 4 s `band_floor_rise_s`.
+    run_gated(&mut sa, 420);
+    let open = open_bands(&sa);
+    assert!(
+        open.last().copied().unwrap_or(false),
+        "held tone closed: {open:?}"
+    );
+}
+
+#[test]
+fn band_gates_respect_min_db() {
+    let mut sa = gated_analyzer(0.0);
+    run_gated(&mut sa, SETTLE_FRAMES);
+    fill_bins(&mut sa, 300..700, QUIET * 1000.0);
+    run_gated(&mut sa, 2);
+    assert!(open_bands(&sa).iter().all(|&o| !o));
+}
+
+#[test]
+fn closed_bands_fall_while_open_bands_rise() {
+    let mut sa = gated_analyzer(-150.0);
+    run_gated(&mut sa, SETTLE_FRAMES);
+    fill_bins(&mut sa, 300..700, QUIET * 1000.0);
+    sa.bars_y.fill(0.5);
+
+    for _ in 0..60 {
+        sa.analyze_bands(&bands(&AUTO, &DYN), DT, false);
+        sa.apply_flow_and_spring(&default_params(), DT, false);
+    }
+
+    let first = sa.bars_y.first().copied().unwrap_or(1.0);
+    let last = sa.bars_y.last().copied().unwrap_or(0.0);
+    assert!(first < 0.01, "closed bass should decay, got {first}");
+    assert!(last > 0.1, "open treble should stay up, got {last}");
+}
+
+#[test]
+fn band_gating_can_be_turned_off() {
+    let mut sa = gated_analyzer(-150.0);
+    sa.set_band_gating(None);
+    assert!(sa.band_gates.is_none());
+    sa.spec_pow_smooth.fill(1.0);
+    sa.analyze_bands(&bands(&AUTO, &DYN), DT, true);
+    assert!(sa.bars_target.iter().any(|&t| t > 0.0));
+}
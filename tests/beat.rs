@@ -0,0 +1,203 @@
+mod common;
+
+use common::rng;
+use lookas::beat::{
+    BeatEvent, BeatTracker, OnsetDetector, spectral_flux,
+};
+use lookas::dsp::{hann, prepare_fft_input_inplace};
+use realfft::RealFftPlanner;
+
+// ---------------------------------------------------------------------------
+// helpers
+// ---------------------------------------------------------------------------
+
+const SR: usize = 48_000;
+const HOP: usize = SR / 60; // 60 fps frame time
+const WINDOW: usize = 1024;
+#[allow(clippy::cast_precision_loss)]
+const DT: f32 = HOP as f32 / SR as f32;
+
+/// Short decaying noise bursts at `bpm` over a quiet noise bed.
+#[allow(
+    clippy::cast_precision_loss,
+    clippy::cast_possible_truncation,
+    clippy::cast_sign_loss
+)]
+fn click_track(bpm: f32, seconds: f32) -> Vec<f32> {
+    let len = (seconds * SR as f32) as usize;
+    let period = 60.0 / bpm * SR as f32;
+    let click_len = SR / 100;
+    let mut state = 7u32;
+    let mut out: Vec<f32> =
+        (0..len).map(|_| rng(&mut state) * 0.001).collect();
+
+    let mut t = 0.0f32;
+    while (t as usize) < len {
+        let start = t as usize;
+        for (k, s) in
+            out.iter_mut().skip(start).take(click_len).enumerate()
+        {
+            let env = (-(k as f32) / (click_len as f32 / 5.0)).exp();
+            *s += rng(&mut state) * 0.8 * env;
+        }
+        t += period;
+    }
+
+    out
+}
+
+struct Spectra {
+    fft: std::sync::Arc<dyn realfft::RealToComplex<f32>>,
+    window: Vec<f32>,
+    buf: Vec<f32>,
+    out: Vec<realfft::num_complex::Complex<f32>>,
+    pow: Vec<f32>,
+}
+
+impl Spectra {
+    fn new() -> Self {
+        let fft =
+            RealFftPlanner::<f32>::new().plan_fft_forward(WINDOW);
+        let buf = fft.make_input_vec();
+        let out = fft.make_output_vec();
+        Self {
+            fft,
+            window: hann(WINDOW),
+            buf,
+            out,
+            pow: vec![0.0; WINDOW / 2],
+        }
+    }
+
+    #[allow(clippy::cast_precision_loss)]
+    fn at(&mut self, signal: &[f32], end: usize) -> &[f32] {
+        let start = end.saturating_sub(WINDOW);
+        let mut frame =
+            signal.get(start..end).unwrap_or(&[]).to_vec();
+        frame.resize(WINDOW, 0.0);
+        prepare_fft_input_inplace(
+            &frame,
+            &self.window,
+            &mut self.buf,
+        );
+        assert!(
+            self.fft.process(&mut self.buf, &mut self.out).is_ok()
+        );
+
+        let norm_inv = 1.0 / (WINDOW as f32 * WINDOW as f32);
+        for (p, c) in self.pow.iter_mut().zip(&self.out) {
+            *p = c.re.mul_add(c.re, c.im * c.im) * norm_inv;
+        }
+        &self.pow
+    }
+}
+
+fn run_tracker(signal: &[f32]) -> (BeatTracker, Vec<BeatEvent>) {
+    let mut spectra = Spectra::new();
+    let mut tracker = BeatTracker::new();
+    let mut beats = Vec::new();
+
+    for end in (WINDOW..=signal.len()).step_by(HOP) {
+        if let Some(beat) =
+            tracker.process(spectra.at(signal, end), DT)
+        {
+            beats.push(beat);
+        }
+    }
+
+    (tracker, beats)
+}
+
+// ---------------------------------------------------------------------------
+// spectral flux / onset detection
+// ---------------------------------------------------------------------------
+
+#[test]
+fn spectral_flux_ignores_decreasing_energy() {
+    let mut prev = vec![1.0f32; 8];
+    let _ = spectral_flux(&mut prev, &[1.0; 8]);
+    let flux = spectral_flux(&mut prev, &[0.1; 8]);
+    assert!(flux.abs() < f32::EPSILON, "decay produced flux {flux}");
+}
+
+#[test]
+fn spectral_flux_positive_on_rise() {
+    let mut prev = Vec::new();
+    let _ = spectral_flux(&mut prev, &[1e-6; 8]);
+    let flux = spectral_flux(&mut prev, &[1e-2; 8]);
+    assert!(flux > 0.0, "rise produced no flux");
+}
+
+#[test]
+fn onset_detector_silent_on_steady_signal() {
+    let mut det = OnsetDetector::new();
+    let steady = vec![1e-3f32; 256];
+    let fired = (0..300)
+        .filter(|_| det.process(&steady, DT).is_some())
+        .count();
+    assert_eq!(fired, 0, "steady spectrum should not trigger onsets");
+}
+
+#[test]
+#[allow(clippy::cast_precision_loss)]
+fn onset_detector_fires_once_per_click() {
+    let signal = click_track(120.0, 6.0);
+    let mut spectra = Spectra::new();
+    let mut det = OnsetDetector::new();
+
+    let mut fired = 0usize;
+    for end in (WINDOW..=signal.len()).step_by(HOP) {
+        if det.process(spectra.at(&signal, end), DT).is_some() {
+            fired += 1;
+        }
+    }
+
+    // 6 s at 120 BPM = 12 clicks
+    assert!(
+        (11..=13).contains(&fired),
+        "expected ~12 onsets, got {fired}"
+    );
+}
+
+// ---------------------------------------------------------------------------
+// tempo tracking
+// ---------------------------------------------------------------------------
+
+#[test]
+fn tempo_matches_click_tracks() {
+    for bpm in [90.0f32, 120.0, 128.0, 140.0] {
+        let (tracker, _) = run_tracker(&click_track(bpm, 12.0));
+        let got = tracker.bpm().unwrap_or(0.0);
+        assert!(
+            (got - bpm).abs() < 2.0,
+            "click track at {bpm} BPM estimated as {got}"
+        );
+    }
+}
+
+#[test]
+fn tempo_unknown_in_silence() {
+    let silence = vec![0.0f32; SR * 8];
+    let (tracker, beats) = run_tracker(&silence);
+    assert!(tracker.bpm().is_none(), "silence produced a tempo");
+    assert!(beats.is_empty(), "silence produced beat events");
+}
+
+#[test]
+fn beat_events_follow_the_tempo() {
+    let bpm = 120.0f32;
+    let (_, beats) = run_tracker(&click_track(bpm, 16.0));
+    assert!(beats.len() >= 8, "too few beats: {}", beats.len());
+
+    let period = 60.0 / bpm;
+    for w in beats.windows(2).skip(2) {
+        let (Some(a), Some(b)) = (w.first(), w.get(1)) else {
+            continue;
+        };
+        let gap = b.time_s - a.time_s;
+        assert!(
+            (gap - period).abs() < 0.05,
+            "beat spacing {gap:.3}s, expected {period:.3}s"
+        );
+    }
+}
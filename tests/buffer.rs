@@ -34,6 +34,17 @@ fn len_caps_at_capacity_after_wraparound() {
     );
 }
 
+#[test]
+fn written_counts_every_push_across_wraparound() {
+    let mut buf = SharedBuf::new(8);
+    assert_eq!(buf.written(), 0);
+    for i in 0_u8..20 {
+        buf.push(f32::from(i));
+    }
+    assert_eq!(buf.written(), 20);
+    assert_eq!(buf.len(), 8);
+}
+
 // ---------------------------------------------------------------------------
 // copy_last_n_into
 // ---------------------------------------------------------------------------
@@ -127,3 +138,72 @@ fn non_power_of_two_capacity_wraps_in_order() {
     assert!(buf.copy_last_n_into(3, &mut out));
     assert_eq!(out, vec![3.0, 4.0, 5.0]);
 }
+
+// ---------------------------------------------------------------------------
+// stereo
+// ---------------------------------------------------------------------------
+
+#[test]
+fn push_copies_mono_to_both_channels() {
+    let mut buf = SharedBuf::new(8);
+    buf.set_stereo(true);
+    for i in 0_u8..4 {
+        buf.push(f32::from(i));
+    }
+    let (mut left, mut right) = (Vec::new(), Vec::new());
+    assert!(buf.copy_last_n_stereo_into(3, &mut left, &mut right));
+    assert_eq!(left, vec![1.0, 2.0, 3.0]);
+    assert_eq!(right, left);
+}
+
+#[test]
+fn push_stereo_keeps_channels_apart_across_wraparound() {
+    let mut buf = SharedBuf::new(4);
+    buf.set_stereo(true);
+    for i in 0_u8..6 {
+        let x = f32::from(i);
+        buf.push_stereo(0.0, x, -x);
+    }
+
+    let (mut left, mut right) = (Vec::new(), Vec::new());
+    assert!(buf.copy_last_n_stereo_into(4, &mut left, &mut right));
+    assert_eq!(left, vec![2.0, 3.0, 4.0, 5.0]);
+    assert_eq!(right, vec![-2.0, -3.0, -4.0, -5.0]);
+
+    let mut mix = Vec::new();
+    assert!(buf.copy_last_n_into(4, &mut mix));
+    assert_eq!(mix, vec![0.0; 4]);
+    assert_eq!(buf.written(), 6);
+}
+
+#[test]
+fn copy_last_n_stereo_needs_enough_samples() {
+    let mut buf = SharedBuf::new(8);
+    buf.set_stereo(true);
+    buf.push_stereo(1.0, 1.0, 1.0);
+    let (mut left, mut right) = (Vec::new(), Vec::new());
+    assert!(!buf.copy_last_n_stereo_into(2, &mut left, &mut right));
+}
+
+#[test]
+fn channels_are_kept_only_on_request() {
+    let mut buf = SharedBuf::new(8);
+    assert!(!buf.is_stereo());
+    for _ in 0..8 {
+        buf.push_stereo(0.0, 1.0, -1.0);
+    }
+    let (mut left, mut right) = (Vec::new(), Vec::new());
+    assert!(!buf.copy_last_n_stereo_into(2, &mut left, &mut right));
+
+    buf.set_stereo(true);
+    buf.push_stereo(0.0, 1.0, -1.0);
+    assert!(!buf.copy_last_n_stereo_into(2, &mut left, &mut right));
+    buf.push_stereo(0.0, 2.0, -2.0);
+    assert!(buf.copy_last_n_stereo_into(2, &mut left, &mut right));
+    assert_eq!(left, vec![1.0, 2.0]);
+    assert_eq!(right, vec![-1.0, -2.0]);
+
+    buf.set_stereo(false);
+    assert!(!buf.is_stereo());
+    assert!(!buf.copy_last_n_stereo_into(2, &mut left, &mut right));
+}
@@ -0,0 +1,274 @@
+mod common;
+
+use common::{SR, power_spectrum};
+use lookas::chroma::{
+    Chromagram, Key, Mode, PITCH_CLASSES, estimate_key, fold_chroma,
+    midi_note, parse_note, tonal_peaks, tuning_offset,
+};
+
+const FFT: usize = 8192;
+#[allow(clippy::cast_precision_loss)]
+const BIN_HZ: f32 = SR / FFT as f32;
+const DT: f32 = 1.0 / 60.0;
+
+const MAJOR_SCALE: [usize; 7] = [0, 2, 4, 5, 7, 9, 11];
+const MINOR_SCALE: [usize; 7] = [0, 2, 3, 5, 7, 8, 10];
+
+// ---------------------------------------------------------------------------
+// helpers
+// ---------------------------------------------------------------------------
+
+fn note_hz(midi: f32) -> f32 {
+    440.0 * ((midi - 69.0) / 12.0).exp2()
+}
+
+#[allow(clippy::cast_precision_loss)]
+fn tones(hz: &[f32], n: usize) -> Vec<f32> {
+    (0..n)
+        .map(|i| {
+            let t = i as f32 / SR;
+            hz.iter()
+                .map(|f| (std::f32::consts::TAU * f * t).sin())
+                .sum()
+        })
+        .collect()
+}
+
+fn chroma_of(hz: &[f32]) -> [f32; 12] {
+    let mut peaks = Vec::new();
+    tonal_peaks(&power_spectrum(&tones(hz, FFT)), BIN_HZ, &mut peaks);
+    fold_chroma(&peaks, 0.0)
+}
+
+fn argmax(v: &[f32]) -> Option<usize> {
+    v.iter()
+        .enumerate()
+        .max_by(|a, b| a.1.total_cmp(b.1))
+        .map(|(i, _)| i)
+}
+
+/// Chroma with the scale degrees of `tonic` set, tonic and fifth
+/// emphasised.
+fn scale_chroma(tonic: usize, scale: &[usize]) -> [f32; 12] {
+    let mut chroma = [0.0f32; 12];
+    for (degree, &step) in scale.iter().enumerate() {
+        let weight = match degree {
+            0 => 3.0,
+            4 => 2.0,
+            _ => 1.0,
+        };
+        if let Some(c) = chroma.get_mut(tonic.wrapping_add(step) % 12)
+        {
+            *c = weight;
+        }
+    }
+    chroma
+}
+
+// ---------------------------------------------------------------------------
+// pitch
+// ---------------------------------------------------------------------------
+
+#[test]
+fn midi_note_of_a4_is_69() {
+    assert!((midi_note(440.0) - 69.0).abs() < 1e-4);
+    assert!((midi_note(261.6256) - 60.0).abs() < 1e-3);
+}
+
+#[test]
+fn note_names_parse_to_midi() {
+    assert_eq!(parse_note("A0"), Some(21));
+    assert_eq!(parse_note("C4"), Some(60));
+    assert_eq!(parse_note("c#4"), Some(61));
+    assert_eq!(parse_note("Bb3"), Some(58));
+    assert_eq!(parse_note("C8"), Some(108));
+    assert_eq!(parse_note("C-1"), Some(0));
+    assert_eq!(parse_note("Cb4"), Some(59));
+    assert_eq!(parse_note("B#3"), Some(60));
+    assert_eq!(parse_note("C"), None);
+    assert_eq!(parse_note("X4"), None);
+}
+
+#[test]
+fn pure_tone_folds_into_its_pitch_class() {
+    for (midi, pc) in [(69.0, 9), (60.0, 0), (66.0, 6), (47.0, 11)] {
+        let chroma = chroma_of(&[note_hz(midi)]);
+        assert_eq!(
+            argmax(&chroma),
+            Some(pc),
+            "note {midi}: {chroma:?}"
+        );
+    }
+}
+
+#[test]
+fn octaves_share_a_pitch_class() {
+    let chroma =
+        chroma_of(&[note_hz(57.0), note_hz(69.0), note_hz(81.0)]);
+    let total = chroma.iter().sum::<f32>();
+    let a = chroma.get(9).copied().unwrap_or(0.0);
+    assert!(a > 0.9 * total, "A should hold the energy: {chroma:?}");
+}
+
+#[test]
+fn peak_interpolation_finds_off_bin_frequency() {
+    let hz = 1_003.7;
+    let mut peaks = Vec::new();
+    tonal_peaks(
+        &power_spectrum(&tones(&[hz], FFT)),
+        BIN_HZ,
+        &mut peaks,
+    );
+    let top = peaks.iter().max_by(|a, b| a.power.total_cmp(&b.power));
+    assert!(
+        top.is_some_and(|p| (p.hz - hz).abs() < 0.1 * BIN_HZ),
+        "got {top:?}"
+    );
+}
+
+#[test]
+fn tuning_offset_measures_detuned_tones() {
+    for cents in [-30.0f32, -10.0, 0.0, 15.0, 35.0] {
+        let hz: Vec<f32> = [57.0, 64.0, 69.0, 72.0]
+            .iter()
+            .map(|&m| note_hz(m + cents / 100.0))
+            .collect();
+        let mut peaks = Vec::new();
+        tonal_peaks(
+            &power_spectrum(&tones(&hz, FFT)),
+            BIN_HZ,
+            &mut peaks,
+        );
+        let offset = tuning_offset(&peaks);
+        assert!(
+            offset.is_some_and(|o| (o - cents).abs() < 3.0),
+            "{cents} cents: got {offset:?}"
+        );
+    }
+}
+
+#[test]
+fn tuning_correction_keeps_quarter_tone_sharp_notes_in_place() {
+    let hz = [note_hz(69.4)];
+    let mut peaks = Vec::new();
+    tonal_peaks(
+        &power_spectrum(&tones(&hz, FFT)),
+        BIN_HZ,
+        &mut peaks,
+    );
+
+    let corrected = fold_chroma(&peaks, 40.0);
+    assert_eq!(argmax(&corrected), Some(9), "{corrected:?}");
+}
+
+#[test]
+fn silence_has_no_peaks() {
+    let mut peaks = Vec::new();
+    tonal_peaks(&vec![0.0; FFT / 2], BIN_HZ, &mut peaks);
+    assert!(peaks.is_empty());
+    assert!(tuning_offset(&peaks).is_none());
+}
+
+// ---------------------------------------------------------------------------
+// key
+// ---------------------------------------------------------------------------
+
+#[test]
+fn every_major_scale_is_recognised() {
+    for tonic in 0..12 {
+        let est = estimate_key(&scale_chroma(tonic, &MAJOR_SCALE));
+        let expected = Key {
+            tonic,
+            mode: Mode::Major,
+        };
+        assert!(
+            est.is_some_and(|e| e.key == expected),
+            "{}: got {est:?}",
+            PITCH_CLASSES.get(tonic).unwrap_or(&"?")
+        );
+    }
+}
+
+#[test]
+fn every_minor_scale_is_recognised() {
+    for tonic in 0..12 {
+        let est = estimate_key(&scale_chroma(tonic, &MINOR_SCALE));
+        let expected = Key {
+            tonic,
+            mode: Mode::Minor,
+        };
+        assert!(
+            est.is_some_and(|e| e.key == expected),
+            "{}: got {est:?}",
+            PITCH_CLASSES.get(tonic).unwrap_or(&"?")
+        );
+    }
+}
+
+#[test]
+fn flat_chroma_has_no_key() {
+    assert!(estimate_key(&[0.0; 12]).is_none());
+    assert!(estimate_key(&[1.0; 12]).is_none());
+}
+
+#[test]
+fn key_names_read_naturally() {
+    let key = Key {
+        tonic: 9,
+        mode: Mode::Minor,
+    };
+    assert_eq!(key.to_string(), "A minor");
+}
+
+// ---------------------------------------------------------------------------
+// Chromagram
+// ---------------------------------------------------------------------------
+
+#[test]
+fn chromagram_tracks_a_chord_progression_key() {
+    // I - IV - V - I in G major, two seconds per chord
+    let chords: [[f32; 3]; 4] = [
+        [55.0, 59.0, 62.0],
+        [60.0, 64.0, 67.0],
+        [62.0, 66.0, 69.0],
+        [55.0, 59.0, 62.0],
+    ];
+    let mut cg = Chromagram::new();
+    for chord in chords {
+        let hz: Vec<f32> =
+            chord.iter().map(|&m| note_hz(m)).collect();
+        let spec = power_spectrum(&tones(&hz, FFT));
+        for _ in 0..120 {
+            cg.process(&spec, BIN_HZ, DT);
+        }
+    }
+
+    let key = cg.key().map(|e| e.key);
+    assert_eq!(
+        key,
+        Some(Key {
+            tonic: 7,
+            mode: Mode::Major,
+        })
+    );
+    assert!(cg.tuning_cents().abs() < 3.0);
+}
+
+#[test]
+fn chromagram_display_is_normalised_and_decays() {
+    let spec = power_spectrum(&tones(&[note_hz(64.0)], FFT));
+    let mut cg = Chromagram::new();
+    for _ in 0..60 {
+        cg.process(&spec, BIN_HZ, DT);
+    }
+    let e = cg.chroma().get(4).copied().unwrap_or(0.0);
+    assert!((e - 1.0).abs() < 1e-3, "E should fill the scale: {e}");
+    assert!(cg.chroma().iter().all(|&v| (0.0..=1.0).contains(&v)));
+
+    let silence = vec![0.0; FFT / 2];
+    for _ in 0..60 {
+        cg.process(&silence, BIN_HZ, DT);
+    }
+    let after = cg.chroma().get(4).copied().unwrap_or(1.0);
+    assert!(after < 0.1, "display should fall in silence: {after}");
+}
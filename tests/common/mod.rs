@@ -0,0 +1,81 @@
+//! Signal generators and spectra shared by the integration tests.
+//!
+//! Each test crate uses only some of these.
+#![allow(dead_code)]
+
+use lookas::dsp::{hann, prepare_fft_input_inplace};
+use realfft::RealFftPlanner;
+
+pub const SR: f32 = 48_000.0;
+
+/// Next value of a deterministic uniform generator, in [-1, 1].
+pub fn rng(state: &mut u32) -> f32 {
+    *state =
+        state.wrapping_mul(1_664_525).wrapping_add(1_013_904_223);
+    #[allow(clippy::cast_precision_loss)]
+    let v = (*state >> 8) as f32 / (1u32 << 24) as f32;
+    v.mul_add(2.0, -1.0)
+}
+
+/// `n` samples of deterministic white noise in `-amp..amp`.
+pub fn noise(seed: u32, amp: f32, n: usize) -> Vec<f32> {
+    let mut state = seed;
+    (0..n).map(|_| amp * rng(&mut state)).collect()
+}
+
+/// `n` samples of a sine at [`SR`], starting from zero phase.
+pub fn sine(hz: f32, amp: f32, n: usize) -> Vec<f32> {
+    sine_from(hz, amp, 0, n)
+}
+
+/// Samples `start..start + n` of a sine at [`SR`].
+#[allow(clippy::cast_precision_loss, clippy::arithmetic_side_effects)]
+pub fn sine_from(
+    hz: f32,
+    amp: f32,
+    start: usize,
+    n: usize,
+) -> Vec<f32> {
+    (start..start + n)
+        .map(|i| {
+            amp * (std::f32::consts::TAU * hz * i as f32 / SR).sin()
+        })
+        .collect()
+}
+
+/// `n` samples of a sum of sines, given as `(hz, amp)` pairs.
+#[allow(clippy::cast_precision_loss)]
+pub fn tone(partials: &[(f32, f32)], n: usize) -> Vec<f32> {
+    (0..n)
+        .map(|i| {
+            let t = i as f32 / SR;
+            partials
+                .iter()
+                .map(|&(hz, amp)| {
+                    amp * (std::f32::consts::TAU * hz * t).sin()
+                })
+                .sum()
+        })
+        .collect()
+}
+
+pub fn mix(a: &[f32], b: &[f32]) -> Vec<f32> {
+    a.iter().zip(b).map(|(x, y)| x + y).collect()
+}
+
+/// Hann-windowed power spectrum of `samples`, one value per bin.
+#[allow(clippy::cast_precision_loss)]
+pub fn power_spectrum(samples: &[f32]) -> Vec<f32> {
+    let n = samples.len();
+    let fft = RealFftPlanner::<f32>::new().plan_fft_forward(n);
+    let mut buf = fft.make_input_vec();
+    let mut out = fft.make_output_vec();
+    prepare_fft_input_inplace(samples, &hann(n), &mut buf);
+    assert!(fft.process(&mut buf, &mut out).is_ok());
+
+    let norm_inv = 1.0 / ((n as f32) * (n as f32));
+    out.iter()
+        .take(n / 2)
+        .map(|c| c.re.mul_add(c.re, c.im * c.im) * norm_inv)
+        .collect()
+}
@@ -0,0 +1,68 @@
+use lookas::config::FftSize;
+
+// ---------------------------------------------------------------------------
+// fft_size
+// ---------------------------------------------------------------------------
+
+#[test]
+fn fixed_sizes_are_kept_within_range() {
+    assert_eq!(
+        FftSize::Fixed(2048).resolve(30.0, 48_000.0, 16),
+        2048
+    );
+    assert_eq!(FftSize::Fixed(256).resolve(30.0, 48_000.0, 16), 512);
+    assert_eq!(
+        FftSize::Fixed(65_536).resolve(30.0, 48_000.0, 16),
+        16_384
+    );
+}
+
+#[test]
+#[allow(clippy::cast_precision_loss)]
+fn auto_gives_the_lowest_octave_enough_bins() {
+    for (fmin, sr) in
+        [(30.0, 48_000.0), (30.0, 44_100.0), (100.0, 48_000.0)]
+    {
+        let size = FftSize::Auto.resolve(fmin, sr, 16);
+        let bin_hz = sr / size as f32;
+        assert!(
+            fmin / bin_hz >= FftSize::AUTO_BINS_PER_OCTAVE,
+            "fmin {fmin} at {sr}: {size}"
+        );
+        assert!(
+            fmin / bin_hz < 2.0 * FftSize::AUTO_BINS_PER_OCTAVE,
+            "fmin {fmin} at {sr}: {size} is larger than needed"
+        );
+    }
+}
+
+#[test]
+fn auto_sizes_are_powers_of_two() {
+    for fmin in [10.0, 20.0, 30.0, 55.0, 100.0, 400.0, 1000.0] {
+        let size = FftSize::Auto.resolve(fmin, 48_000.0, 16);
+        assert!(size.is_power_of_two(), "fmin {fmin}: {size}");
+    }
+}
+
+#[test]
+fn auto_follows_fmin_and_sample_rate() {
+    assert_eq!(FftSize::Auto.resolve(30.0, 48_000.0, 16), 8192);
+    assert_eq!(FftSize::Auto.resolve(100.0, 48_000.0, 16), 2048);
+    assert_eq!(FftSize::Auto.resolve(20.0, 48_000.0, 16), 16_384);
+    assert_eq!(FftSize::Auto.resolve(30.0, 96_000.0, 16), 16_384);
+    assert_eq!(FftSize::Auto.resolve(60.0, 24_000.0, 16), 2048);
+}
+
+#[test]
+fn auto_is_held_back_by_short_frames() {
+    // 24 frames of 8 ms at 48 kHz is 9216 samples.
+    assert_eq!(FftSize::Auto.resolve(20.0, 48_000.0, 8), 8192);
+    assert_eq!(FftSize::Auto.resolve(20.0, 48_000.0, 16), 16_384);
+}
+
+#[test]
+fn auto_stays_within_range() {
+    assert_eq!(FftSize::Auto.resolve(1000.0, 48_000.0, 16), 512);
+    assert_eq!(FftSize::Auto.resolve(10.0, 192_000.0, 50), 16_384);
+    assert_eq!(FftSize::Auto.resolve(30.0, 0.0, 16), 512);
+}
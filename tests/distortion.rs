@@ -0,0 +1,160 @@
+mod common;
+
+use common::{SR, mix, noise, tone};
+use lookas::distortion::{
+    DistortionAnalyzer, DistortionInput, DistortionMeter,
+    DistortionParams, DistortionReading, measure_distortion,
+    ratio_db,
+};
+use lookas::dsp::blackman_harris;
+
+const FFT: usize = 16_384;
+const BIN_HZ: f32 = SR / 16_384.0;
+
+// ---------------------------------------------------------------------------
+// helpers
+// ---------------------------------------------------------------------------
+
+fn spectrum(samples: &[f32]) -> Vec<f32> {
+    let mut analyzer = DistortionAnalyzer::new(FFT);
+    assert!(analyzer.process(samples));
+    analyzer.spectrum().to_vec()
+}
+
+fn measure(
+    samples: &[f32],
+    hint_hz: Option<f32>,
+) -> Option<DistortionReading> {
+    measure_distortion(
+        &spectrum(samples),
+        BIN_HZ,
+        hint_hz,
+        &DistortionParams::DEFAULT,
+    )
+}
+
+/// A 0.1 s frame of `samples` without a hint.
+const fn frame(samples: &[f32]) -> DistortionInput<'_> {
+    DistortionInput {
+        samples,
+        sample_rate: SR,
+        hint_hz: None,
+        dt_s: 0.1,
+    }
+}
+
+// ---------------------------------------------------------------------------
+// window and scaling
+// ---------------------------------------------------------------------------
+
+#[test]
+fn blackman_harris_is_symmetric_and_peaks_in_the_middle() {
+    let w = blackman_harris(1025);
+    let mid = w.get(512).copied().unwrap_or(0.0);
+
+    assert!((mid - 1.0).abs() < 1e-4, "got {mid}");
+    assert!(w.first().is_some_and(|&v| v < 1e-4));
+    assert!(
+        w.iter()
+            .zip(w.iter().rev())
+            .all(|(a, b)| (a - b).abs() < 1e-6)
+    );
+}
+
+#[test]
+fn sine_power_is_accounted_for_exactly() {
+    let hz = 0.3f32.mul_add(BIN_HZ, 997.0);
+    let reading = measure(&tone(&[(hz, 0.5)], FFT), None);
+
+    assert!(
+        reading.as_ref().is_some_and(|r| {
+            (r.fundamental - 0.125).abs() < 1e-4
+                && (r.fundamental_hz - hz).abs() < 0.05 * BIN_HZ
+                && (r.fundamental_dbfs() + 6.02).abs() < 0.02
+        }),
+        "got {reading:?}"
+    );
+}
+
+// ---------------------------------------------------------------------------
+// measurement
+// ---------------------------------------------------------------------------
+
+#[test]
+fn thd_of_known_harmonics() {
+    let samples =
+        tone(&[(1000.0, 0.5), (2000.0, 0.005), (3000.0, 0.001)], FFT);
+    let reading = measure(&samples, None);
+    let want = (0.01f32.mul_add(0.01, 0.002 * 0.002)).sqrt();
+
+    assert!(
+        reading.as_ref().is_some_and(|r| {
+            (r.thd() - want).abs() < 0.02 * want
+                && (r.thd_n() - want).abs() < 0.05 * want
+        }),
+        "got {reading:?}"
+    );
+    assert!(reading.as_ref().is_some_and(|r| {
+        r.harmonic_dbc(2).is_some_and(|db| (db + 40.0).abs() < 0.1)
+            && r.harmonic_dbc(3)
+                .is_some_and(|db| (db + 54.0).abs() < 0.1)
+    }));
+}
+
+#[test]
+fn noise_counts_towards_thd_n_and_sinad() {
+    // Uniform noise of peak 0.01 has mean power 0.01² / 3, spread
+    // evenly up to Nyquist; only the 20 Hz to 20 kHz share is counted.
+    let samples =
+        mix(&tone(&[(1000.0, 0.5)], FFT), &noise(7, 0.01, FFT));
+    let reading = measure(&samples, None);
+    let in_band = 0.0001 / 3.0 * 19_980.0 / 24_000.0;
+    let want_db = 10.0 * (0.125f32 / in_band).log10();
+
+    assert!(
+        reading.as_ref().is_some_and(|r| {
+            r.thd() < 0.25 * r.thd_n()
+                && (r.sinad_db() - want_db).abs() < 0.5
+                && (ratio_db(r.thd_n()) + want_db).abs() < 0.5
+        }),
+        "got {reading:?}, want SINAD {want_db}"
+    );
+}
+
+#[test]
+fn hint_picks_the_tone_to_measure() {
+    let samples = tone(&[(1000.0, 0.5), (5000.0, 0.05)], FFT);
+
+    assert!(measure(&samples, Some(5000.0)).is_none());
+    assert!(
+        measure(&samples, Some(1010.0)).is_some_and(|r| (r
+            .fundamental_hz
+            - 1000.0)
+            .abs()
+            < 1.0)
+    );
+}
+
+#[test]
+fn no_reading_without_a_tone() {
+    assert!(measure(&vec![0.0; FFT], None).is_none());
+    assert!(measure(&noise(7, 0.5, FFT), None).is_none());
+}
+
+#[test]
+fn meter_averages_the_same_tone_and_restarts_on_a_new_one() {
+    let mut meter =
+        DistortionMeter::new(FFT, DistortionParams::DEFAULT);
+    let clean = tone(&[(1000.0, 0.5)], FFT);
+    let dirty = tone(&[(1000.0, 0.5), (2000.0, 0.05)], FFT);
+
+    let _ = meter.process(&frame(&clean));
+    let averaged =
+        meter.process(&frame(&dirty)).map(DistortionReading::thd);
+    assert!(averaged.is_some_and(|thd| thd > 0.01 && thd < 0.09));
+
+    let moved = tone(&[(2500.0, 0.5), (5000.0, 0.05)], FFT);
+    let fresh =
+        meter.process(&frame(&moved)).map(DistortionReading::thd);
+    assert!(fresh.is_some_and(|thd| (thd - 0.1).abs() < 0.002));
+}
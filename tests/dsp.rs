@@ -1,4 +1,7 @@
-use lookas::dsp::{a_weighting, ema_tc, hann, hz_to_mel, mel_to_hz};
+use lookas::dsp::{
+    Biquad, DcBlocker, Preprocess, PreprocessParams, a_weighting,
+    ema_tc, hann, hz_to_mel, mel_to_hz,
+};
 
 fn to_db(linear: f32) -> f32 {
     20.0 * linear.max(1e-12).log10()
@@ -209,3 +212,151 @@ fn mel_scale_is_monotone() {
         );
     }
 }
+
+// ---------------------------------------------------------------------------
+// biquad
+// ---------------------------------------------------------------------------
+
+const SR: f32 = 48_000.0;
+const Q: f32 = std::f32::consts::FRAC_1_SQRT_2;
+
+/// Steady-state gain in dB of `process` for a sine at `hz`.
+#[allow(clippy::cast_precision_loss)]
+fn measured_db(mut process: impl FnMut(f32) -> f32, hz: f32) -> f32 {
+    let n = 48_000usize;
+    let settle = 24_000usize;
+    let mut peak = 0.0f32;
+    for i in 0..n {
+        let x = (std::f32::consts::TAU * hz * i as f32 / SR).sin();
+        let y = process(x);
+        if i >= settle {
+            peak = peak.max(y.abs());
+        }
+    }
+    to_db(peak)
+}
+
+#[allow(clippy::cast_possible_truncation)]
+fn run(filter: &mut Biquad) -> impl FnMut(f32) -> f32 + '_ {
+    |x| filter.process(f64::from(x)) as f32
+}
+
+#[test]
+fn lowpass_response_shape() {
+    let lp = Biquad::low_pass(SR, 1_000.0, Q);
+    let pass = lp.response_db(SR, 50.0);
+    let corner = lp.response_db(SR, 1_000.0);
+    let octave = lp.response_db(SR, 2_000.0);
+    let two_octaves = lp.response_db(SR, 4_000.0);
+
+    assert!(pass.abs() < 0.01, "passband should be flat, got {pass}");
+    assert!((corner + 3.01).abs() < 0.05, "corner {corner}");
+    // second order: about 12 dB per octave once past the corner
+    let slope = octave - two_octaves;
+    assert!((slope - 12.0).abs() < 1.5, "slope {slope}");
+}
+
+#[test]
+fn highpass_response_shape() {
+    let hp = Biquad::high_pass(SR, 100.0, Q);
+    let pass = hp.response_db(SR, 5_000.0);
+    let corner = hp.response_db(SR, 100.0);
+    let octave = hp.response_db(SR, 50.0);
+    let two_octaves = hp.response_db(SR, 25.0);
+
+    assert!(pass.abs() < 0.01, "passband should be flat, got {pass}");
+    assert!((corner + 3.01).abs() < 0.05, "corner {corner}");
+    let slope = octave - two_octaves;
+    assert!((slope - 12.0).abs() < 0.5, "slope {slope}");
+}
+
+#[test]
+fn biquad_processing_matches_response() {
+    for &hz in &[60.0f32, 250.0, 1_000.0, 4_000.0] {
+        let mut hp = Biquad::high_pass(SR, 200.0, Q);
+        let expected = hp.response_db(SR, hz);
+        let got = measured_db(run(&mut hp), hz);
+        #[allow(clippy::cast_possible_truncation)]
+        let expected = expected as f32;
+        assert!(
+            (got - expected).abs() < 0.1,
+            "{hz} Hz: processed {got:.2} dB, response {expected:.2} dB"
+        );
+    }
+}
+
+#[test]
+fn biquad_reset_clears_state() {
+    let mut lp = Biquad::low_pass(SR, 500.0, Q);
+    for _ in 0..64 {
+        lp.process(1.0);
+    }
+    let mut fresh = Biquad::low_pass(SR, 500.0, Q);
+    lp.reset();
+    assert!((lp.process(0.5) - fresh.process(0.5)).abs() < 1e-12);
+}
+
+// ---------------------------------------------------------------------------
+// pre-processing
+// ---------------------------------------------------------------------------
+
+#[test]
+fn dc_blocker_removes_offset() {
+    let mut dc = DcBlocker::new(SR, 10.0);
+    let mut last = 1.0f32;
+    for _ in 0..48_000 {
+        last = dc.process(0.5);
+    }
+    assert!(last.abs() < 1e-3, "offset should decay, got {last}");
+}
+
+#[test]
+fn default_chain_passes_midrange() {
+    let mut chain = Preprocess::new(PreprocessParams::DEFAULT, SR);
+    let got = measured_db(|x| chain.process(x), 1_000.0);
+    assert!(
+        got.abs() < 0.05,
+        "1 kHz should pass untouched, got {got}"
+    );
+}
+
+#[test]
+fn default_chain_keeps_dc() {
+    let mut chain = Preprocess::new(PreprocessParams::DEFAULT, SR);
+    let mut last = 0.0f32;
+    for _ in 0..48_000 {
+        last = chain.process(0.5);
+    }
+    assert!(
+        (last - 0.5).abs() < 1e-6,
+        "the default chain should not filter, got {last}"
+    );
+}
+
+#[test]
+fn highpass_chain_cuts_rumble() {
+    let params = PreprocessParams {
+        highpass_hz: 80.0,
+        ..PreprocessParams::DEFAULT
+    };
+    let mut chain = Preprocess::new(params, SR);
+    let got = measured_db(|x| chain.process(x), 20.0);
+    assert!(got < -20.0, "20 Hz should be cut, got {got}");
+}
+
+#[test]
+fn pre_emphasis_tilts_toward_highs() {
+    let params = PreprocessParams {
+        dc_block: false,
+        pre_emphasis: 0.95,
+        ..PreprocessParams::DEFAULT
+    };
+    let mut low = Preprocess::new(params, SR);
+    let mut high = Preprocess::new(params, SR);
+    let low_db = measured_db(|x| low.process(x), 100.0);
+    let high_db = measured_db(|x| high.process(x), 8_000.0);
+    assert!(
+        high_db > low_db + 20.0,
+        "pre-emphasis should lift highs: {low_db} vs {high_db}"
+    );
+}
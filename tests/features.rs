@@ -0,0 +1,155 @@
+mod common;
+
+use common::{SR, noise, power_spectrum, sine};
+use lookas::features::{
+    FeatureExtractor, crest_factor, rms, spectral_centroid,
+    spectral_flatness, spectral_rolloff, zero_crossing_rate,
+};
+
+const FFT: usize = 4096;
+#[allow(clippy::cast_precision_loss)]
+const BIN_HZ: f32 = SR / FFT as f32;
+
+// ---------------------------------------------------------------------------
+// spectral features
+// ---------------------------------------------------------------------------
+
+#[test]
+fn centroid_of_pure_tone_is_its_frequency() {
+    for hz in [250.0f32, 1_000.0, 5_000.0] {
+        let c = spectral_centroid(
+            &power_spectrum(&sine(hz, 1.0, FFT)),
+            BIN_HZ,
+        );
+        assert!(
+            (c - hz).abs() < 2.0 * BIN_HZ,
+            "{hz} Hz tone: centroid {c}"
+        );
+    }
+}
+
+#[test]
+fn centroid_of_white_noise_is_mid_band() {
+    let c = spectral_centroid(
+        &power_spectrum(&noise(12_345, 1.0, FFT)),
+        BIN_HZ,
+    );
+    let nyquist = SR / 2.0;
+    assert!(
+        (c - nyquist / 2.0).abs() < nyquist * 0.05,
+        "white noise centroid {c}"
+    );
+}
+
+#[test]
+fn rolloff_of_pure_tone_sits_on_the_tone() {
+    let r = spectral_rolloff(
+        &power_spectrum(&sine(2_000.0, 1.0, FFT)),
+        BIN_HZ,
+        0.85,
+    );
+    assert!((r - 2_000.0).abs() < 2.0 * BIN_HZ, "rolloff {r}");
+}
+
+#[test]
+fn rolloff_of_white_noise_tracks_fraction() {
+    let r = spectral_rolloff(
+        &power_spectrum(&noise(12_345, 1.0, FFT)),
+        BIN_HZ,
+        0.85,
+    );
+    let expected = 0.85 * SR / 2.0;
+    assert!(
+        (r - expected).abs() < SR / 2.0 * 0.05,
+        "rolloff {r}, expected about {expected}"
+    );
+}
+
+#[test]
+fn flatness_separates_tone_from_noise() {
+    let tone =
+        spectral_flatness(&power_spectrum(&sine(1_000.0, 1.0, FFT)));
+    let white =
+        spectral_flatness(&power_spectrum(&noise(12_345, 1.0, FFT)));
+    assert!(tone < 0.01, "tone flatness {tone}");
+    // a periodogram of white noise has geometric/arithmetic mean
+    // exp(-gamma), about 0.56
+    assert!((white - 0.56).abs() < 0.08, "noise flatness {white}");
+}
+
+#[test]
+fn flatness_of_flat_spectrum_is_one() {
+    let f = spectral_flatness(&[0.5; 64]);
+    assert!((f - 1.0).abs() < 1e-5, "got {f}");
+}
+
+// ---------------------------------------------------------------------------
+// temporal features
+// ---------------------------------------------------------------------------
+
+#[test]
+fn sine_rms_and_crest() {
+    let s = sine(1_000.0, 1.0, 4_800);
+    let level = rms(&s);
+    let crest = crest_factor(&s);
+    assert!((level - std::f32::consts::FRAC_1_SQRT_2).abs() < 1e-3);
+    assert!((crest - std::f32::consts::SQRT_2).abs() < 1e-3);
+}
+
+#[test]
+fn square_wave_crest_is_one() {
+    let s: Vec<f32> = (0..1_000)
+        .map(|i| if (i / 10) % 2 == 0 { 0.5 } else { -0.5 })
+        .collect();
+    assert!((crest_factor(&s) - 1.0).abs() < 1e-6);
+    assert!((rms(&s) - 0.5).abs() < 1e-6);
+}
+
+#[test]
+fn silence_features_are_zero() {
+    let s = vec![0.0f32; 256];
+    assert!(rms(&s).abs() < f32::EPSILON);
+    assert!(crest_factor(&s).abs() < f32::EPSILON);
+    assert!(zero_crossing_rate(&s).abs() < f32::EPSILON);
+    assert!(spectral_centroid(&s, BIN_HZ).abs() < f32::EPSILON);
+    assert!(spectral_rolloff(&s, BIN_HZ, 0.85).abs() < f32::EPSILON);
+}
+
+#[test]
+fn zcr_of_sine_is_twice_its_frequency() {
+    let hz = 1_200.0;
+    let zcr = zero_crossing_rate(&sine(hz, 1.0, 48_000));
+    let expected = 2.0 * hz / SR;
+    assert!(
+        (zcr - expected).abs() < 1e-4,
+        "zcr {zcr}, expected {expected}"
+    );
+}
+
+#[test]
+fn zcr_of_white_noise_is_about_half() {
+    let zcr = zero_crossing_rate(&noise(12_345, 1.0, 48_000));
+    assert!((zcr - 0.5).abs() < 0.02, "zcr {zcr}");
+}
+
+// ---------------------------------------------------------------------------
+// FeatureExtractor
+// ---------------------------------------------------------------------------
+
+#[test]
+fn extractor_reports_flux_only_on_change() {
+    let mut fx = FeatureExtractor::new();
+    let quiet = sine(1_000.0, 1.0, FFT);
+    let spec = power_spectrum(&quiet);
+
+    let first = fx.process(&spec, &quiet, BIN_HZ);
+    let steady = fx.process(&spec, &quiet, BIN_HZ);
+    assert!(first.flux.abs() < f32::EPSILON);
+    assert!(steady.flux.abs() < f32::EPSILON);
+
+    let burst = noise(12_345, 1.0, FFT);
+    let changed = fx.process(&power_spectrum(&burst), &burst, BIN_HZ);
+    assert!(changed.flux > 0.0, "noise burst should produce flux");
+    assert!(changed.flatness > steady.flatness);
+    assert!(changed.zcr > steady.zcr);
+}
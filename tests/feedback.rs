@@ -0,0 +1,128 @@
+mod common;
+
+use common::{SR, mix, noise, power_spectrum, sine_from};
+use lookas::feedback::{
+    FeedbackDetector, FeedbackParams, narrow_peaks,
+};
+
+const FFT: usize = 4096;
+const BIN_HZ: f32 = SR / 4096.0;
+const DT: f32 = 4096.0 / SR;
+
+// ---------------------------------------------------------------------------
+// helpers
+// ---------------------------------------------------------------------------
+
+/// Runs `frames` frames of `hz` at an amplitude set by `amp(frame)`
+/// over a noise bed, returning the frame of the first howl.
+#[allow(clippy::arithmetic_side_effects)]
+fn first_howl(
+    hz: f32,
+    frames: usize,
+    amp: impl Fn(usize) -> f32,
+) -> Option<(usize, f32)> {
+    let mut det = FeedbackDetector::new(FeedbackParams::DEFAULT);
+    (0..frames).find_map(|k| {
+        #[allow(clippy::cast_possible_truncation)]
+        let frame = mix(
+            &sine_from(hz, amp(k), k * FFT, FFT),
+            &noise(k as u32, 0.01, FFT),
+        );
+        det.process(&power_spectrum(&frame), BIN_HZ, DT)
+            .map(|h| (k, h.hz))
+    })
+}
+
+/// Gain growing by `db_per_frame` from a quiet start.
+#[allow(clippy::cast_precision_loss)]
+fn rising(db_per_frame: f32) -> impl Fn(usize) -> f32 {
+    move |k| 0.01 * 10f32.powf(db_per_frame * k as f32 / 20.0)
+}
+
+// ---------------------------------------------------------------------------
+// narrow peaks
+// ---------------------------------------------------------------------------
+
+#[test]
+fn a_pure_tone_is_a_narrow_peak_at_its_frequency() {
+    let spec = power_spectrum(&sine_from(1_234.0, 0.5, 0, FFT));
+    let mut peaks = Vec::new();
+    narrow_peaks(&spec, BIN_HZ, &FeedbackParams::DEFAULT, &mut peaks);
+
+    assert_eq!(peaks.len(), 1, "{peaks:?}");
+    let peak = peaks.first();
+    assert!(peak.is_some_and(|p| (p.hz - 1_234.0).abs() < 1.0));
+    assert!(peak.is_some_and(|p| p.prominence_db > 40.0));
+}
+
+#[test]
+fn noise_has_no_narrow_peaks() {
+    let spec = power_spectrum(&noise(3, 0.5, FFT));
+    let mut peaks = Vec::new();
+    narrow_peaks(&spec, BIN_HZ, &FeedbackParams::DEFAULT, &mut peaks);
+    assert!(peaks.is_empty(), "{peaks:?}");
+}
+
+#[test]
+fn peaks_outside_the_range_are_ignored() {
+    let spec = power_spectrum(&sine_from(1_234.0, 0.5, 0, FFT));
+    let mut peaks = Vec::new();
+    let params = FeedbackParams {
+        fmin_hz: 2_000.0,
+        ..FeedbackParams::DEFAULT
+    };
+    narrow_peaks(&spec, BIN_HZ, &params, &mut peaks);
+    assert!(peaks.is_empty());
+}
+
+// ---------------------------------------------------------------------------
+// detector
+// ---------------------------------------------------------------------------
+
+#[test]
+fn growing_tone_is_flagged_after_it_persists() {
+    let found = first_howl(2_500.0, 20, rising(1.0));
+    let (frame, hz) = found.unwrap_or((usize::MAX, 0.0));
+
+    // 0.3 s is about four frames of 85 ms
+    assert!((3..=6).contains(&frame), "flagged at frame {frame}");
+    assert!((hz - 2_500.0).abs() < 2.0, "flagged {hz} Hz");
+}
+
+#[test]
+fn steady_tone_is_not_feedback() {
+    assert_eq!(first_howl(440.0, 30, |_| 0.3), None);
+}
+
+#[test]
+fn decaying_tone_is_not_feedback() {
+    assert_eq!(first_howl(880.0, 30, rising(-1.0)), None);
+}
+
+#[test]
+#[allow(clippy::arithmetic_side_effects)]
+fn howl_holds_at_the_top_and_clears_when_it_stops() {
+    let mut det = FeedbackDetector::new(FeedbackParams::DEFAULT);
+    let amp = |k: usize| rising(1.5)(k.min(8));
+    let mut flagged = Vec::new();
+    for k in 0..16 {
+        #[allow(clippy::cast_possible_truncation)]
+        let frame = mix(
+            &sine_from(3_000.0, amp(k), k * FFT, FFT),
+            &noise(k as u32, 0.01, FFT),
+        );
+        flagged.push(
+            det.process(&power_spectrum(&frame), BIN_HZ, DT)
+                .is_some(),
+        );
+    }
+    assert!(
+        flagged.get(8..).is_some_and(|f| f.iter().all(|&h| h)),
+        "{flagged:?}"
+    );
+
+    // silence drops it within the release time
+    let quiet = power_spectrum(&noise(99, 0.01, FFT));
+    assert!(det.process(&quiet, BIN_HZ, DT).is_none());
+    assert!(det.howl().is_none());
+}
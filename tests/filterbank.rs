@@ -1,4 +1,9 @@
-use lookas::filterbank::{FilterbankParams, build_filterbank};
+use lookas::filterbank::{
+    FilterbankParams, NoteFilterbankParams, PIANO_HIGH_MIDI,
+    PIANO_LOW_MIDI, Tri, build_filterbank, build_note_filterbank,
+    note_hz,
+};
+use lookas::render::{keyboard_labels, layout_centered};
 
 const SR: f32 = 44_100.0;
 const FFT: usize = 2048;
@@ -264,3 +269,160 @@ fn filterbank_flat_spectrum_produces_positive_output() {
         );
     }
 }
+
+// ---------------------------------------------------------------------------
+// piano filterbank
+// ---------------------------------------------------------------------------
+
+fn piano(fft_size: usize) -> Vec<Tri> {
+    build_note_filterbank(NoteFilterbankParams {
+        sr: SR,
+        fft_size,
+        low_midi: PIANO_LOW_MIDI,
+        high_midi: PIANO_HIGH_MIDI,
+        a4_hz: 440.0,
+    })
+}
+
+/// Band outputs for a spectrum with all power in bin `bin`.
+fn response(fb: &[Tri], bin: usize) -> Vec<f32> {
+    fb.iter()
+        .map(|tri| {
+            tri.taps
+                .iter()
+                .filter(|&&(i, _)| i == bin)
+                .map(|&(_, w)| w)
+                .sum()
+        })
+        .collect()
+}
+
+#[test]
+fn piano_has_one_band_per_key_on_exact_notes() {
+    let fb = piano(FFT);
+    assert_eq!(fb.len(), 88);
+
+    for (tri, midi) in fb.iter().zip(PIANO_LOW_MIDI..) {
+        let expected = note_hz(midi, 440.0);
+        assert!(
+            (tri.center_hz - expected).abs() < 1e-3 * expected,
+            "midi {midi}: {} vs {expected}",
+            tri.center_hz
+        );
+    }
+    assert!(
+        fb.first()
+            .is_some_and(|t| (t.center_hz - 27.5).abs() < 1e-3)
+    );
+    assert!(
+        fb.get(48)
+            .is_some_and(|t| (t.center_hz - 440.0).abs() < 1e-3)
+    );
+}
+
+#[test]
+fn piano_taps_are_valid_and_normalised() {
+    for fft_size in [1024, 4096, 16_384] {
+        for (i, tri) in piano(fft_size).iter().enumerate() {
+            assert!(!tri.taps.is_empty(), "band {i} has no taps");
+            assert!(
+                tri.taps.iter().all(|&(idx, w)| {
+                    idx < fft_size / 2 && w >= 0.0
+                })
+            );
+            let sum: f32 = tri.taps.iter().map(|&(_, w)| w).sum();
+            assert!((sum - 1.0).abs() < 1e-4, "band {i}: sum {sum}");
+        }
+    }
+}
+
+#[test]
+fn piano_bandwidth_follows_semitone_spacing() {
+    let fft_size = 16_384;
+    #[allow(clippy::cast_precision_loss)]
+    let hz_per_bin = SR / fft_size as f32;
+    let fb = piano(fft_size);
+
+    for (tri, midi) in fb.iter().zip(PIANO_LOW_MIDI..) {
+        let lo = note_hz(midi - 1, 440.0);
+        let hi = note_hz(midi + 1, 440.0);
+        for &(idx, _) in &tri.taps {
+            #[allow(clippy::cast_precision_loss)]
+            let hz = idx as f32 * hz_per_bin;
+            // narrow low bands fall back to the two bins around the note
+            assert!(
+                (hz > lo && hz < hi) || hi - lo < 2.0 * hz_per_bin,
+                "midi {midi}: tap at {hz} Hz outside {lo}..{hi}"
+            );
+        }
+    }
+}
+
+#[test]
+fn piano_tone_lights_its_own_key() {
+    let fft_size = 16_384;
+    #[allow(clippy::cast_precision_loss)]
+    let hz_per_bin = SR / fft_size as f32;
+    let fb = piano(fft_size);
+
+    for midi in [48, 60, 69, 81, 96] {
+        #[allow(
+            clippy::cast_possible_truncation,
+            clippy::cast_sign_loss
+        )]
+        let bin =
+            (note_hz(midi, 440.0) / hz_per_bin).round() as usize;
+        let out = response(&fb, bin);
+        let loudest = out
+            .iter()
+            .enumerate()
+            .max_by(|a, b| a.1.total_cmp(b.1))
+            .map(|(i, _)| i);
+        let expected = usize::try_from(midi - PIANO_LOW_MIDI).ok();
+        assert_eq!(loudest, expected, "midi {midi}");
+    }
+}
+
+#[test]
+fn piano_respects_reference_pitch() {
+    let fb = build_note_filterbank(NoteFilterbankParams {
+        sr: SR,
+        fft_size: FFT,
+        low_midi: 69,
+        high_midi: 69,
+        a4_hz: 432.0,
+    });
+    assert_eq!(fb.len(), 1);
+    assert!(
+        fb.first()
+            .is_some_and(|t| (t.center_hz - 432.0).abs() < 1e-3)
+    );
+}
+
+#[test]
+fn keyboard_labels_mark_cs_and_black_keys() {
+    let narrow = keyboard_labels(59, 63, 1);
+    assert_eq!(narrow, ["", "4", "\u{2580}", "", "\u{2580}"]);
+
+    let wide = keyboard_labels(PIANO_LOW_MIDI, PIANO_HIGH_MIDI, 2);
+    assert_eq!(wide.len(), 88);
+    assert_eq!(wide.last().map(String::as_str), Some("C8"));
+    assert_eq!(wide.iter().filter(|l| l.starts_with('C')).count(), 8);
+}
+
+#[test]
+fn centered_layout_fits_fixed_bar_count() {
+    let wide = layout_centered(200, 12, 0);
+    assert_eq!(wide.bars, 12);
+    assert!(wide.bar_w > 2 && wide.gap_w == 1);
+    assert_eq!(
+        usize::from(wide.left_pad + wide.right_pad)
+            + 12 * wide.slot_w(),
+        200
+    );
+
+    let piano = layout_centered(100, 88, 1);
+    assert_eq!((piano.bar_w, piano.gap_w), (1, 0));
+    assert_eq!(piano.left_pad, 6);
+    assert_eq!(piano.top_pad, 1);
+}
@@ -0,0 +1,249 @@
+use lookas::loudness::{
+    ChannelLayout, KWeighting, LoudnessHistogram, LoudnessMeter,
+    TruePeak, gated_loudness, loudness_range, lufs_to_energy,
+};
+
+// ---------------------------------------------------------------------------
+// helpers
+// ---------------------------------------------------------------------------
+
+const SR: f32 = 48_000.0;
+
+fn db_to_amp(db: f32) -> f32 {
+    10f32.powf(db / 20.0)
+}
+
+/// Sine segments of `(dBFS peak, seconds)` at `hz`, back to back.
+#[allow(
+    clippy::cast_precision_loss,
+    clippy::cast_possible_truncation,
+    clippy::cast_sign_loss,
+    clippy::arithmetic_side_effects
+)]
+fn sine_segments(hz: f32, segments: &[(f32, f32)]) -> Vec<f32> {
+    let mut out = Vec::new();
+    for &(db, secs) in segments {
+        let amp = db_to_amp(db);
+        let start = out.len();
+        let len = (secs * SR) as usize;
+        out.extend((start..start + len).map(|i| {
+            amp * (std::f32::consts::TAU * hz * i as f32 / SR).sin()
+        }));
+    }
+    out
+}
+
+fn measure(signal: &[f32]) -> LoudnessMeter {
+    let mut meter = LoudnessMeter::new(SR, ChannelLayout::DualMono);
+    // feed in uneven chunks to mimic frame-sized deliveries
+    for chunk in signal.chunks(797) {
+        meter.process(chunk);
+    }
+    meter
+}
+
+fn assert_close(label: &str, got: f32, expected: f32, tol: f32) {
+    assert!(
+        (got - expected).abs() <= tol,
+        "{label}: got {got:.3}, expected {expected:.3} (tol {tol})"
+    );
+}
+
+#[allow(clippy::cast_precision_loss, clippy::arithmetic_side_effects)]
+fn kweighted_gain_db(hz: f32) -> f32 {
+    let mut filter = KWeighting::new(SR);
+    let signal = sine_segments(hz, &[(0.0, 2.0)]);
+    let settle = signal.len() / 2;
+    let mut acc = 0.0f64;
+    for (i, &x) in signal.iter().enumerate() {
+        let y = filter.process(x);
+        if i >= settle {
+            acc = y.mul_add(y, acc);
+        }
+    }
+    let ms = acc / (signal.len() - settle) as f64;
+    // sine mean square is 0.5
+    #[allow(clippy::cast_possible_truncation)]
+    let gain = (10.0 * (ms / 0.5).log10()) as f32;
+    gain
+}
+
+// ---------------------------------------------------------------------------
+// K-weighting
+// ---------------------------------------------------------------------------
+
+#[test]
+fn kweighting_response_shape() {
+    assert_close("1 kHz", kweighted_gain_db(1_000.0), 0.69, 0.1);
+    assert_close("10 kHz", kweighted_gain_db(10_000.0), 4.0, 0.3);
+    assert!(
+        kweighted_gain_db(20.0) < -10.0,
+        "20 Hz should be strongly attenuated"
+    );
+}
+
+// ---------------------------------------------------------------------------
+// EBU Tech 3341 minimum requirements
+// ---------------------------------------------------------------------------
+
+#[test]
+fn tech3341_case1_steady_minus_23() {
+    let r =
+        measure(&sine_segments(1_000.0, &[(-23.0, 5.0)])).reading();
+    assert_close("momentary", r.momentary, -23.0, 0.1);
+    assert_close("short-term", r.short_term, -23.0, 0.1);
+    assert_close("integrated", r.integrated, -23.0, 0.1);
+}
+
+#[test]
+fn tech3341_case2_steady_minus_33() {
+    let r =
+        measure(&sine_segments(1_000.0, &[(-33.0, 5.0)])).reading();
+    assert_close("momentary", r.momentary, -33.0, 0.1);
+    assert_close("short-term", r.short_term, -33.0, 0.1);
+    assert_close("integrated", r.integrated, -33.0, 0.1);
+}
+
+#[test]
+fn tech3341_case3_relative_gate() {
+    let signal = sine_segments(
+        1_000.0,
+        &[(-36.0, 2.5), (-23.0, 15.0), (-36.0, 2.5)],
+    );
+    let r = measure(&signal).reading();
+    assert_close("integrated", r.integrated, -23.0, 0.1);
+}
+
+#[test]
+fn tech3341_case4_absolute_and_relative_gate() {
+    let signal = sine_segments(
+        1_000.0,
+        &[
+            (-72.0, 2.5),
+            (-36.0, 2.5),
+            (-23.0, 15.0),
+            (-36.0, 2.5),
+            (-72.0, 2.5),
+        ],
+    );
+    let r = measure(&signal).reading();
+    assert_close("integrated", r.integrated, -23.0, 0.1);
+}
+
+#[test]
+fn tech3341_true_peak_quarter_rate_sine() {
+    // fs/4 sine with a 45 degree phase offset: samples land at
+    // 0.707 of the true peak, which sits between them.
+    let amp = db_to_amp(-6.0);
+    let signal: Vec<f32> = [0.0f32, 1.0, 2.0, 3.0]
+        .iter()
+        .cycle()
+        .take(48_000)
+        .map(|&quarter| {
+            let phase = quarter.mul_add(
+                std::f32::consts::FRAC_PI_2,
+                std::f32::consts::FRAC_PI_4,
+            );
+            amp * phase.sin()
+        })
+        .collect();
+
+    let sample_peak =
+        signal.iter().fold(0.0f32, |m, &x| m.max(x.abs()));
+    assert_close(
+        "sample peak",
+        20.0 * sample_peak.log10(),
+        -9.01,
+        0.05,
+    );
+
+    let mut tp = TruePeak::new();
+    for &x in &signal {
+        tp.process(x);
+    }
+    let got = 20.0 * tp.peak().log10();
+    assert!(
+        (-6.4..=-5.8).contains(&got),
+        "true peak {got:.2} dBTP outside -6.0 +0.2/-0.4"
+    );
+}
+
+// ---------------------------------------------------------------------------
+// EBU Tech 3342 loudness range
+// ---------------------------------------------------------------------------
+
+#[test]
+fn tech3342_case1_range_10_lu() {
+    let signal =
+        sine_segments(1_000.0, &[(-20.0, 10.0), (-30.0, 10.0)]);
+    let r = measure(&signal).reading();
+    assert_close("LRA", r.range, 10.0, 1.0);
+}
+
+#[test]
+fn tech3342_case2_range_5_lu() {
+    let signal =
+        sine_segments(1_000.0, &[(-20.0, 10.0), (-15.0, 10.0)]);
+    let r = measure(&signal).reading();
+    assert_close("LRA", r.range, 5.0, 1.0);
+}
+
+// ---------------------------------------------------------------------------
+// gating edge cases
+// ---------------------------------------------------------------------------
+
+#[test]
+fn silence_has_no_integrated_loudness() {
+    let r = measure(&vec![0.0f32; 48_000 * 3]).reading();
+    assert!(r.integrated.is_infinite() && r.integrated < 0.0);
+    assert!(r.range.abs() < f32::EPSILON);
+}
+
+#[test]
+fn blocks_below_absolute_gate_are_ignored() {
+    let quiet = lufs_to_energy(-80.0);
+    assert!(gated_loudness(&[quiet; 20]).is_infinite());
+
+    let loud = lufs_to_energy(-20.0);
+    let mut blocks = vec![quiet; 20];
+    blocks.extend([loud; 5]);
+    assert_close("gated", gated_loudness(&blocks), -20.0, 0.01);
+}
+
+#[test]
+fn range_of_blocks_spans_the_percentiles() {
+    let mut blocks = vec![lufs_to_energy(-30.0); 50];
+    blocks.extend([lufs_to_energy(-20.0); 50]);
+    assert_close("LRA", loudness_range(&blocks), 10.0, 0.1);
+    assert!(loudness_range(&[]).abs() < f32::EPSILON);
+}
+
+#[test]
+fn histogram_holds_a_long_session() {
+    // Ten hours of 100 ms blocks, mostly at -23 LUFS with quiet
+    // breaks the relative gate removes.
+    let mut hist = LoudnessHistogram::new();
+    for i in 0..360_000u32 {
+        let lufs = if i % 5 == 0 { -40.0 } else { -23.0 };
+        hist.add(lufs_to_energy(lufs));
+    }
+    assert_close("integrated", hist.integrated(), -23.0, 0.01);
+    assert_close("LRA", hist.range(), 17.0, 0.1);
+
+    hist.clear();
+    assert!(hist.integrated().is_infinite());
+}
+
+#[test]
+fn mono_layout_reads_3_lu_below_dual_mono() {
+    let signal = sine_segments(1_000.0, &[(-23.0, 5.0)]);
+    let mut mono = LoudnessMeter::new(SR, ChannelLayout::Mono);
+    mono.process(&signal);
+    let dual = measure(&signal);
+    assert_close(
+        "difference",
+        dual.reading().integrated - mono.reading().integrated,
+        3.01,
+        0.02,
+    );
+}
@@ -0,0 +1,242 @@
+mod common;
+
+use common::{SR, power_spectrum, sine};
+use lookas::analyzer::{
+    BandParams, DynamicsParams, ScalingMode, ScalingParams,
+    SpectrumAnalyzer, band_level_db,
+};
+use lookas::filterbank::{FilterbankParams, build_filterbank};
+use lookas::ltas::{Ltas, Snapshot, SnapshotBand, octave_deltas};
+
+const FFT: usize = 2048;
+const DT: f32 = 0.02;
+
+// ---------------------------------------------------------------------------
+// helpers
+// ---------------------------------------------------------------------------
+
+fn close(a: Option<f32>, b: f32, tol: f32) -> bool {
+    a.is_some_and(|a| (a - b).abs() <= tol)
+}
+
+fn analyzer(bands: usize) -> SpectrumAnalyzer {
+    let mut sa = SpectrumAnalyzer::new(FFT / 2);
+    sa.filters = build_filterbank(FilterbankParams {
+        sr: SR,
+        fft_size: FFT,
+        bands,
+        fmin: 30.0,
+        fmax: 16_000.0,
+    });
+    sa.resize(bands);
+    sa
+}
+
+const fn band(hz: f32, db: f32) -> SnapshotBand {
+    SnapshotBand { hz, db }
+}
+
+// ---------------------------------------------------------------------------
+// long-term average
+// ---------------------------------------------------------------------------
+
+#[test]
+fn session_average_is_the_plain_mean() {
+    let mut ltas = Ltas::new(None);
+    for level in [1.0, 2.0, 3.0, 6.0] {
+        ltas.process(&[level, level * 2.0], DT);
+    }
+
+    assert!(close(ltas.power().first().copied(), 3.0, 1e-5));
+    assert!(close(ltas.power().get(1).copied(), 6.0, 1e-5));
+    assert!(close(Some(ltas.elapsed_s()), 4.0 * DT, 1e-6));
+}
+
+#[test]
+fn windowed_average_forgets_old_audio() {
+    let mut ltas = Ltas::new(Some(0.5));
+    for _ in 0..500 {
+        ltas.process(&[1.0], DT);
+    }
+    for _ in 0..250 {
+        ltas.process(&[0.0], DT);
+    }
+    let windowed = ltas.power().first().copied();
+
+    let mut session = Ltas::new(None);
+    for _ in 0..500 {
+        session.process(&[1.0], DT);
+    }
+    for _ in 0..250 {
+        session.process(&[0.0], DT);
+    }
+
+    assert!(windowed.is_some_and(|p| p < 1e-3), "got {windowed:?}");
+    assert!(close(session.power().first().copied(), 2.0 / 3.0, 1e-3));
+}
+
+#[test]
+fn average_restarts_on_new_spectrum_size() {
+    let mut ltas = Ltas::new(None);
+    ltas.process(&[4.0, 4.0], DT);
+    ltas.process(&[1.0, 1.0, 1.0], DT);
+
+    assert_eq!(ltas.power().len(), 3);
+    assert!(close(ltas.power().first().copied(), 1.0, 1e-6));
+
+    ltas.reset();
+    assert!(ltas.is_empty());
+}
+
+// ---------------------------------------------------------------------------
+// band levels and heights
+// ---------------------------------------------------------------------------
+
+#[test]
+fn band_level_tracks_amplitude_in_db() {
+    let sa = analyzer(32);
+    let loudest = |amp: f32| {
+        let spec = power_spectrum(&sine(1000.0, amp, FFT));
+        sa.filters
+            .iter()
+            .map(|tri| band_level_db(tri, &spec))
+            .fold(f32::NEG_INFINITY, f32::max)
+    };
+    let full = loudest(1.0);
+    let quiet = loudest(0.1);
+
+    assert!(full <= 0.5, "got {full}");
+    assert!((full - quiet - 20.0).abs() < 0.1, "{full} vs {quiet}");
+}
+
+#[test]
+fn level_height_matches_live_bars() {
+    for mode in [ScalingMode::Absolute, ScalingMode::Auto] {
+        let mut sa = analyzer(24);
+        sa.spec_pow_smooth = power_spectrum(&sine(440.0, 0.3, FFT))
+            .iter()
+            .map(|p| p + 1e-9)
+            .collect();
+        let scaling = ScalingParams {
+            mode,
+            floor_db: -90.0,
+            ceiling_db: -20.0,
+        };
+        let dynamics = DynamicsParams::DEFAULT;
+        let bands = BandParams { scaling, dynamics };
+        for _ in 0..20 {
+            sa.analyze_bands(&bands, DT, true);
+        }
+
+        for (i, tri) in sa.filters.iter().enumerate() {
+            let db = band_level_db(tri, &sa.spec_pow_smooth);
+            let height = sa.level_height(i, db, &bands);
+            let live = sa.bars_target.get(i).copied();
+            assert!(
+                close(live, height, 1e-3),
+                "band {i}: live {live:?}, line {height}"
+            );
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// snapshots
+// ---------------------------------------------------------------------------
+
+#[test]
+fn snapshot_interpolates_over_log_frequency() {
+    let snap = Snapshot::from_bands([
+        band(400.0, -10.0),
+        band(100.0, -30.0),
+    ]);
+
+    assert!(close(snap.level_at(200.0), -20.0, 1e-4));
+    assert!(close(snap.level_at(100.0), -30.0, 1e-6));
+    assert!(close(snap.level_at(90.0), -30.0, 1e-6));
+    assert!(snap.level_at(20.0).is_none());
+    assert!(snap.level_at(4000.0).is_none());
+}
+
+#[test]
+fn snapshot_survives_a_filterbank_change() {
+    let wide = analyzer(64);
+    let narrow = analyzer(16);
+    let spec = power_spectrum(&sine(1000.0, 0.5, FFT));
+    let snap = Snapshot::from_spectrum(&wide.filters, &spec);
+
+    let peak = narrow
+        .filters
+        .iter()
+        .filter_map(|tri| {
+            snap.level_at(tri.center_hz).map(|db| (tri, db))
+        })
+        .max_by(|a, b| a.1.total_cmp(&b.1))
+        .map(|(tri, _)| tri.center_hz);
+
+    assert!(
+        peak.is_some_and(|hz| (hz / 1000.0).log2().abs() < 0.5),
+        "got {peak:?}"
+    );
+}
+
+#[test]
+fn snapshot_round_trips_as_toml_and_json() {
+    let dir = std::env::temp_dir()
+        .join(format!("lookas-snapshot-{}", std::process::id()));
+    let snap = Snapshot::from_bands([
+        band(63.0, -24.5),
+        band(1000.0, -12.0),
+    ]);
+
+    for name in ["snap.toml", "snap.json"] {
+        let path = dir.join(name);
+        let saved = snap.save_to(&path);
+        let loaded = Snapshot::load_from(&path);
+
+        assert!(saved.is_ok(), "save failed: {saved:?}");
+        assert!(
+            loaded.as_ref().is_ok_and(|s| *s == snap),
+            "load failed: {loaded:?}"
+        );
+    }
+    let json = std::fs::read_to_string(dir.join("snap.json"));
+    let _ = std::fs::remove_dir_all(&dir);
+
+    assert!(json.is_ok_and(|j| j.trim_start().starts_with('{')));
+}
+
+#[test]
+fn snapshot_rejects_malformed_files() {
+    let dir = std::env::temp_dir()
+        .join(format!("lookas-bad-snapshot-{}", std::process::id()));
+    let path = dir.join("snap.json");
+    let written = std::fs::create_dir_all(&dir)
+        .and_then(|()| std::fs::write(&path, "bands = 3"));
+    let loaded = Snapshot::load_from(&path);
+    let _ = std::fs::remove_dir_all(&dir);
+
+    assert!(written.is_ok());
+    assert!(loaded.is_err());
+}
+
+// ---------------------------------------------------------------------------
+// readout
+// ---------------------------------------------------------------------------
+
+#[test]
+fn octave_deltas_average_nearest_bands() {
+    let centers = [60.0, 66.0, 1000.0, 3000.0];
+    let live = [-10.0, -20.0, -5.0, 0.0];
+    let reference = [Some(-12.0), Some(-20.0), None, Some(-3.0)];
+    let mut out = Vec::new();
+    octave_deltas(&centers, &live, &reference, &mut out);
+
+    assert_eq!(out.len(), 2, "got {out:?}");
+    assert!(out.first().is_some_and(|&(hz, d)| {
+        (hz - 63.0).abs() < 1e-3 && (d - 1.0).abs() < 1e-4
+    }));
+    assert!(out.get(1).is_some_and(|&(hz, d)| {
+        (hz - 4000.0).abs() < 1e-3 && (d - 3.0).abs() < 1e-4
+    }));
+}
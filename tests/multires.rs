@@ -0,0 +1,162 @@
+mod common;
+
+use common::{SR, power_spectrum, sine};
+use lookas::multires::{
+    MultiResParams, ResolutionBand, fold_power, plan_resolutions,
+};
+
+const FFT: usize = 2048;
+
+// ---------------------------------------------------------------------------
+// helpers
+// ---------------------------------------------------------------------------
+
+fn argmax(values: &[f32]) -> usize {
+    values
+        .iter()
+        .enumerate()
+        .max_by(|a, b| a.1.total_cmp(b.1))
+        .map_or(0, |(i, _)| i)
+}
+
+// ---------------------------------------------------------------------------
+// plan_resolutions
+// ---------------------------------------------------------------------------
+
+#[test]
+fn plan_covers_whole_grid_contiguously() {
+    let bands = plan_resolutions(MultiResParams {
+        fft_size: FFT,
+        levels: 3,
+    });
+
+    assert_eq!(bands.first().map(|b| b.start_bin), Some(0));
+    assert_eq!(bands.last().map(|b| b.end_bin), Some(FFT / 2));
+    for w in bands.windows(2) {
+        let (Some(prev), Some(next)) = (w.first(), w.get(1)) else {
+            continue;
+        };
+        assert_eq!(
+            prev.end_bin, next.start_bin,
+            "gap between resolution bands: {prev:?} -> {next:?}"
+        );
+    }
+}
+
+#[test]
+fn plan_halves_window_per_level() {
+    let bands = plan_resolutions(MultiResParams {
+        fft_size: FFT,
+        levels: 3,
+    });
+    let sizes: Vec<usize> =
+        bands.iter().map(|b| b.fft_size).collect();
+    assert_eq!(sizes, vec![2048, 1024, 512]);
+}
+
+#[test]
+fn plan_single_level_is_plain_fft() {
+    let bands = plan_resolutions(MultiResParams {
+        fft_size: FFT,
+        levels: 1,
+    });
+    assert_eq!(
+        bands,
+        vec![ResolutionBand {
+            fft_size: FFT,
+            start_bin: 0,
+            end_bin: FFT / 2,
+        }]
+    );
+}
+
+#[test]
+fn plan_stops_at_minimum_window() {
+    let bands = plan_resolutions(MultiResParams {
+        fft_size: 512,
+        levels: 4,
+    });
+    assert!(bands.iter().all(|b| b.fft_size >= 256));
+    assert_eq!(bands.last().map(|b| b.end_bin), Some(256));
+}
+
+// ---------------------------------------------------------------------------
+// fold_power
+// ---------------------------------------------------------------------------
+
+#[test]
+fn fold_same_size_is_copy() {
+    let src: Vec<f32> = (0..8u8).map(f32::from).collect();
+    let mut dst = vec![0.0f32; 8];
+    let band = ResolutionBand {
+        fft_size: 16,
+        start_bin: 0,
+        end_bin: 8,
+    };
+    fold_power(&mut dst, &src, &band, 16);
+    assert_eq!(dst, src);
+}
+
+#[test]
+fn fold_only_touches_its_band() {
+    let src = vec![1.0f32; 64];
+    let mut dst = vec![-1.0f32; 512];
+    let band = ResolutionBand {
+        fft_size: 128,
+        start_bin: 100,
+        end_bin: 200,
+    };
+    fold_power(&mut dst, &src, &band, 1024);
+
+    for (k, &v) in dst.iter().enumerate() {
+        if (100..200).contains(&k) {
+            assert!(v >= 0.0, "bin {k} inside band was not written");
+        } else {
+            assert!(
+                (v + 1.0).abs() < f32::EPSILON,
+                "bin {k} outside band was modified: {v}"
+            );
+        }
+    }
+}
+
+#[test]
+fn fold_preserves_band_power() {
+    let src: Vec<f32> =
+        (0..256u16).map(|i| f32::from(i % 7)).collect();
+    let mut dst = vec![0.0f32; 1024];
+    let band = ResolutionBand {
+        fft_size: 512,
+        start_bin: 0,
+        end_bin: 1024,
+    };
+    fold_power(&mut dst, &src, &band, 2048);
+
+    let src_sum: f32 = src.iter().sum();
+    let dst_sum: f32 = dst.iter().sum();
+    assert!(
+        (src_sum - dst_sum).abs() / src_sum < 0.02,
+        "folding should preserve power: src {src_sum}, dst {dst_sum}"
+    );
+}
+
+#[test]
+#[allow(clippy::cast_precision_loss)]
+fn short_window_tone_lands_on_matching_grid_bin() {
+    let hz = 3_000.0;
+    let short = power_spectrum(&sine(hz, 1.0, 512));
+    let mut grid = vec![0.0f32; FFT / 2];
+    let band = ResolutionBand {
+        fft_size: 512,
+        start_bin: 0,
+        end_bin: FFT / 2,
+    };
+    fold_power(&mut grid, &short, &band, FFT);
+
+    let expected = hz / (SR / FFT as f32);
+    let peak = argmax(&grid) as f32;
+    assert!(
+        (peak - expected).abs() <= 4.0,
+        "tone should peak near grid bin {expected}, got {peak}"
+    );
+}
@@ -0,0 +1,204 @@
+use lookas::analyzer::{
+    BandParams, DynamicsParams, ScalingMode, ScalingParams,
+    SpectrumAnalyzer,
+};
+use lookas::filterbank::{FilterbankParams, build_filterbank};
+use lookas::noise::{
+    GATE_MARGIN_DB, NoiseCalibrator, NoiseProfile, SPECTRAL_FLOOR,
+    subtract_noise,
+};
+
+// ---------------------------------------------------------------------------
+// helpers
+// ---------------------------------------------------------------------------
+
+const DT: f32 = 1.0 / 60.0;
+
+fn close(a: Option<&f32>, b: f32) -> bool {
+    a.is_some_and(|&a| (a - b).abs() < 1e-6)
+}
+
+fn flat_profile(fft_size: usize, pow: f32) -> NoiseProfile {
+    NoiseProfile {
+        sample_rate: 48_000.0,
+        fft_size,
+        floor_db: -70.0,
+        bins: vec![pow; fft_size / 2],
+    }
+}
+
+// ---------------------------------------------------------------------------
+// subtraction
+// ---------------------------------------------------------------------------
+
+#[test]
+fn subtraction_removes_noise_power() {
+    let out = subtract_noise(1.0, 0.25);
+    assert!((out - 0.75).abs() < 1e-6, "got {out}");
+}
+
+#[test]
+fn subtraction_keeps_spectral_floor() {
+    let floor = 0.5 * SPECTRAL_FLOOR;
+    let out = subtract_noise(0.5, 2.0);
+    assert!(
+        (out - floor).abs() < 1e-9,
+        "noise above signal should leave the floor, got {out}"
+    );
+}
+
+// ---------------------------------------------------------------------------
+// calibrator
+// ---------------------------------------------------------------------------
+
+#[test]
+fn calibrator_averages_frames() {
+    let mut cal = NoiseCalibrator::new(0.05);
+    assert!(!cal.push(&[1.0, 2.0], 1e-6, 0.02));
+    assert!(!cal.push(&[3.0, 4.0], 1e-6, 0.02));
+    assert!(cal.push(&[2.0, 3.0], 1e-6, 0.02));
+    assert!(cal.is_done());
+
+    let profile = cal.finish(48_000.0, 4);
+    assert!(profile.is_some(), "calibrator should produce a profile");
+    let profile = profile.unwrap_or_else(|| flat_profile(4, 0.0));
+    assert_eq!(profile.bins.len(), 2);
+    assert!(close(profile.bins.first(), 2.0));
+    assert!(close(profile.bins.get(1), 3.0));
+    assert!((profile.floor_db + 60.0).abs() < 1e-3);
+}
+
+#[test]
+fn calibrator_restarts_the_window_on_a_new_size() {
+    let mut cal = NoiseCalibrator::new(0.05);
+    assert!(!cal.push(&[1.0, 2.0], 1e-6, 0.02));
+    assert!(!cal.push(&[1.0, 2.0], 1e-6, 0.02));
+    assert!(!cal.push(&[1.0, 2.0, 3.0], 1e-6, 0.02));
+    assert!((cal.remaining_s() - 0.03).abs() < 1e-6);
+    assert!(!cal.push(&[1.0, 2.0, 3.0], 1e-6, 0.02));
+    assert!(cal.push(&[1.0, 2.0, 3.0], 1e-6, 0.02));
+}
+
+#[test]
+fn calibrator_without_frames_gives_nothing() {
+    let cal = NoiseCalibrator::new(1.0);
+    assert!(!cal.is_done());
+    assert!(cal.finish(48_000.0, 2048).is_none());
+}
+
+// ---------------------------------------------------------------------------
+// profile
+// ---------------------------------------------------------------------------
+
+#[test]
+fn gate_sits_above_floor() {
+    let profile = flat_profile(2048, 1e-8);
+    let gate = profile.gate_db();
+    assert!(
+        (gate - (profile.floor_db + GATE_MARGIN_DB)).abs() < 1e-6
+    );
+}
+
+#[test]
+fn gate_is_clamped() {
+    let mut profile = flat_profile(2048, 1e-8);
+    profile.floor_db = -200.0;
+    assert!(profile.gate_db() >= -90.0);
+    profile.floor_db = 0.0;
+    assert!(profile.gate_db() <= -20.0);
+}
+
+#[test]
+fn bins_for_same_grid_is_identity() {
+    let mut profile = flat_profile(8, 0.0);
+    profile.bins = vec![1.0, 2.0, 3.0, 4.0];
+    let out = profile.bins_for(48_000.0, 8);
+    assert_eq!(out, profile.bins);
+}
+
+#[test]
+fn bins_for_larger_fft_interpolates_and_rescales() {
+    let mut profile = flat_profile(8, 0.0);
+    profile.bins = vec![1.0, 2.0, 3.0, 4.0];
+    let out = profile.bins_for(48_000.0, 16);
+
+    assert_eq!(out.len(), 8);
+    // bin 1 of the finer grid sits halfway between source bins 0 and 1
+    assert!(close(out.get(1), 0.75), "got {out:?}");
+    assert!(close(out.get(2), 1.0), "got {out:?}");
+    // past the source range the last bin is held
+    assert!(close(out.get(7), 2.0), "got {out:?}");
+}
+
+#[test]
+fn profile_round_trips_through_disk() {
+    let dir = std::env::temp_dir()
+        .join(format!("lookas-noise-{}", std::process::id()));
+    let path = dir.join("profile.toml");
+    let profile = flat_profile(16, 1e-7);
+
+    let saved = profile.save_to(&path);
+    let loaded = NoiseProfile::load_from(&path);
+    let _ = std::fs::remove_dir_all(&dir);
+
+    assert!(saved.is_ok(), "save failed: {saved:?}");
+    assert!(
+        loaded.as_ref().is_ok_and(|p| *p == profile),
+        "load failed: {loaded:?}"
+    );
+}
+
+// ---------------------------------------------------------------------------
+// analyzer integration
+// ---------------------------------------------------------------------------
+
+#[test]
+fn analyzer_subtracts_noise_profile() {
+    let params = ScalingParams {
+        mode: ScalingMode::Absolute,
+        floor_db: -90.0,
+        ceiling_db: -20.0,
+    };
+    let make = || {
+        let mut sa = SpectrumAnalyzer::new(1024);
+        sa.filters = build_filterbank(FilterbankParams {
+            sr: 48_000.0,
+            fft_size: 2048,
+            bands: 16,
+            fmin: 30.0,
+            fmax: 16_000.0,
+        });
+        sa.resize(16);
+        sa.spec_pow_smooth.fill(1e-8);
+        sa
+    };
+
+    let mut plain = make();
+    let mut cleaned = make();
+    cleaned.noise_pow = flat_profile(2048, 1e-8).bins;
+
+    let bands = BandParams {
+        scaling: params,
+        dynamics: DynamicsParams::DEFAULT,
+    };
+    plain.analyze_bands(&bands, DT, true);
+    cleaned.analyze_bands(&bands, DT, true);
+
+    for (i, (&p, &c)) in plain
+        .bars_target
+        .iter()
+        .zip(&cleaned.bars_target)
+        .enumerate()
+    {
+        assert!(
+            c <= p,
+            "band {i}: subtraction should not raise the level"
+        );
+    }
+    let plain_sum = plain.bars_target.iter().sum::<f32>();
+    let cleaned_sum = cleaned.bars_target.iter().sum::<f32>();
+    assert!(
+        cleaned_sum < plain_sum,
+        "noise at the input level should be pulled down: {plain_sum} vs {cleaned_sum}"
+    );
+}
@@ -0,0 +1,67 @@
+use lookas::config::RgbColor;
+use lookas::render::{OverlayLayer, draw_blocks_overlay, layout_for};
+
+// ---------------------------------------------------------------------------
+// helpers
+// ---------------------------------------------------------------------------
+
+const RED: RgbColor = RgbColor { r: 255, g: 0, b: 0 };
+const BLUE: RgbColor = RgbColor { r: 0, g: 0, b: 255 };
+
+fn render_overlay(front: f32, back: f32) -> Vec<String> {
+    let lay = layout_for(12, 8, 0);
+    let (front, back) = (vec![front; lay.bars], vec![back; lay.bars]);
+    let layers = [
+        &OverlayLayer {
+            bars: &front,
+            color: RED,
+        },
+        &OverlayLayer {
+            bars: &back,
+            color: BLUE,
+        },
+    ];
+    let mut out = Vec::new();
+    assert!(
+        draw_blocks_overlay(&mut out, layers, 12, 8, &lay).is_ok()
+    );
+    String::from_utf8(out)
+        .unwrap_or_default()
+        .split("\r\n")
+        .map(String::from)
+        .collect()
+}
+
+// ---------------------------------------------------------------------------
+// overlay
+// ---------------------------------------------------------------------------
+
+#[test]
+fn overlay_blends_shared_cells_and_colors_the_taller_bar() {
+    let rows = render_overlay(0.25, 0.75);
+    assert_eq!(rows.len(), 8);
+
+    // the back bar alone reaches rows 3 to 6 from the bottom
+    let above = rows.get(2).map_or("", String::as_str);
+    assert!(above.contains("\x1b[38;2;0;0;255m"), "{above:?}");
+    assert!(above.contains('\u{2588}'));
+
+    // both bars cover the bottom two rows; the color is set once
+    let shared = rows.get(6).map_or("", String::as_str);
+    assert!(shared.contains("\x1b[38;2;128;0;128m"), "{shared:?}");
+
+    let top = rows.first().map_or("", String::as_str);
+    assert!(top.trim().is_empty(), "{top:?}");
+}
+
+#[test]
+fn overlay_of_equal_bars_draws_one_blended_bar() {
+    let rows = render_overlay(0.5, 0.5);
+    let text = rows.concat();
+    assert!(!text.contains("\x1b[38;2;0;0;255m"));
+    assert!(!text.contains("\x1b[38;2;255;0;0m"));
+    assert_eq!(
+        text.matches('\u{2588}').count(),
+        4 * 2 * layout_for(12, 8, 0).bars
+    );
+}
@@ -0,0 +1,170 @@
+mod common;
+
+use common::{SR, mix, power_spectrum, sine};
+use lookas::partials::{
+    Interpolation, PartialParams, Reassigner, interpolate_peak,
+    strongest_partials,
+};
+
+const FFT: usize = 2048;
+const BIN_HZ: f32 = SR / 2048.0;
+
+// ---------------------------------------------------------------------------
+// helpers
+// ---------------------------------------------------------------------------
+
+/// Frequency of the strongest partial of `samples`.
+fn strongest_hz(
+    samples: &[f32],
+    interpolation: Interpolation,
+) -> f32 {
+    let mut out = Vec::new();
+    strongest_partials(
+        &power_spectrum(samples),
+        &PartialParams {
+            bin_hz: BIN_HZ,
+            interpolation,
+            count: 1,
+        },
+        &mut out,
+    );
+    out.first().map_or(0.0, |p| p.hz)
+}
+
+// ---------------------------------------------------------------------------
+// interpolation
+// ---------------------------------------------------------------------------
+
+#[test]
+fn symmetric_peak_stays_on_its_bin() {
+    for interpolation in
+        [Interpolation::Quadratic, Interpolation::Gaussian]
+    {
+        let (delta, power) =
+            interpolate_peak((0.5, 1.0, 0.5), interpolation);
+        assert!(delta.abs() < 1e-6);
+        assert!((power - 1.0).abs() < 1e-6);
+    }
+}
+
+#[test]
+fn gaussian_is_exact_on_a_gaussian() {
+    let g = |x: f32| (-(x - 0.3) * (x - 0.3)).exp();
+    let (delta, power) = interpolate_peak(
+        (g(-1.0), g(0.0), g(1.0)),
+        Interpolation::Gaussian,
+    );
+
+    assert!((delta - 0.3).abs() < 1e-4, "got {delta}");
+    assert!((power - 1.0).abs() < 1e-4, "got {power}");
+}
+
+#[test]
+fn interpolation_beats_the_bin_grid() {
+    let hz = 0.37f32.mul_add(BIN_HZ, 1000.0);
+    let samples = sine(hz, 0.5, FFT);
+    let on_grid = (hz / BIN_HZ).round() * BIN_HZ;
+
+    for (interpolation, tol) in [
+        (Interpolation::Quadratic, 0.1 * BIN_HZ),
+        (Interpolation::Gaussian, 0.03 * BIN_HZ),
+    ] {
+        let err = (strongest_hz(&samples, interpolation) - hz).abs();
+        assert!(err < tol, "{interpolation:?}: error {err} Hz");
+        assert!(err < (on_grid - hz).abs());
+    }
+}
+
+// ---------------------------------------------------------------------------
+// top-N
+// ---------------------------------------------------------------------------
+
+#[test]
+fn strongest_partials_are_loudest_first() {
+    let samples = mix(
+        &mix(&sine(440.0, 0.1, FFT), &sine(1250.0, 0.5, FFT)),
+        &sine(3300.0, 0.25, FFT),
+    );
+    let mut out = Vec::new();
+    strongest_partials(
+        &power_spectrum(&samples),
+        &PartialParams {
+            bin_hz: BIN_HZ,
+            interpolation: Interpolation::Gaussian,
+            count: 3,
+        },
+        &mut out,
+    );
+
+    let hz: Vec<f32> = out.iter().map(|p| p.hz).collect();
+    assert_eq!(out.len(), 3);
+    for (got, want) in hz.iter().zip([1250.0, 3300.0, 440.0]) {
+        assert!((got - want).abs() < 2.0, "got {hz:?}");
+    }
+    assert!(out.windows(2).all(|w| {
+        w.first().zip(w.get(1)).is_some_and(|(a, b)| a.db >= b.db)
+    }));
+}
+
+#[test]
+fn partial_level_reads_in_dbfs() {
+    let mut out = Vec::new();
+    strongest_partials(
+        &power_spectrum(&sine(
+            0.5f32.mul_add(BIN_HZ, 1000.0),
+            0.5,
+            FFT,
+        )),
+        &PartialParams {
+            bin_hz: BIN_HZ,
+            interpolation: Interpolation::Gaussian,
+            count: 1,
+        },
+        &mut out,
+    );
+
+    let db = out.first().map_or(f32::NEG_INFINITY, |p| p.db);
+    assert!((db + 6.02).abs() < 0.5, "got {db}");
+}
+
+#[test]
+fn silence_has_no_partials() {
+    let mut out = Vec::new();
+    strongest_partials(
+        &[0.0; 64],
+        &PartialParams {
+            bin_hz: BIN_HZ,
+            interpolation: Interpolation::Quadratic,
+            count: 4,
+        },
+        &mut out,
+    );
+    assert!(out.is_empty());
+}
+
+// ---------------------------------------------------------------------------
+// reassignment
+// ---------------------------------------------------------------------------
+
+#[test]
+fn reassignment_recovers_off_bin_frequency() {
+    let mut reassigner = Reassigner::new(FFT);
+    for frac in [-0.45f32, -0.2, 0.0, 0.3, 0.49] {
+        let hz = (200.0 + frac) * BIN_HZ;
+        assert!(reassigner.process(&sine(hz, 0.5, FFT)));
+
+        let got = reassigner.frequency(200, BIN_HZ);
+        assert!(
+            got.is_some_and(|f| (f - hz).abs() < 0.01 * BIN_HZ),
+            "{hz} Hz: got {got:?}"
+        );
+    }
+}
+
+#[test]
+fn reassignment_skips_empty_bins_and_wrong_lengths() {
+    let mut reassigner = Reassigner::new(FFT);
+    assert!(!reassigner.process(&[0.0; 16]));
+    assert!(reassigner.process(&vec![0.0; FFT]));
+    assert!(reassigner.frequency(100, BIN_HZ).is_none());
+}
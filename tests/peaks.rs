@@ -0,0 +1,150 @@
+use lookas::analyzer::{PeakParams, SpectrumAnalyzer};
+use lookas::config::RgbColor;
+use lookas::render::{
+    PeakCaps, bar_glyph, draw_blocks_vertical, layout_for,
+};
+
+// ---------------------------------------------------------------------------
+// helpers
+// ---------------------------------------------------------------------------
+
+const DT: f32 = 1.0 / 60.0;
+
+const PARAMS: PeakParams = PeakParams {
+    hold_s: 0.5,
+    gravity: 2.0,
+};
+
+fn make_analyzer(bars: usize) -> SpectrumAnalyzer {
+    let mut sa = SpectrumAnalyzer::new(16);
+    sa.resize(bars);
+    sa
+}
+
+fn first(v: &[f32]) -> f32 {
+    v.first().copied().unwrap_or(f32::NAN)
+}
+
+// ---------------------------------------------------------------------------
+// update_peaks
+// ---------------------------------------------------------------------------
+
+#[test]
+fn cap_follows_rising_bar() {
+    let mut sa = make_analyzer(2);
+    sa.bars_y = vec![0.4, 0.7];
+    sa.update_peaks(&PARAMS, DT);
+    assert_eq!(sa.peak_y, sa.bars_y);
+}
+
+#[test]
+fn cap_holds_before_falling() {
+    let mut sa = make_analyzer(1);
+    sa.bars_y = vec![0.8];
+    sa.update_peaks(&PARAMS, DT);
+
+    sa.bars_y = vec![0.0];
+    for _ in 0..25 {
+        sa.update_peaks(&PARAMS, DT);
+    }
+    assert!(
+        (first(&sa.peak_y) - 0.8).abs() < f32::EPSILON,
+        "cap should hold for 0.5 s, got {}",
+        first(&sa.peak_y)
+    );
+
+    for _ in 0..20 {
+        sa.update_peaks(&PARAMS, DT);
+    }
+    assert!(first(&sa.peak_y) < 0.8, "cap should fall after hold");
+}
+
+#[test]
+fn cap_falls_with_acceleration_and_rests_on_bar() {
+    let mut sa = make_analyzer(1);
+    let params = PeakParams {
+        hold_s: 0.0,
+        gravity: 2.0,
+    };
+    sa.bars_y = vec![1.0];
+    sa.update_peaks(&params, DT);
+    sa.bars_y = vec![0.2];
+
+    let mut prev = first(&sa.peak_y);
+    let mut last_drop = 0.0f32;
+    for _ in 0..30 {
+        sa.update_peaks(&params, DT);
+        let cur = first(&sa.peak_y);
+        let drop = prev - cur;
+        if cur > 0.2 {
+            assert!(drop > last_drop, "fall should accelerate");
+        }
+        last_drop = drop;
+        prev = cur;
+    }
+
+    for _ in 0..120 {
+        sa.update_peaks(&params, DT);
+    }
+    assert!((first(&sa.peak_y) - 0.2).abs() < f32::EPSILON);
+}
+
+// ---------------------------------------------------------------------------
+// rendering
+// ---------------------------------------------------------------------------
+
+fn render(caps: Option<&PeakCaps<'_>>) -> String {
+    let lay = layout_for(12, 8, 0);
+    let bars = vec![0.25; lay.bars];
+    let mut fulls = vec![0; lay.bars];
+    let mut fracs = vec![0.0; lay.bars];
+    let mut out = Vec::new();
+    let drawn = draw_blocks_vertical(
+        &mut out, &bars, 12, 8, &lay, &mut fulls, &mut fracs, caps,
+    );
+    assert!(drawn.is_ok());
+    String::from_utf8(out).unwrap_or_default()
+}
+
+#[test]
+fn caps_render_above_bars_in_their_own_color() {
+    let lay = layout_for(12, 8, 0);
+    let heights = vec![0.75; lay.bars];
+    let caps = PeakCaps {
+        heights: &heights,
+        color: RgbColor { r: 255, g: 0, b: 0 },
+        bar_color: RgbColor::WHITE,
+    };
+    let text = render(Some(&caps));
+
+    let rows: Vec<&str> = text.split("\r\n").collect();
+    // 8 rows: the cap tops out at row 6 from the bottom, i.e. line 2
+    let cap_line = rows.get(2).copied().unwrap_or("");
+    assert!(cap_line.contains('\u{2594}'), "cap glyph missing");
+    assert!(cap_line.contains("\x1b[38;2;255;0;0m"));
+    assert!(cap_line.contains("\x1b[38;2;255;255;255m"));
+
+    let caps_drawn = text.matches('\u{2594}').count();
+    assert_eq!(caps_drawn, lay.bars * 2, "one cap per bar column");
+}
+
+#[test]
+fn caps_hidden_inside_bars() {
+    let lay = layout_for(12, 8, 0);
+    let heights = vec![0.1; lay.bars];
+    let caps = PeakCaps {
+        heights: &heights,
+        color: RgbColor { r: 255, g: 0, b: 0 },
+        bar_color: RgbColor::WHITE,
+    };
+    assert_eq!(render(Some(&caps)), render(None));
+}
+
+#[test]
+fn bar_glyph_matches_the_drawn_bar() {
+    assert_eq!(bar_glyph(2, 0.5, 0), "\u{2588}");
+    assert_eq!(bar_glyph(2, 0.5, 1), "\u{2588}");
+    assert_eq!(bar_glyph(2, 0.5, 2), "\u{2584}");
+    assert_eq!(bar_glyph(2, 0.5, 3), " ");
+    assert_eq!(bar_glyph(2, 0.0, 2), " ");
+}
@@ -0,0 +1,282 @@
+use lookas::analyzer::{
+    FlowSpringParams, MAX_ACCUM_S, PHYSICS_STEP_S, PhysicsModel,
+    SpectrumAnalyzer,
+};
+use lookas::filterbank::{FilterbankParams, build_filterbank};
+
+// ---------------------------------------------------------------------------
+// helpers
+// ---------------------------------------------------------------------------
+
+const DT: f32 = 1.0 / 60.0;
+const BANDS: usize = 8;
+
+const MODELS: [PhysicsModel; 5] = [
+    PhysicsModel::Spring,
+    PhysicsModel::Gravity,
+    PhysicsModel::Ema,
+    PhysicsModel::Monstercat,
+    PhysicsModel::BandSpring,
+];
+
+const fn params(model: PhysicsModel) -> FlowSpringParams {
+    FlowSpringParams {
+        model,
+        flow_k: 0.18,
+        spr_k: 60.0,
+        spr_zeta: 1.0,
+        gravity: 4.0,
+        ema_tau_s: 0.08,
+        monstercat: 1.5,
+        silence_tau_s: 0.22,
+    }
+}
+
+fn make_analyzer() -> SpectrumAnalyzer {
+    let mut sa = SpectrumAnalyzer::new(1024);
+    sa.filters = build_filterbank(FilterbankParams {
+        sr: 44_100.0,
+        fft_size: 2048,
+        bands: BANDS,
+        fmin: 30.0,
+        fmax: 16_000.0,
+    });
+    sa.resize(BANDS);
+    sa
+}
+
+fn run(
+    sa: &mut SpectrumAnalyzer,
+    p: &FlowSpringParams,
+    frames: usize,
+    gate_open: bool,
+) -> f32 {
+    let mut max_y = 0.0f32;
+    for _ in 0..frames {
+        sa.apply_flow_and_spring(p, DT, gate_open);
+        max_y = sa.bars_y.iter().fold(max_y, |m, &y| m.max(y));
+    }
+    max_y
+}
+
+// ---------------------------------------------------------------------------
+// all models
+// ---------------------------------------------------------------------------
+
+#[test]
+fn every_model_settles_on_target() {
+    for model in MODELS {
+        let mut sa = make_analyzer();
+        sa.bars_target = vec![0.6; BANDS];
+        run(&mut sa, &params(model), 300, true);
+
+        for (i, &y) in sa.bars_y.iter().enumerate() {
+            assert!(
+                (y - 0.6).abs() < 0.02,
+                "{model:?} bar {i} should settle on 0.6, got {y}"
+            );
+        }
+    }
+}
+
+#[test]
+fn every_model_falls_back_when_target_drops() {
+    for model in MODELS {
+        let mut sa = make_analyzer();
+        sa.bars_target = vec![0.9; BANDS];
+        run(&mut sa, &params(model), 120, true);
+        sa.bars_target = vec![0.1; BANDS];
+        run(&mut sa, &params(model), 300, true);
+
+        for (i, &y) in sa.bars_y.iter().enumerate() {
+            assert!(
+                (y - 0.1).abs() < 0.02,
+                "{model:?} bar {i} should fall to 0.1, got {y}"
+            );
+        }
+    }
+}
+
+#[test]
+fn every_model_decays_to_silence() {
+    for model in MODELS {
+        let mut sa = make_analyzer();
+        sa.bars_y = vec![1.0; BANDS];
+        sa.bars_target = vec![0.0; BANDS];
+        run(&mut sa, &params(model), 200, false);
+
+        for (i, &y) in sa.bars_y.iter().enumerate() {
+            assert!(
+                y < 0.01,
+                "{model:?} bar {i} should decay when gated, got {y}"
+            );
+        }
+    }
+}
+
+#[test]
+fn damped_models_do_not_overshoot() {
+    for model in MODELS {
+        let mut sa = make_analyzer();
+        sa.bars_target = vec![0.5; BANDS];
+        let max_y = run(&mut sa, &params(model), 300, true);
+        assert!(
+            max_y < 0.52,
+            "{model:?} should not overshoot: max_y = {max_y}"
+        );
+    }
+}
+
+// ---------------------------------------------------------------------------
+// model specifics
+// ---------------------------------------------------------------------------
+
+#[test]
+fn gravity_rises_instantly_and_accelerates_down() {
+    let p = params(PhysicsModel::Gravity);
+    let mut sa = make_analyzer();
+    sa.bars_target = vec![1.0; BANDS];
+    run(&mut sa, &p, 1, true);
+    assert!(sa.bars_y.iter().all(|&y| (y - 1.0).abs() < 1e-6));
+
+    sa.bars_target = vec![0.0; BANDS];
+    let y0 = sa.bars_y.first().copied().unwrap_or(0.0);
+    run(&mut sa, &p, 5, true);
+    let y1 = sa.bars_y.first().copied().unwrap_or(0.0);
+    run(&mut sa, &p, 5, true);
+    let y2 = sa.bars_y.first().copied().unwrap_or(0.0);
+    assert!(
+        y1 - y2 > y0 - y1,
+        "fall should speed up: {y0} -> {y1} -> {y2}"
+    );
+}
+
+#[test]
+fn ema_rises_gradually() {
+    let mut sa = make_analyzer();
+    sa.bars_target = vec![1.0; BANDS];
+    run(&mut sa, &params(PhysicsModel::Ema), 1, true);
+    for &y in &sa.bars_y {
+        assert!(y > 0.0 && y < 0.5, "EMA should not snap, got {y}");
+    }
+}
+
+#[test]
+fn monstercat_spreads_peaks_to_neighbours() {
+    let mut sa = make_analyzer();
+    sa.bars_target = vec![0.0; BANDS];
+    if let Some(t) = sa.bars_target.get_mut(3) {
+        *t = 0.9;
+    }
+    run(&mut sa, &params(PhysicsModel::Monstercat), 1, true);
+
+    let y = |i: usize| sa.bars_y.get(i).copied().unwrap_or(0.0);
+    assert!((y(3) - 0.9).abs() < 1e-6);
+    assert!((y(2) - 0.6).abs() < 1e-5, "left neighbour {}", y(2));
+    assert!((y(4) - 0.6).abs() < 1e-5, "right neighbour {}", y(4));
+    assert!((y(5) - 0.4).abs() < 1e-5, "second neighbour {}", y(5));
+}
+
+#[test]
+fn band_spring_moves_treble_faster() {
+    let mut sa = make_analyzer();
+    sa.bars_target = vec![1.0; BANDS];
+    let p = FlowSpringParams {
+        flow_k: 0.0,
+        ..params(PhysicsModel::BandSpring)
+    };
+    run(&mut sa, &p, 5, true);
+
+    let bass = sa.bars_y.first().copied().unwrap_or(0.0);
+    let treble = sa.bars_y.last().copied().unwrap_or(0.0);
+    assert!(
+        treble > bass,
+        "stiffer treble springs should rise first: {bass} vs {treble}"
+    );
+}
+
+// ---------------------------------------------------------------------------
+// fixed timestep
+// ---------------------------------------------------------------------------
+
+/// Runs one second of a step response at the given frame time.
+#[allow(
+    clippy::cast_possible_truncation,
+    clippy::cast_sign_loss,
+    clippy::cast_precision_loss
+)]
+fn second_at(frame_s: f32) -> Vec<f32> {
+    let mut sa = make_analyzer();
+    sa.bars_target = vec![0.8; BANDS];
+    let p = FlowSpringParams {
+        spr_zeta: 0.4,
+        ..params(PhysicsModel::Spring)
+    };
+    let count = (1.0 / frame_s).round() as usize;
+    for _ in 0..count {
+        sa.apply_flow_and_spring(&p, frame_s, true);
+    }
+    sa.bars_render
+}
+
+#[test]
+fn motion_is_independent_of_frame_rate() {
+    let slow = second_at(1.0 / 20.0);
+    let fast = second_at(1.0 / 125.0);
+    for (i, (s, f)) in slow.iter().zip(&fast).enumerate() {
+        assert!(
+            (s - f).abs() < 0.02,
+            "bar {i}: 20 fps {s} vs 125 fps {f}"
+        );
+    }
+}
+
+#[test]
+fn stall_does_not_destabilise_stiff_spring() {
+    let mut sa = make_analyzer();
+    sa.bars_target = vec![0.5; BANDS];
+    let p = FlowSpringParams {
+        spr_k: 200.0,
+        ..params(PhysicsModel::Spring)
+    };
+    // a two-second hitch arrives as one huge frame
+    sa.apply_flow_and_spring(&p, 2.0, true);
+    for &y in &sa.bars_y {
+        assert!(y.is_finite() && y <= 0.52, "stalled step gave {y}");
+    }
+    assert!(sa.physics_acc_s < PHYSICS_STEP_S);
+}
+
+#[test]
+fn stall_is_capped_at_max_accumulation() {
+    let p = params(PhysicsModel::Ema);
+    let mut stalled = make_analyzer();
+    let mut capped = make_analyzer();
+    stalled.bars_target = vec![1.0; BANDS];
+    capped.bars_target = vec![1.0; BANDS];
+
+    stalled.apply_flow_and_spring(&p, 5.0, true);
+    capped.apply_flow_and_spring(&p, MAX_ACCUM_S, true);
+
+    for (s, c) in stalled.bars_y.iter().zip(&capped.bars_y) {
+        assert!((s - c).abs() < 1e-6, "stall {s} vs cap {c}");
+    }
+}
+
+#[test]
+fn render_interpolates_between_steps() {
+    let mut sa = make_analyzer();
+    sa.bars_target = vec![1.0; BANDS];
+    let p = params(PhysicsModel::Ema);
+    sa.apply_flow_and_spring(&p, PHYSICS_STEP_S * 2.5, true);
+
+    for ((&prev, &cur), &drawn) in
+        sa.bars_prev.iter().zip(&sa.bars_y).zip(&sa.bars_render)
+    {
+        assert!(prev < cur, "EMA should still be rising");
+        assert!(
+            drawn > prev && drawn < cur,
+            "render {drawn} should sit between {prev} and {cur}"
+        );
+    }
+}
@@ -0,0 +1,205 @@
+mod common;
+
+use common::{SR, sine};
+use lookas::dsp::a_weighting;
+use lookas::spl::{
+    ExposureStandard, FrequencyWeighting, SplMeter, SplParams,
+    TimeWeighting, WeightingFilter,
+};
+
+// ---------------------------------------------------------------------------
+// helpers
+// ---------------------------------------------------------------------------
+
+/// Number of samples in `seconds`.
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn samples(seconds: f32) -> usize {
+    (seconds * SR) as usize
+}
+
+fn meter(
+    frequency: FrequencyWeighting,
+    time: TimeWeighting,
+) -> SplMeter {
+    SplMeter::new(
+        SR,
+        SplParams {
+            frequency,
+            time,
+            ..SplParams::DEFAULT
+        },
+    )
+}
+
+// ---------------------------------------------------------------------------
+// frequency weighting
+// ---------------------------------------------------------------------------
+
+#[test]
+fn filters_follow_the_weighting_curves() {
+    let a = WeightingFilter::new(FrequencyWeighting::A, SR);
+    let c = WeightingFilter::new(FrequencyWeighting::C, SR);
+    // IEC 61672-1 nominal C weighting, in dB.
+    for (hz, want_c) in [
+        (20.0, -6.2),
+        (50.0, -1.3),
+        (100.0, -0.3),
+        (500.0, 0.0),
+        (1000.0, 0.0),
+        (2000.0, -0.2),
+        (5000.0, -1.3),
+    ] {
+        let want_a = 20.0 * f64::from(a_weighting(hz)).log10();
+        let got_a = a.response_db(SR, hz);
+        let got_c = c.response_db(SR, hz);
+
+        assert!((got_a - want_a).abs() < 0.5, "A {hz} Hz: {got_a}");
+        assert!((got_c - want_c).abs() < 0.5, "C {hz} Hz: {got_c}");
+    }
+}
+
+#[test]
+fn filters_stay_within_class_1_tolerance_up_high() {
+    // IEC 61672-1 class 1 limits at 8, 10 and 12.5 kHz.
+    let a = WeightingFilter::new(FrequencyWeighting::A, SR);
+    for (hz, below, above) in [
+        (8000.0, 2.5, 1.5),
+        (10_000.0, 3.0, 2.0),
+        (12_500.0, 5.0, 3.0),
+    ] {
+        let want = 20.0 * f64::from(a_weighting(hz)).log10();
+        let err = a.response_db(SR, hz) - want;
+        assert!(err > -below && err < above, "{hz} Hz: {err:+.2} dB");
+    }
+}
+
+#[test]
+fn weighting_filters_are_unity_at_1_khz() {
+    let a = WeightingFilter::new(FrequencyWeighting::A, SR);
+    let c = WeightingFilter::new(FrequencyWeighting::C, SR);
+    assert!(a.response_db(SR, 1000.0).abs() < 1e-3);
+    assert!(c.response_db(SR, 1000.0).abs() < 1e-3);
+    assert!(c.response_db(SR, 31.5) > a.response_db(SR, 31.5) + 20.0);
+}
+
+#[test]
+fn full_scale_sine_reads_zero_dbfs() {
+    let mut m = meter(FrequencyWeighting::Z, TimeWeighting::Slow);
+    m.process(&sine(1000.0, 1.0, samples(5.0)));
+    let r = m.reading();
+
+    assert!(r.level.abs() < 0.1, "got {r:?}");
+    assert!(r.leq.abs() < 0.1, "got {r:?}");
+    assert!(r.dose.is_none());
+}
+
+// ---------------------------------------------------------------------------
+// time weighting
+// ---------------------------------------------------------------------------
+
+#[test]
+fn fast_falls_faster_than_slow() {
+    let tone = sine(1000.0, 0.5, samples(3.0));
+    let quiet = vec![0.0; 24_000];
+    let drop = |time| {
+        let mut m = meter(FrequencyWeighting::A, time);
+        m.process(&tone);
+        let before = m.reading().level;
+        m.process(&quiet);
+        before - m.reading().level
+    };
+
+    // 4.34 dB per time constant, over half a second.
+    let fast = drop(TimeWeighting::Fast);
+    let slow = drop(TimeWeighting::Slow);
+    assert!((fast - 17.4).abs() < 0.5, "fast {fast}");
+    assert!((slow - 2.2).abs() < 0.3, "slow {slow}");
+}
+
+#[test]
+fn impulse_rises_fast_and_holds() {
+    let burst = sine(1000.0, 0.5, samples(0.1));
+    let mut impulse =
+        meter(FrequencyWeighting::Z, TimeWeighting::Impulse);
+    let mut fast = meter(FrequencyWeighting::Z, TimeWeighting::Fast);
+    impulse.process(&burst);
+    fast.process(&burst);
+
+    assert!(impulse.reading().lmax > fast.reading().lmax + 2.0);
+
+    let quiet = vec![0.0; 12_000];
+    impulse.process(&quiet);
+    fast.process(&quiet);
+    assert!(impulse.reading().level > fast.reading().level + 5.0);
+}
+
+#[test]
+fn leq_and_lmax_cover_the_session() {
+    let mut m = meter(FrequencyWeighting::Z, TimeWeighting::Fast);
+    m.process(&sine(1000.0, 1.0, samples(1.0)));
+    m.process(&vec![0.0; 48_000]);
+    let r = m.reading();
+
+    assert!((r.leq + 3.01).abs() < 0.1, "got {r:?}");
+    assert!(r.lmax.abs() < 0.1, "got {r:?}");
+    assert!((r.elapsed_s - 2.0).abs() < 1e-3);
+
+    m.reset();
+    assert!(m.reading().lmax.is_infinite());
+}
+
+// ---------------------------------------------------------------------------
+// calibration and dose
+// ---------------------------------------------------------------------------
+
+#[test]
+fn calibration_maps_the_reference_to_its_level() {
+    // -26 dBFS, a common digital microphone sensitivity at 94 dB SPL.
+    let amp = 10f32.powf(-26.0 / 20.0);
+    let mut m = meter(FrequencyWeighting::A, TimeWeighting::Fast);
+    m.calibrate(94.0, 1.0);
+    assert!(m.calibrating());
+    m.process(&sine(1000.0, amp, samples(1.5)));
+
+    let offset = m.params().offset_db;
+    assert!(!m.calibrating());
+    assert!(
+        offset.is_some_and(|o| (o - 120.0).abs() < 0.1),
+        "{offset:?}"
+    );
+    assert!((m.reading().level - 94.0).abs() < 0.1);
+}
+
+#[test]
+fn dose_follows_the_exchange_rate() {
+    let niosh = ExposureStandard::Niosh.params();
+    let osha = ExposureStandard::Osha.params();
+    let hour = 3600.0;
+
+    assert!(
+        (niosh.dose_percent(85.0, 8.0 * hour) - 100.0).abs() < 1e-3
+    );
+    assert!(
+        (niosh.dose_percent(88.0, 4.0 * hour) - 100.0).abs() < 1e-3
+    );
+    assert!(
+        (osha.dose_percent(95.0, 4.0 * hour) - 100.0).abs() < 1e-3
+    );
+    assert!(niosh.dose_percent(79.0, 8.0 * hour) <= 0.0);
+}
+
+#[test]
+fn meter_accumulates_dose_once_calibrated() {
+    // The tone reads 100 dB SPL; NIOSH allows 15 minutes of it.
+    let mut m = SplMeter::new(
+        SR,
+        SplParams {
+            offset_db: Some(106.02),
+            ..SplParams::DEFAULT
+        },
+    );
+    m.process(&sine(1000.0, 0.5, samples(9.0)));
+    let dose = m.reading().dose;
+
+    assert!(dose.is_some_and(|d| (d - 1.0).abs() < 0.02), "{dose:?}");
+}
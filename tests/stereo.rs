@@ -0,0 +1,215 @@
+mod common;
+
+use common::{SR, noise, power_spectrum};
+use lookas::filterbank::{FilterbankParams, build_filterbank};
+use lookas::render::{BrailleCanvas, draw_gauge_end_labels};
+use lookas::stereo::{
+    Moments, StereoMeter, StereoSpectra, balance, band_balance,
+    correlation, goniometer_point,
+};
+
+const FFT: usize = 4096;
+
+// ---------------------------------------------------------------------------
+// helpers
+// ---------------------------------------------------------------------------
+
+#[allow(clippy::cast_precision_loss)]
+fn sine(hz: f32, phase: f32, n: usize) -> Vec<f32> {
+    (0..n)
+        .map(|i| {
+            (std::f32::consts::TAU * hz)
+                .mul_add(i as f32 / SR, phase)
+                .sin()
+        })
+        .collect()
+}
+
+fn scaled(samples: &[f32], gain: f32) -> Vec<f32> {
+    samples.iter().map(|&x| x * gain).collect()
+}
+
+// ---------------------------------------------------------------------------
+// correlation and balance
+// ---------------------------------------------------------------------------
+
+#[test]
+fn correlation_is_one_for_mono() {
+    let x = sine(440.0, 0.0, FFT);
+    let half = scaled(&x, 0.5);
+    assert!((correlation(&x, &half) - 1.0).abs() < 1e-4);
+}
+
+#[test]
+fn correlation_is_minus_one_for_inverted_channel() {
+    let x = sine(440.0, 0.0, FFT);
+    let inverted = scaled(&x, -1.0);
+    assert!((correlation(&x, &inverted) + 1.0).abs() < 1e-4);
+}
+
+#[test]
+fn correlation_is_near_zero_for_unrelated_channels() {
+    let c = correlation(&noise(1, 1.0, FFT), &noise(99, 1.0, FFT));
+    assert!(c.abs() < 0.1, "correlation {c}");
+
+    let quadrature = std::f32::consts::FRAC_PI_2;
+    let c = correlation(
+        &sine(1_000.0, 0.0, 4_800),
+        &sine(1_000.0, quadrature, 4_800),
+    );
+    assert!(c.abs() < 0.01, "correlation {c}");
+}
+
+#[test]
+fn silence_reads_zero() {
+    let silent = vec![0.0; FFT];
+    let x = sine(440.0, 0.0, FFT);
+    assert!(correlation(&silent, &silent).abs() < f32::EPSILON);
+    assert!(correlation(&x, &silent).abs() < f32::EPSILON);
+    assert!(balance(&silent, &silent).abs() < f32::EPSILON);
+    assert!(Moments::default().balance_db().abs() < f32::EPSILON);
+}
+
+#[test]
+fn balance_follows_the_louder_channel() {
+    let x = sine(440.0, 0.0, FFT);
+    let silent = vec![0.0; FFT];
+    assert!((balance(&x, &silent) + 1.0).abs() < 1e-6);
+    assert!((balance(&silent, &x) - 1.0).abs() < 1e-6);
+    assert!(balance(&x, &x).abs() < 1e-6);
+
+    // twice the amplitude on the right is four times the power
+    let loud = scaled(&x, 2.0);
+    assert!((balance(&x, &loud) - 0.6).abs() < 1e-4);
+    let db = Moments::measure(&x, &loud).balance_db();
+    assert!((db - 6.02).abs() < 0.01, "balance {db} dB");
+}
+
+#[test]
+fn band_balance_separates_channels_by_frequency() {
+    let filters = build_filterbank(FilterbankParams {
+        sr: SR,
+        fft_size: FFT,
+        bands: 16,
+        fmin: 50.0,
+        fmax: 10_000.0,
+    });
+    let left = power_spectrum(&sine(200.0, 0.0, FFT));
+    let right = power_spectrum(&sine(4_000.0, 0.0, FFT));
+
+    let mut bands = Vec::new();
+    band_balance(&filters, &left, &right, &mut bands);
+    assert_eq!(bands.len(), filters.len());
+
+    let at = |hz: f32| {
+        filters
+            .iter()
+            .zip(&bands)
+            .min_by(|a, b| {
+                (a.0.center_hz - hz)
+                    .abs()
+                    .total_cmp(&(b.0.center_hz - hz).abs())
+            })
+            .map(|(_, &b)| b)
+    };
+    assert!(at(200.0).is_some_and(|b| b < -0.9), "{bands:?}");
+    assert!(at(4_000.0).is_some_and(|b| b > 0.9), "{bands:?}");
+}
+
+#[test]
+fn meter_settles_on_the_signal() {
+    let x = sine(440.0, 0.0, 1_024);
+    let quiet = scaled(&x, 0.5);
+    let filters = build_filterbank(FilterbankParams {
+        sr: SR,
+        fft_size: 1_024,
+        bands: 8,
+        fmin: 50.0,
+        fmax: 10_000.0,
+    });
+    let (lp, rp) = (power_spectrum(&x), power_spectrum(&quiet));
+
+    let mut meter = StereoMeter::new();
+    for _ in 0..200 {
+        meter.process(&x, &quiet, 0.02);
+        meter.process_bands(
+            &StereoSpectra {
+                filters: &filters,
+                left_pow: &lp,
+                right_pow: &rp,
+            },
+            0.02,
+        );
+    }
+    assert!((meter.correlation() - 1.0).abs() < 1e-3);
+    assert!((meter.balance_db() + 6.02).abs() < 0.05);
+    assert_eq!(meter.bands().len(), 8);
+    assert!(meter.bands().iter().all(|&b| b <= 0.0));
+
+    meter.reset();
+    assert!(meter.correlation().abs() < f32::EPSILON);
+    assert!(meter.bands().is_empty());
+}
+
+// ---------------------------------------------------------------------------
+// goniometer
+// ---------------------------------------------------------------------------
+
+#[test]
+fn goniometer_points_follow_the_convention() {
+    let (x, y) = goniometer_point(0.5, 0.5);
+    assert!(x.abs() < 1e-6 && y > 0.0, "mono is vertical");
+
+    let (x, y) = goniometer_point(0.5, 0.0);
+    assert!(x < 0.0 && y > 0.0, "left alone leans left");
+    assert!((x + y).abs() < 1e-6);
+
+    let (x, y) = goniometer_point(0.0, 0.5);
+    assert!(x > 0.0 && y > 0.0, "right alone leans right");
+
+    let (x, y) = goniometer_point(0.5, -0.5);
+    assert!(x < 0.0 && y.abs() < 1e-6, "out of phase is horizontal");
+}
+
+#[test]
+fn braille_canvas_sets_the_right_dots() {
+    let mut canvas = BrailleCanvas::new(2, 2);
+    canvas.plot(-1.0, 1.0);
+    canvas.plot(1.0, -1.0);
+    canvas.plot(1.5, 0.0);
+
+    assert_eq!(canvas.cell(0, 0), '\u{2801}');
+    assert_eq!(canvas.cell(1, 1), '\u{2880}');
+    assert_eq!(canvas.cell(1, 0), '\u{2800}');
+    assert_eq!(canvas.cell(5, 0), '\u{2800}');
+
+    canvas.plot(-1.0, 1.0);
+    assert_eq!(canvas.cell(0, 0), '\u{2801}');
+    canvas.clear();
+    assert_eq!(canvas.cell(0, 0), '\u{2800}');
+}
+
+#[test]
+fn braille_rows_fill_their_width() {
+    let mut canvas = BrailleCanvas::new(12, 3);
+    canvas.plot(0.0, 0.0);
+    for row in 0..3 {
+        let mut out = Vec::new();
+        assert!(canvas.draw_row(&mut out, row).is_ok());
+        let text = String::from_utf8_lossy(&out);
+        assert_eq!(text.chars().count(), 12);
+    }
+}
+
+#[test]
+fn gauge_end_labels_fill_their_width() {
+    for width in [3, 7, 41] {
+        let mut out = Vec::new();
+        assert!(
+            draw_gauge_end_labels(&mut out, "-1", "+1", width)
+                .is_ok()
+        );
+        let text = String::from_utf8_lossy(&out);
+        assert_eq!(text.chars().count(), width, "{text:?}");
+    }
+}
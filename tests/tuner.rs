@@ -0,0 +1,283 @@
+mod common;
+
+use common::{SR, noise};
+use lookas::chroma::parse_pitch_class;
+use lookas::render::{
+    BIG_TEXT_ROWS, big_text_width, draw_big_text_row,
+    draw_gauge_labels, draw_gauge_needle, draw_gauge_scale,
+    gauge_col,
+};
+use lookas::tuner::{Temperament, Tuner, Tuning, Yin, YinParams};
+
+// ---------------------------------------------------------------------------
+// helpers
+// ---------------------------------------------------------------------------
+
+/// Harmonic tone with `1/k` partial amplitudes, like a plucked string.
+#[allow(clippy::cast_precision_loss)]
+fn harmonic(hz: f32, partials: usize, n: usize) -> Vec<f32> {
+    (0..n)
+        .map(|i| {
+            let t = i as f32 / SR;
+            (1..=partials)
+                .map(|k| {
+                    let k = k as f32;
+                    (std::f32::consts::TAU * hz * k * t).sin() / k
+                })
+                .sum()
+        })
+        .collect()
+}
+
+fn cents(a: f32, b: f32) -> f32 {
+    1200.0 * (a / b).log2()
+}
+
+fn detect(samples: &[f32]) -> Option<f32> {
+    Yin::new(YinParams::DEFAULT)
+        .detect(samples, SR)
+        .map(|p| p.hz)
+}
+
+fn window() -> usize {
+    YinParams::DEFAULT.window_len(SR)
+}
+
+// ---------------------------------------------------------------------------
+// YIN
+// ---------------------------------------------------------------------------
+
+#[test]
+fn detects_pure_tones_within_a_cent() {
+    for hz in [41.2f32, 82.41, 110.0, 196.0, 440.0, 1_318.5] {
+        let got = detect(&harmonic(hz, 1, window()));
+        assert!(
+            got.is_some_and(|g| cents(g, hz).abs() < 1.0),
+            "{hz} Hz: got {got:?}"
+        );
+    }
+}
+
+#[test]
+fn detects_fundamental_of_harmonic_tones() {
+    for hz in [55.0f32, 146.83, 329.63, 659.26] {
+        let got = detect(&harmonic(hz, 8, window()));
+        assert!(
+            got.is_some_and(|g| cents(g, hz).abs() < 1.0),
+            "{hz} Hz: got {got:?}"
+        );
+    }
+}
+
+#[test]
+fn detects_missing_fundamental() {
+    // partials 2..=6 of 100 Hz, with no energy at 100 Hz itself
+    #[allow(clippy::cast_precision_loss)]
+    let samples: Vec<f32> = (0..window())
+        .map(|i| {
+            let t = i as f32 / SR;
+            (2..=6u8)
+                .map(|k| {
+                    (std::f32::consts::TAU * 100.0 * f32::from(k) * t)
+                        .sin()
+                })
+                .sum()
+        })
+        .collect();
+    let got = detect(&samples);
+    assert!(
+        got.is_some_and(|g| cents(g, 100.0).abs() < 1.0),
+        "got {got:?}"
+    );
+}
+
+#[test]
+fn clarity_is_high_for_clean_tone() {
+    let est = Yin::new(YinParams::DEFAULT)
+        .detect(&harmonic(220.0, 4, window()), SR);
+    assert!(est.is_some_and(|e| e.clarity > 0.9), "got {est:?}");
+}
+
+#[test]
+fn noise_and_silence_are_unvoiced() {
+    assert!(detect(&noise(777, 1.0, window())).is_none());
+    assert!(detect(&vec![0.0; window()]).is_none());
+}
+
+#[test]
+fn short_input_is_rejected() {
+    assert!(detect(&harmonic(440.0, 1, 16)).is_none());
+}
+
+// ---------------------------------------------------------------------------
+// Tuner window
+// ---------------------------------------------------------------------------
+
+#[test]
+fn tuner_follows_pushed_audio() {
+    let mut tuner = Tuner::new(YinParams::DEFAULT, SR);
+    let tone = harmonic(196.0, 4, window() * 2);
+    for chunk in tone.chunks(800) {
+        tuner.push(chunk);
+    }
+    let got = tuner.estimate().map(|p| p.hz);
+    assert!(
+        got.is_some_and(|g| cents(g, 196.0).abs() < 1.0),
+        "got {got:?}"
+    );
+
+    tuner.reset();
+    assert!(tuner.estimate().is_none());
+}
+
+// ---------------------------------------------------------------------------
+// tuning and temperament
+// ---------------------------------------------------------------------------
+
+#[test]
+fn equal_temperament_reads_notes_and_cents() {
+    let tuning = Tuning::DEFAULT;
+    let r = tuning.nearest(445.0);
+    assert!(
+        r.is_some_and(|r| r.name() == "A"
+            && r.octave == 4
+            && (r.cents - cents(445.0, 440.0)).abs() < 0.01),
+        "got {r:?}"
+    );
+
+    let c4 = tuning.nearest(261.63);
+    assert!(
+        c4.is_some_and(|r| r.midi == 60 && r.cents.abs() < 0.1),
+        "got {c4:?}"
+    );
+}
+
+#[test]
+fn reference_pitch_moves_every_note() {
+    let tuning = Tuning {
+        a4_hz: 442.0,
+        ..Tuning::DEFAULT
+    };
+    let a = tuning.nearest(442.0);
+    assert!(a.is_some_and(|r| r.midi == 69 && r.cents.abs() < 0.01));
+
+    let e5 = tuning.target_hz(76);
+    let expected = 442.0 * (7.0f32 / 12.0).exp2();
+    assert!((e5 - expected).abs() < 0.01, "got {e5}");
+}
+
+#[test]
+fn tempered_a_stays_on_reference() {
+    for temperament in [
+        Temperament::Just,
+        Temperament::Pythagorean,
+        Temperament::Werckmeister,
+    ] {
+        for root in 0..12 {
+            let tuning = Tuning {
+                temperament,
+                root,
+                ..Tuning::DEFAULT
+            };
+            assert!(
+                (tuning.target_hz(69) - 440.0).abs() < 1e-3,
+                "{temperament:?} root {root}"
+            );
+        }
+    }
+}
+
+#[test]
+fn just_intervals_are_pure() {
+    let tuning = Tuning {
+        temperament: Temperament::Just,
+        root: 0,
+        ..Tuning::DEFAULT
+    };
+    let c = tuning.target_hz(60);
+    let e = tuning.target_hz(64);
+    let g = tuning.target_hz(67);
+    assert!((e / c - 1.25).abs() < 1e-5, "major third {}", e / c);
+    assert!((g / c - 1.5).abs() < 1e-5, "fifth {}", g / c);
+}
+
+#[test]
+fn pythagorean_fifths_are_wide_of_equal() {
+    let tuning = Tuning {
+        temperament: Temperament::Pythagorean,
+        root: 2,
+        ..Tuning::DEFAULT
+    };
+    let fifth = tuning.offset_cents(9) - tuning.offset_cents(2);
+    assert!((fifth - 1.955).abs() < 0.01, "got {fifth}");
+}
+
+#[test]
+fn nearest_picks_tempered_target() {
+    // a just major third above C sits 13.7 cents below equal E, so a
+    // pure third should read as in tune
+    let tuning = Tuning {
+        temperament: Temperament::Just,
+        root: 0,
+        ..Tuning::DEFAULT
+    };
+    let e = tuning.target_hz(60) * 1.25;
+    let r = tuning.nearest(e);
+    assert!(
+        r.is_some_and(|r| r.midi == 64 && r.cents.abs() < 0.01),
+        "got {r:?}"
+    );
+    assert!(tuning.nearest(0.0).is_none());
+}
+
+#[test]
+fn pitch_class_names_parse() {
+    assert_eq!(parse_pitch_class("C"), Some(0));
+    assert_eq!(parse_pitch_class("f#"), Some(6));
+    assert_eq!(parse_pitch_class("Bb"), Some(10));
+    assert_eq!(parse_pitch_class("Cb"), Some(11));
+    assert_eq!(parse_pitch_class("H"), None);
+    assert_eq!(parse_pitch_class("C##"), None);
+}
+
+// ---------------------------------------------------------------------------
+// gauge
+// ---------------------------------------------------------------------------
+
+#[test]
+fn gauge_maps_cents_across_its_width() {
+    assert_eq!(gauge_col(0.0, 41), 20);
+    assert_eq!(gauge_col(-50.0, 41), 0);
+    assert_eq!(gauge_col(50.0, 41), 40);
+    assert_eq!(gauge_col(-200.0, 41), 0);
+    assert_eq!(gauge_col(25.0, 41), 30);
+}
+
+#[test]
+fn gauge_rows_fill_their_width() {
+    let width = 41;
+    let mut rows = [Vec::new(), Vec::new(), Vec::new()];
+    let [needle, scale, labels] = &mut rows;
+    assert!(draw_gauge_needle(needle, Some(10.0), width).is_ok());
+    assert!(draw_gauge_scale(scale, width).is_ok());
+    assert!(draw_gauge_labels(labels, width).is_ok());
+
+    for row in &rows {
+        let text = String::from_utf8_lossy(row);
+        assert_eq!(text.chars().count(), width, "{text:?}");
+    }
+    let needle = rows.first().map(|r| String::from_utf8_lossy(r));
+    let needle = needle.unwrap_or_default();
+    assert_eq!(needle.chars().position(|c| c != ' '), Some(24));
+}
+
+#[test]
+fn big_text_rows_match_reported_width() {
+    let text = "C#4";
+    for row in 0..BIG_TEXT_ROWS {
+        let mut out = Vec::new();
+        assert!(draw_big_text_row(&mut out, text, row).is_ok());
+        let line = String::from_utf8_lossy(&out);
+        assert_eq!(line.chars().count(), big_text_width(text));
+    }
+}
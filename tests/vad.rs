@@ -0,0 +1,218 @@
+mod common;
+
+use common::{SR, noise, power_spectrum, rng};
+use lookas::vad::{VadFeatures, VadInput, VadParams, VoiceDetector};
+
+const FFT: usize = 4096;
+const BIN_HZ: f32 = SR / 4096.0;
+const DT: f32 = 4096.0 / SR;
+/// 80 ms at 48 kHz.
+const CLICK_PERIOD: usize = 3840;
+
+// ---------------------------------------------------------------------------
+// helpers
+// ---------------------------------------------------------------------------
+
+/// A voiced vowel: harmonics of `f0` falling at 6 dB per octave and
+/// shaped by formants near 700, 1200 and 2600 Hz, as in /a/.
+#[allow(
+    clippy::cast_precision_loss,
+    clippy::cast_possible_truncation,
+    clippy::cast_sign_loss
+)]
+fn vowel(f0: f32) -> Vec<f32> {
+    let formant = |hz: f32| {
+        [700.0f32, 1200.0, 2600.0]
+            .iter()
+            .map(|&f| (-((hz - f) / 150.0).powi(2)).exp())
+            .sum::<f32>()
+    };
+    let mut out = vec![0.0f32; FFT];
+    for k in 1..=(4000.0 / f0) as usize {
+        let hz = f0 * k as f32;
+        let amp = 0.05 * (0.1 + formant(hz)) / k as f32;
+        for (i, v) in out.iter_mut().enumerate() {
+            *v += amp
+                * (std::f32::consts::TAU * hz * i as f32 / SR
+                    + k as f32)
+                    .sin();
+        }
+    }
+    out
+}
+
+/// Noise confined to the speech band: many sines at random phases.
+#[allow(clippy::cast_precision_loss)]
+fn band_noise(seed: u32) -> Vec<f32> {
+    let mut state = seed;
+    let mut out = vec![0.0f32; FFT];
+    for step in 40..720 {
+        let hz = 5.0 * step as f32;
+        let phase = std::f32::consts::PI * rng(&mut state);
+        for (i, v) in out.iter_mut().enumerate() {
+            *v += 0.002
+                * (std::f32::consts::TAU * hz * i as f32 / SR
+                    + phase)
+                    .sin();
+        }
+    }
+    out
+}
+
+/// A fan: mains hum and its harmonics over rumbling noise.
+#[allow(clippy::cast_precision_loss)]
+fn fan(seed: u32) -> Vec<f32> {
+    let mut state = seed;
+    let mut rumble = 0.0f32;
+    (0..FFT)
+        .map(|i| {
+            rumble = rumble.mul_add(0.99, 0.01 * rng(&mut state));
+            let t = i as f32 / SR;
+            let hum = [50.0f32, 100.0, 150.0]
+                .iter()
+                .map(|&hz| {
+                    0.05 * (std::f32::consts::TAU * hz * t).sin()
+                })
+                .sum::<f32>();
+            hum + rumble
+        })
+        .collect()
+}
+
+/// Keyboard clicks: short decaying bursts of noise every 80 ms.
+#[allow(clippy::cast_precision_loss)]
+fn clicks(seed: u32) -> Vec<f32> {
+    let mut state = seed;
+    (0..FFT)
+        .map(|i| {
+            let t = i.rem_euclid(CLICK_PERIOD) as f32 / SR;
+            0.3 * (-t / 0.002).exp() * rng(&mut state)
+        })
+        .collect()
+}
+
+fn features(samples: &[f32]) -> VadFeatures {
+    let input = VadInput {
+        spec_pow: &power_spectrum(samples),
+        bin_hz: BIN_HZ,
+        samples,
+        sample_rate: SR,
+    };
+    VadFeatures::measure(&input, &VadParams::DEFAULT)
+}
+
+/// Feeds `frames` loud frames of `samples` and returns whether voice
+/// was active after each.
+fn run(
+    detector: &mut VoiceDetector,
+    samples: &[f32],
+    frames: usize,
+) -> Vec<bool> {
+    let f = features(samples);
+    (0..frames)
+        .map(|_| detector.process(&f, true, DT))
+        .collect()
+}
+
+// ---------------------------------------------------------------------------
+// features
+// ---------------------------------------------------------------------------
+
+#[test]
+fn vowels_look_like_voice() {
+    for f0 in [110.0, 150.0, 220.0] {
+        let f = features(&vowel(f0));
+        assert!(f.voice_like(&VadParams::DEFAULT), "{f0} Hz: {f:?}");
+    }
+}
+
+#[test]
+fn noises_do_not_look_like_voice() {
+    let cases = [
+        ("white noise", noise(7, 0.1, FFT)),
+        ("speech-band noise", band_noise(7)),
+        ("fan", fan(7)),
+        ("keyboard", clicks(7)),
+    ];
+    for (name, samples) in cases {
+        let f = features(&samples);
+        assert!(!f.voice_like(&VadParams::DEFAULT), "{name}: {f:?}");
+    }
+}
+
+#[test]
+fn each_feature_rejects_its_noise() {
+    let white = features(&noise(3, 0.1, FFT));
+    assert!(white.speech_ratio < 0.3, "{white:?}");
+    assert!(white.zcr_hz > 8000.0, "{white:?}");
+
+    let band = features(&band_noise(3));
+    assert!(band.speech_ratio > 0.8, "{band:?}");
+    assert!(band.flatness > 0.3, "{band:?}");
+
+    let hum = features(&fan(3));
+    assert!(hum.speech_ratio < 0.2, "{hum:?}");
+}
+
+#[test]
+fn silence_has_no_speech() {
+    let f = features(&[0.0; FFT]);
+    assert!(f.speech_ratio.abs() < 1e-6);
+    assert!(!f.voice_like(&VadParams::DEFAULT));
+}
+
+// ---------------------------------------------------------------------------
+// detector
+// ---------------------------------------------------------------------------
+
+#[test]
+fn detector_opens_on_speech_only() {
+    let mut detector = VoiceDetector::new(VadParams::DEFAULT);
+    assert!(
+        run(&mut detector, &noise(1, 0.1, FFT), 20)
+            .iter()
+            .all(|&a| !a)
+    );
+    assert!(run(&mut detector, &fan(1), 20).iter().all(|&a| !a));
+    assert!(run(&mut detector, &clicks(1), 20).iter().all(|&a| !a));
+    assert!(run(&mut detector, &vowel(150.0), 4).iter().any(|&a| a));
+}
+
+#[test]
+fn detector_waits_for_onset() {
+    let mut detector = VoiceDetector::new(VadParams {
+        onset_s: 0.2,
+        ..VadParams::DEFAULT
+    });
+    let active = run(&mut detector, &vowel(150.0), 4);
+    // 0.085 s frames: the third one passes 0.2 s.
+    assert_eq!(active, [false, false, true, true]);
+}
+
+#[test]
+fn hangover_holds_then_closes() {
+    let mut detector = VoiceDetector::new(VadParams::DEFAULT);
+    assert!(run(&mut detector, &vowel(150.0), 5).iter().all(|&a| a));
+
+    // 0.3 s of hangover is a little under four frames.
+    let after = run(&mut detector, &noise(2, 0.1, FFT), 6);
+    assert_eq!(after, [true, true, true, false, false, false]);
+}
+
+#[test]
+fn quiet_speech_stays_closed() {
+    let mut detector = VoiceDetector::new(VadParams::DEFAULT);
+    let f = features(&vowel(150.0));
+    for _ in 0..10 {
+        assert!(!detector.process(&f, false, DT));
+    }
+}
+
+#[test]
+fn reset_closes() {
+    let mut detector = VoiceDetector::new(VadParams::DEFAULT);
+    let _ = run(&mut detector, &vowel(150.0), 5);
+    assert!(detector.active());
+    detector.reset();
+    assert!(!detector.active());
+}